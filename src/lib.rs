@@ -1,5 +1,12 @@
+#![allow(clippy::module_inception)]
+
+pub mod loader;
 pub mod model;
 mod parser;
+pub mod processing;
 
 // TODO Remove?
-pub use parser::{ParseResultExt, marshal_directive, parse_directive};
+pub use parser::{
+    ParseResultExt, marshal_directive, marshal_file, marshal_options, marshal_pragma,
+    parse_directive, parse_file, parse_pragma,
+};
@@ -0,0 +1,488 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use ariadne::{Cache, Report, Source};
+use chumsky::{Parser as _, error::Rich};
+use thiserror::Error;
+
+use crate::{
+    model::{Directive, DirectiveVariant, FileId, Ledger, Pragma, SourceSpan},
+    parser::{format_error_in_file, parse_file, parse_pragma},
+    processing::resolve_document_paths,
+};
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Include cycle: {} includes itself", path.display())]
+    IncludeCycle { path: PathBuf },
+    #[error("Invalid include pattern {pattern}: {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: glob::PatternError,
+    },
+    #[error("Include pattern {pattern} doesn't match any files")]
+    NoMatchingFiles { pattern: String },
+}
+
+/// A file of a ledger, identified by the [FileId] of its position in the [Loader]
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    path: PathBuf,
+    content: String,
+}
+
+impl SourceFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// Loader for a ledger spread over multiple files.
+/// Starting from the main file, `include` pragmas are resolved recursively. Include paths are
+/// relative to the including file and may be glob patterns like `"years/*.beancount"`.
+#[derive(Debug, Clone, Default)]
+pub struct Loader {
+    files: Vec<SourceFile>,
+}
+
+impl Loader {
+    /// Read the main file and all files it includes, directly or indirectly.
+    /// The main file gets the default [FileId], included files follow in the order they're included.
+    pub fn load(main_file: &Path) -> Result<Self, LoadError> {
+        let mut loader = Self::default();
+        loader.load_file(main_file, &mut Vec::new())?;
+        Ok(loader)
+    }
+
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    pub fn file(&self, file_id: FileId) -> Option<&SourceFile> {
+        self.files.get(file_id.index())
+    }
+
+    /// Parse all loaded files and merge their directives and pragmas into one ledger.
+    /// The spans of directives, postings and options refer to the [FileId] of the file they're from.
+    pub fn parse(&self) -> LoadedLedger<'_> {
+        let mut ledger = Ledger::new();
+        let mut directives = Vec::new();
+        let mut errors = Vec::new();
+        for (index, file) in self.files.iter().enumerate() {
+            let file_id = FileId::new(index);
+            let (output, file_errors) = parse_file().parse(file.content()).into_output_errors();
+            errors.extend(file_errors.into_iter().map(|error| (file_id, error)));
+            let Some(file_ledger) = output else {
+                continue;
+            };
+
+            for pragma in file_ledger.pragmas() {
+                match pragma {
+                    Pragma::Include(_) => {}
+                    Pragma::Option(option) => {
+                        let mut option = option.clone();
+                        if let Some(span) = option.span_mut() {
+                            span.set_file_id(file_id);
+                        }
+                        ledger.add_pragma(Pragma::Option(option));
+                    }
+                    _ => ledger.add_pragma(pragma.clone()),
+                }
+            }
+            let file_directives =
+                resolve_document_paths(file_ledger.into_directives(), file.path());
+            directives.extend(file_directives.into_iter().map(|mut directive| {
+                assign_file_id(&mut directive, file_id);
+                directive
+            }));
+        }
+
+        // stable sort, directives of the same date keep their order of appearance
+        directives.sort_by_key(|directive| *directive.date());
+        for directive in directives {
+            ledger.add_directive(directive);
+        }
+        LoadedLedger { ledger, errors }
+    }
+
+    /// Cache with the content of all loaded files, to render reports from
+    /// [LoadedLedger::get_formatted_errors] or [format_semantic_error](crate::processing::format_semantic_error)
+    pub fn report_cache(&self) -> impl Cache<FileId> + '_ {
+        ReportCache {
+            sources: self
+                .files
+                .iter()
+                .map(|file| {
+                    (
+                        file.path.display().to_string(),
+                        Source::from(file.content.as_str()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn load_file(
+        &mut self,
+        path: &Path,
+        include_stack: &mut Vec<PathBuf>,
+    ) -> Result<(), LoadError> {
+        let path = fs::canonicalize(path).map_err(|source| LoadError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        if include_stack.contains(&path) {
+            return Err(LoadError::IncludeCycle { path });
+        }
+        // a file included from several places is only loaded once
+        if self.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|source| LoadError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let included_paths = included_paths(&content, base_dir)?;
+        self.files.push(SourceFile {
+            path: path.clone(),
+            content,
+        });
+
+        include_stack.push(path);
+        for included_path in included_paths {
+            self.load_file(&included_path, include_stack)?;
+        }
+        include_stack.pop();
+        Ok(())
+    }
+}
+
+/// Ledger merged from all files of a [Loader], with directives sorted by date,
+/// and the parse errors found in the files
+#[derive(Debug)]
+pub struct LoadedLedger<'a> {
+    ledger: Ledger<'a>,
+    errors: Vec<(FileId, Rich<'a, char>)>,
+}
+
+impl<'a> LoadedLedger<'a> {
+    pub fn ledger(&self) -> &Ledger<'a> {
+        &self.ledger
+    }
+
+    pub fn into_ledger(self) -> Ledger<'a> {
+        self.ledger
+    }
+
+    pub fn errors(&self) -> &[(FileId, Rich<'a, char>)] {
+        &self.errors
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Reports for the parse errors, to be rendered with [Loader::report_cache]
+    pub fn get_formatted_errors(&self) -> Vec<Report<'a, SourceSpan>> {
+        self.errors
+            .iter()
+            .map(|(file_id, error)| format_error_in_file(error, *file_id))
+            .collect()
+    }
+}
+
+struct ReportCache<'a> {
+    sources: Vec<(String, Source<&'a str>)>,
+}
+
+impl<'a> Cache<FileId> for ReportCache<'a> {
+    type Storage = &'a str;
+
+    fn fetch(&mut self, id: &FileId) -> Result<&Source<&'a str>, impl std::fmt::Debug> {
+        self.sources
+            .get(id.index())
+            .map(|(_, source)| source)
+            .ok_or_else(|| format!("Unknown source {id}"))
+    }
+
+    fn display<'b>(&self, id: &'b FileId) -> Option<impl std::fmt::Display + 'b> {
+        self.sources.get(id.index()).map(|(path, _)| path.clone())
+    }
+}
+
+/// Paths of the files included by a ledger file, resolved relative to its directory.
+/// Malformed include pragmas are skipped here, they're reported when parsing the file.
+fn included_paths(content: &str, base_dir: &Path) -> Result<Vec<PathBuf>, LoadError> {
+    let mut paths = Vec::new();
+    for line in content.lines().filter(|line| line.starts_with("include")) {
+        let Ok(Pragma::Include(include)) = parse_pragma().lazy().parse(line).into_result() else {
+            continue;
+        };
+        let path = base_dir.join(include.path());
+        if is_glob_pattern(include.path()) {
+            paths.extend(expand_glob(&path)?);
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// Files matching a glob pattern, in alphabetical order
+fn expand_glob(pattern: &Path) -> Result<Vec<PathBuf>, LoadError> {
+    let pattern = pattern.to_string_lossy().into_owned();
+    let paths = glob::glob(&pattern)
+        .map_err(|source| LoadError::InvalidPattern {
+            pattern: pattern.clone(),
+            source,
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| LoadError::Io {
+            path: error.path().to_owned(),
+            source: error.into_error(),
+        })?;
+    if paths.is_empty() {
+        return Err(LoadError::NoMatchingFiles { pattern });
+    }
+    Ok(paths)
+}
+
+fn assign_file_id(directive: &mut Directive, file_id: FileId) {
+    if let Some(span) = directive.span_mut() {
+        span.set_file_id(file_id);
+    }
+    if let DirectiveVariant::Transaction(transaction) = directive.content_mut() {
+        for posting in transaction.postings_mut() {
+            if let Some(span) = posting.span_mut() {
+                span.set_file_id(file_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_files(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn file_names(loader: &Loader) -> Vec<String> {
+        loader
+            .files()
+            .iter()
+            .map(|file| {
+                file.path()
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn load_nested_includes_relative_to_including_file() {
+        let dir = write_files(&[
+            (
+                "main.beancount",
+                "option \"title\" \"Ledger\"\ninclude \"2024/ledger.beancount\" ; yearly file\n",
+            ),
+            ("2024/ledger.beancount", "include \"prices.beancount\"\n"),
+            ("2024/prices.beancount", "2024-01-01 price EUR 1.10 USD\n"),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+
+        assert_eq!(
+            file_names(&loader),
+            ["main.beancount", "ledger.beancount", "prices.beancount"]
+        );
+        let loaded = loader.parse();
+        assert!(!loaded.has_errors());
+        assert_eq!(loaded.ledger().directives().len(), 1);
+        assert_eq!(loaded.ledger().pragmas().len(), 1);
+    }
+
+    #[test]
+    fn load_glob_includes_and_sort_by_date() {
+        let dir = write_files(&[
+            (
+                "main.beancount",
+                "include \"years/*.beancount\"\n2024-06-01 open Assets:Bank\n",
+            ),
+            ("years/2023.beancount", "2023-01-01 open Assets:Cash\n"),
+            (
+                "years/2025.beancount",
+                "2025-01-01 * \"Coffee\"\n  Assets:Cash  -2.50 USD\n  Expenses:Coffee\n",
+            ),
+            ("years/notes.txt", "not a ledger\n"),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+
+        assert_eq!(
+            file_names(&loader),
+            ["main.beancount", "2023.beancount", "2025.beancount"]
+        );
+        let loaded = loader.parse();
+        let directives = loaded.ledger().directives();
+        let dates: Vec<String> = directives
+            .iter()
+            .map(|directive| directive.date().to_string())
+            .collect();
+        assert_eq!(dates, ["2023-01-01", "2024-06-01", "2025-01-01"]);
+        let file_ids: Vec<FileId> = directives
+            .iter()
+            .map(|directive| directive.span().unwrap().file_id())
+            .collect();
+        assert_eq!(file_ids, [FileId::new(1), FileId::new(0), FileId::new(2)]);
+        let posting = &directives[2].as_transaction().unwrap().postings()[0];
+        assert_eq!(posting.span().unwrap().file_id(), FileId::new(2));
+    }
+
+    #[test]
+    fn option_spans_refer_to_their_file() {
+        let dir = write_files(&[
+            ("main.beancount", "include \"options.beancount\"\n"),
+            ("options.beancount", "\noption \"title\" \"Ledger\"\n"),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+        let loaded = loader.parse();
+
+        let option = loaded.ledger().pragmas()[0].as_option().unwrap();
+        let span = option.span().unwrap();
+        assert_eq!(span.file_id(), FileId::new(1));
+        assert_eq!(span.byte_range(), 1..24);
+    }
+
+    #[test]
+    fn load_file_included_twice_only_once() {
+        let dir = write_files(&[
+            (
+                "main.beancount",
+                "include \"a.beancount\"\ninclude \"b.beancount\"\n",
+            ),
+            ("a.beancount", "include \"prices.beancount\"\n"),
+            ("b.beancount", "include \"prices.beancount\"\n"),
+            ("prices.beancount", "2024-01-01 price EUR 1.10 USD\n"),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+
+        assert_eq!(loader.files().len(), 4);
+        assert_eq!(loader.parse().ledger().directives().len(), 1);
+    }
+
+    #[test]
+    fn load_include_cycle() {
+        let dir = write_files(&[
+            ("main.beancount", "include \"a.beancount\"\n"),
+            ("a.beancount", "include \"b.beancount\"\n"),
+            ("b.beancount", "include \"a.beancount\"\n"),
+        ]);
+
+        let error = Loader::load(&dir.path().join("main.beancount")).unwrap_err();
+
+        let LoadError::IncludeCycle { path } = error else {
+            panic!("Expected an include cycle, got {error:?}");
+        };
+        assert!(path.ends_with("a.beancount"));
+    }
+
+    #[test]
+    fn load_missing_include() {
+        let dir = write_files(&[("main.beancount", "include \"missing.beancount\"\n")]);
+
+        let error = Loader::load(&dir.path().join("main.beancount")).unwrap_err();
+
+        assert!(matches!(error, LoadError::Io { path, .. } if path.ends_with("missing.beancount")));
+    }
+
+    #[test]
+    fn load_glob_without_matches() {
+        let dir = write_files(&[("main.beancount", "include \"years/*.beancount\"\n")]);
+
+        let error = Loader::load(&dir.path().join("main.beancount")).unwrap_err();
+
+        assert!(matches!(error, LoadError::NoMatchingFiles { .. }));
+    }
+
+    #[test]
+    fn parse_errors_refer_to_their_file() {
+        let dir = write_files(&[
+            (
+                "main.beancount",
+                "include \"broken.beancount\"\n2024-01-01 open Assets:Cash\n",
+            ),
+            (
+                "broken.beancount",
+                "2024-01-02 open Assets:Bank\n2024-01-03 open assets:broken\n",
+            ),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+        let loaded = loader.parse();
+
+        assert_eq!(loaded.ledger().directives().len(), 2);
+        assert_eq!(loaded.errors().len(), 1);
+        assert_eq!(loaded.errors()[0].0, FileId::new(1));
+
+        let mut output = Vec::new();
+        for report in loaded.get_formatted_errors() {
+            report.write(loader.report_cache(), &mut output).unwrap();
+        }
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("broken.beancount:2:"));
+    }
+
+    #[test]
+    fn document_paths_relative_to_including_file() {
+        let dir = write_files(&[
+            ("main.beancount", "include \"2024/ledger.beancount\"\n"),
+            (
+                "2024/ledger.beancount",
+                "2024-01-01 document Assets:Bank \"statement.pdf\"\n",
+            ),
+        ]);
+
+        let loader = Loader::load(&dir.path().join("main.beancount")).unwrap();
+        let loaded = loader.parse();
+
+        let document = loaded.ledger().directives()[0].as_document().unwrap();
+        assert_eq!(
+            document.path(),
+            fs::canonicalize(dir.path())
+                .unwrap()
+                .join("2024/statement.pdf")
+        );
+    }
+}
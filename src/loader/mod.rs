@@ -0,0 +1,8 @@
+mod loader;
+pub use loader::{LoadError, LoadedLedger, Loader, SourceFile};
+
+// Alternative loader using beancount-parser-lima as parser backend
+pub use crate::parser::{
+    LimaConversionError, LimaDirectiveConversionError, LimaLedger, LimaLoadError, LimaLoader,
+    LimaPostingConversionError, LimaTransactionConversionError,
+};
@@ -1,26 +1,107 @@
-use std::io::BufRead as _;
+use std::{io::Read as _, path::Path};
 
-use ariadne::Source;
-use beancount_rs::{ParseResultExt as _, marshal_directive, parse_directive};
+use ariadne::{Report, Source};
+use beancount_rs::{
+    ParseResultExt as _,
+    loader::Loader,
+    marshal_file,
+    model::{Ledger, Options, Pragma, SourceSpan},
+    parse_file,
+    processing::{
+        check_account_lifecycles, check_balance_assertions, check_commodity_constraints,
+        check_transaction_balances, format_semantic_error, interpolate_transactions, resolve_pads,
+    },
+};
 use chumsky::Parser as _;
 
 fn main() {
-    // read line from stdin
-    let stdin = std::io::stdin();
-    let handle = stdin.lock();
-    println!("Input:");
-    for line in handle.lines() {
-        let line = line.unwrap();
-        // parse line as account component
-        let directive = parse_directive().parse(&line);
-        for error_report in directive.get_formatted_errors() {
-            error_report.print(Source::from(&line)).unwrap();
-        }
-        if let Ok(directive) = directive.into_result() {
-            println!("\nParsed: {:#?}\n\nInput:", directive);
-            let mut string = String::new();
-            marshal_directive(&directive, &mut string).unwrap();
-            println!("Marshalled: {}", string);
+    match std::env::args().nth(1) {
+        Some(path) => load_from_file(Path::new(&path)),
+        None => load_from_stdin(),
+    }
+}
+
+/// Load a ledger file together with all files it includes
+fn load_from_file(path: &Path) {
+    let loader = match Loader::load(path) {
+        Ok(loader) => loader,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
         }
+    };
+    let loaded = loader.parse();
+    for error_report in loaded.get_formatted_errors() {
+        error_report.eprint(loader.report_cache()).unwrap();
+    }
+
+    let ledger = loaded.ledger();
+    let (options, errors) = Options::from_valid_pragmas(
+        ledger
+            .pragmas()
+            .iter()
+            .filter_map(Pragma::as_option)
+            .cloned(),
+    );
+    for (option, error) in errors {
+        let report = option
+            .span()
+            .map(|span| format_semantic_error(&error, span));
+        print_semantic_error(&loader, &error, report);
+    }
+    for error in check_account_lifecycles(ledger.directives()) {
+        print_semantic_error(&loader, &error, error.report());
+    }
+    let (directives, errors) = interpolate_transactions(ledger.directives().to_vec());
+    for error in errors {
+        print_semantic_error(&loader, &error, error.report());
+    }
+    for error in check_commodity_constraints(&directives) {
+        print_semantic_error(&loader, &error, error.report());
+    }
+    for error in check_transaction_balances(&directives, &options) {
+        print_semantic_error(&loader, &error, error.report());
+    }
+    let (directives, errors) = resolve_pads(directives);
+    for error in errors {
+        print_semantic_error(&loader, &error, error.report());
     }
+    for error in check_balance_assertions(&directives, &options) {
+        print_semantic_error(&loader, &error, error.report());
+    }
+    print_ledger(ledger);
+}
+
+/// Print an error from processing the ledger, with its source location if known
+fn print_semantic_error(
+    loader: &Loader,
+    error: &impl std::fmt::Display,
+    report: Option<Report<SourceSpan>>,
+) {
+    match report {
+        Some(report) => report.eprint(loader.report_cache()).unwrap(),
+        None => eprintln!("{error}"),
+    }
+}
+
+fn load_from_stdin() {
+    // read the whole ledger from stdin
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+
+    let ledger = parse_file().parse(&input);
+    for error_report in ledger.get_formatted_errors() {
+        error_report.print(Source::from(&input)).unwrap();
+    }
+    // print whatever could be parsed, even if some entries were malformed
+    if let Some(ledger) = ledger.output() {
+        print_ledger(ledger);
+    }
+}
+
+fn print_ledger(ledger: &Ledger) {
+    println!("Parsed: {:#?}", ledger);
+    let mut string = String::new();
+    marshal_file(ledger, &mut string).unwrap();
+    println!("Marshalled:\n{}", string);
 }
@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 use crate::model::account::{account_component::AccountComponent, account_type::AccountType};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -18,8 +20,24 @@ impl<'a> Account<'a> {
         self.account_type
     }
 
-    pub fn components(&self) -> impl Iterator<Item = &'_ AccountComponent<'a>> + ExactSizeIterator {
-        self.components.iter().map(|c| c)
+    pub fn components(&self) -> impl ExactSizeIterator<Item = &'_ AccountComponent<'a>> {
+        self.components.iter()
+    }
+
+    /// Whether `account` is this account itself or one of its sub-accounts
+    pub fn contains(&self, account: &Account) -> bool {
+        self.account_type == account.account_type
+            && account.components.starts_with(&self.components)
+    }
+}
+
+impl Display for Account<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.account_type.default_name())?;
+        for component in &self.components {
+            write!(f, ":{}", component.as_ref())?;
+        }
+        Ok(())
     }
 }
 
@@ -65,6 +83,23 @@ mod tests {
         assert_eq!(components, ["Food", "Groceries", "Store"]);
     }
 
+    #[test]
+    fn test_account_display() {
+        assert_eq!(account!(Assets:US:Cash).to_string(), "Assets:US:Cash");
+    }
+
+    #[test]
+    fn test_account_contains() {
+        let parent = account!(Assets:US);
+
+        assert!(parent.contains(&account!(Assets:US)));
+        assert!(parent.contains(&account!(Assets:US:Cash)));
+        assert!(!parent.contains(&account!(Assets:USD)));
+        assert!(!parent.contains(&account!(Assets:EU:Cash)));
+        assert!(!parent.contains(&account!(Liabilities:US)));
+        assert!(!account!(Assets:US:Cash).contains(&parent));
+    }
+
     #[test]
     #[should_panic(expected = "InvalidCharacter")]
     fn test_account_creation_mixed_valid_invalid() {
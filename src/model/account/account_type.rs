@@ -6,3 +6,16 @@ pub enum AccountType {
     Expenses,
     Equity,
 }
+
+impl AccountType {
+    /// Name of the root account for this type, unless overridden by a `name_*` option
+    pub const fn default_name(self) -> &'static str {
+        match self {
+            AccountType::Assets => "Assets",
+            AccountType::Liabilities => "Liabilities",
+            AccountType::Income => "Income",
+            AccountType::Expenses => "Expenses",
+            AccountType::Equity => "Equity",
+        }
+    }
+}
@@ -1,17 +1,38 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
 use rust_decimal::Decimal;
 
 use crate::model::Commodity;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A number of units of a commodity.
+///
+/// The number may have been written as an arithmetic expression like `(40.00 / 3)`.
+/// The expression text is retained for marshalling, but equality, ordering and hashing
+/// only consider the evaluated number and the commodity.
+#[derive(Debug, Clone)]
 pub struct Amount<'c> {
-    // TODO Beancount allows expressions as amounts, should we represent that? See [beancount_parser_lima].
     number: Decimal,
     commodity: Commodity<'c>,
+    expression: Option<Cow<'c, str>>,
 }
 
 impl<'c> Amount<'c> {
     pub fn new(number: Decimal, commodity: Commodity<'c>) -> Self {
-        Self { number, commodity }
+        Self {
+            number,
+            commodity,
+            expression: None,
+        }
+    }
+
+    /// Retain the source expression the number was evaluated from
+    pub fn with_expression(mut self, expression: impl Into<Cow<'c, str>>) -> Self {
+        self.expression = Some(expression.into());
+        self
     }
 
     pub fn number(&self) -> &Decimal {
@@ -21,6 +42,39 @@ impl<'c> Amount<'c> {
     pub fn commodity(&self) -> &Commodity<'c> {
         &self.commodity
     }
+
+    pub fn expression(&self) -> Option<&str> {
+        self.expression.as_deref()
+    }
+}
+
+impl PartialEq for Amount<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.commodity == other.commodity
+    }
+}
+
+impl Eq for Amount<'_> {}
+
+impl PartialOrd for Amount<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Amount<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number
+            .cmp(&other.number)
+            .then_with(|| self.commodity.cmp(&other.commodity))
+    }
+}
+
+impl Hash for Amount<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.number.hash(state);
+        self.commodity.hash(state);
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +139,24 @@ mod tests {
         assert_eq!(amount.commodity().as_ref(), "BTC");
     }
 
+    #[test]
+    fn test_amount_with_expression() {
+        let amount = Amount::new(dec!(42.00), commodity!(EUR)).with_expression("12 * 3.50");
+
+        assert_eq!(*amount.number(), dec!(42.00));
+        assert_eq!(amount.expression(), Some("12 * 3.50"));
+        assert_eq!(Amount::new(dec!(42.00), commodity!(EUR)).expression(), None);
+    }
+
+    #[test]
+    fn test_expression_is_ignored_for_equality() {
+        let amount1 = Amount::new(dec!(42.00), commodity!(EUR)).with_expression("12 * 3.50");
+        let amount2 = Amount::new(dec!(42.00), commodity!(EUR));
+
+        assert_eq!(amount1, amount2);
+        assert_eq!(amount1.cmp(&amount2), Ordering::Equal);
+    }
+
     #[test]
     fn test_clone_and_hash() {
         use std::collections::HashSet;
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use derive_more::Display;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[error("Unknown booking method: {0}")]
+pub struct InvalidBookingMethodError(pub String);
+
+/// How lots are matched when reducing a position held at cost
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum BookingMethod {
+    #[default]
+    #[display("STRICT")]
+    Strict,
+    #[display("STRICT_WITH_SIZE")]
+    StrictWithSize,
+    #[display("NONE")]
+    None,
+    #[display("AVERAGE")]
+    Average,
+    #[display("FIFO")]
+    Fifo,
+    #[display("LIFO")]
+    Lifo,
+    #[display("HIFO")]
+    Hifo,
+}
+
+impl BookingMethod {
+    pub const ALL: [BookingMethod; 7] = [
+        BookingMethod::Strict,
+        BookingMethod::StrictWithSize,
+        BookingMethod::None,
+        BookingMethod::Average,
+        BookingMethod::Fifo,
+        BookingMethod::Lifo,
+        BookingMethod::Hifo,
+    ];
+}
+
+impl FromStr for BookingMethod {
+    type Err = InvalidBookingMethodError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BookingMethod::ALL
+            .into_iter()
+            .find(|method| method.to_string() == s)
+            .ok_or_else(|| InvalidBookingMethodError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("STRICT", BookingMethod::Strict)]
+    #[case("STRICT_WITH_SIZE", BookingMethod::StrictWithSize)]
+    #[case("NONE", BookingMethod::None)]
+    #[case("AVERAGE", BookingMethod::Average)]
+    #[case("FIFO", BookingMethod::Fifo)]
+    #[case("LIFO", BookingMethod::Lifo)]
+    #[case("HIFO", BookingMethod::Hifo)]
+    fn from_str_and_display(#[case] input: &str, #[case] expected: BookingMethod) {
+        let parsed: BookingMethod = input.parse().unwrap();
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("fifo")]
+    #[case("FIRST_IN_FIRST_OUT")]
+    fn from_str_invalid(#[case] input: &str) {
+        let result: Result<BookingMethod, _> = input.parse();
+        assert_eq!(result, Err(InvalidBookingMethodError(input.to_string())));
+    }
+
+    #[test]
+    fn default_is_strict() {
+        assert_eq!(BookingMethod::default(), BookingMethod::Strict);
+    }
+}
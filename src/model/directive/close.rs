@@ -0,0 +1,46 @@
+use crate::model::Account;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveClose<'a> {
+    account: Account<'a>,
+}
+
+impl<'a> DirectiveClose<'a> {
+    pub fn new(account: Account<'a>) -> Self {
+        Self { account }
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+
+    #[test]
+    fn test_new_directive_close() {
+        let account = account!(Assets:OldBank);
+        let directive = DirectiveClose::new(account.clone());
+
+        assert_eq!(*directive.account(), account);
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveClose::new(account!(Liabilities:CreditCard));
+        let directive2 = directive1.clone();
+
+        assert_eq!(directive1, directive2);
+    }
+
+    #[test]
+    fn test_different_accounts_not_equal() {
+        let directive1 = DirectiveClose::new(account!(Assets:Checking));
+        let directive2 = DirectiveClose::new(account!(Assets:Savings));
+
+        assert_ne!(directive1, directive2);
+    }
+}
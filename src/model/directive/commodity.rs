@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use crate::model::{Commodity, Directive};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveCommodity<'a> {
+    commodity: Commodity<'a>,
+}
+
+impl<'a> DirectiveCommodity<'a> {
+    pub fn new(commodity: Commodity<'a>) -> Self {
+        Self { commodity }
+    }
+
+    pub fn commodity(&self) -> &Commodity<'a> {
+        &self.commodity
+    }
+}
+
+/// All commodities declared by a `commodity` directive in the given directives
+pub fn declared_commodities<'d, 'a>(
+    directives: impl IntoIterator<Item = &'d Directive<'a>>,
+) -> HashSet<&'d Commodity<'a>>
+where
+    'a: 'd,
+{
+    directives
+        .into_iter()
+        .filter_map(Directive::as_commodity)
+        .map(DirectiveCommodity::commodity)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DirectiveClose, account, commodity};
+    use chrono::NaiveDate;
+    use common_macros::hash_set;
+
+    #[test]
+    fn test_new_directive_commodity() {
+        let directive = DirectiveCommodity::new(commodity!(USD));
+
+        assert_eq!(*directive.commodity(), commodity!(USD));
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveCommodity::new(commodity!(EUR));
+        let directive2 = directive1.clone();
+        let directive3 = DirectiveCommodity::new(commodity!(USD));
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+
+    #[test]
+    fn test_declared_commodities() {
+        let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+        let directives = vec![
+            Directive::new_commodity(date, DirectiveCommodity::new(commodity!(USD))),
+            Directive::new_close(date, DirectiveClose::new(account!(Assets:Cash))),
+            Directive::new_commodity(date, DirectiveCommodity::new(commodity!(EUR))),
+            Directive::new_commodity(date, DirectiveCommodity::new(commodity!(USD))),
+        ];
+
+        let declared = declared_commodities(&directives);
+
+        let (usd, eur) = (commodity!(USD), commodity!(EUR));
+        assert_eq!(declared, hash_set![&usd, &eur]);
+    }
+}
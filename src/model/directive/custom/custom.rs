@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use crate::model::directive::CustomValue;
+
+/// Custom directive, used by plugins and tools like Fava
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveCustom<'a> {
+    custom_type: Cow<'a, str>,
+    values: Vec<CustomValue<'a>>,
+}
+
+impl<'a> DirectiveCustom<'a> {
+    pub fn new(custom_type: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            custom_type: custom_type.into(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with_value(mut self, value: CustomValue<'a>) -> Self {
+        self.values.push(value);
+        self
+    }
+
+    pub fn with_values(mut self, values: Vec<CustomValue<'a>>) -> Self {
+        self.values.extend(values);
+        self
+    }
+
+    pub fn custom_type(&self) -> &str {
+        &self.custom_type
+    }
+
+    pub fn values(&self) -> &[CustomValue<'a>] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, account, commodity};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_new_directive_custom() {
+        let directive = DirectiveCustom::new("budget");
+
+        assert_eq!(directive.custom_type(), "budget");
+        assert!(directive.values().is_empty());
+    }
+
+    #[test]
+    fn test_with_values() {
+        let directive = DirectiveCustom::new("budget")
+            .with_value(CustomValue::Account(account!(Expenses:Food)))
+            .with_values(vec![
+                CustomValue::String("monthly".into()),
+                CustomValue::Amount(Amount::new(dec!(400), commodity!(EUR))),
+            ]);
+
+        assert_eq!(
+            directive.values(),
+            [
+                CustomValue::Account(account!(Expenses:Food)),
+                CustomValue::String("monthly".into()),
+                CustomValue::Amount(Amount::new(dec!(400), commodity!(EUR))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveCustom::new("budget").with_value(CustomValue::Bool(true));
+        let directive2 = directive1.clone();
+        let directive3 = DirectiveCustom::new("budget").with_value(CustomValue::Bool(false));
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
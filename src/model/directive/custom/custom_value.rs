@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::model::{Account, Amount};
+
+/// A typed value of a custom directive
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CustomValue<'a> {
+    String(Cow<'a, str>),
+    Date(NaiveDate),
+    Bool(bool),
+    Amount(Amount<'a>),
+    Number(Decimal),
+    Account(Account<'a>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{account, commodity};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(
+            CustomValue::String("budget".into()),
+            CustomValue::String("budget".to_string().into())
+        );
+        assert_eq!(
+            CustomValue::Account(account!(Expenses:Food)),
+            CustomValue::Account(account!(Expenses:Food))
+        );
+        assert_ne!(
+            CustomValue::Number(dec!(100)),
+            CustomValue::Amount(Amount::new(dec!(100), commodity!(USD)))
+        );
+    }
+}
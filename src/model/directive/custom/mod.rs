@@ -0,0 +1,5 @@
+mod custom;
+mod custom_value;
+
+pub use custom::DirectiveCustom;
+pub use custom_value::CustomValue;
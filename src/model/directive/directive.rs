@@ -1,38 +1,106 @@
 use chrono::NaiveDate;
 
-use super::{DirectiveBalance, DirectiveOpen, DirectiveTransaction};
+use crate::model::{IgnoredSpan, Metadata, SourceSpan};
+
+use super::{
+    DirectiveBalance, DirectiveClose, DirectiveCommodity, DirectiveCustom, DirectiveDocument,
+    DirectiveEvent, DirectiveNote, DirectiveOpen, DirectivePad, DirectivePrice, DirectiveQuery,
+    DirectiveTransaction,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DirectiveVariant<'a> {
     Open(DirectiveOpen<'a>),
+    Close(DirectiveClose<'a>),
+    Commodity(DirectiveCommodity<'a>),
+    Pad(DirectivePad<'a>),
     Balance(DirectiveBalance<'a>),
+    Price(DirectivePrice<'a>),
     Transaction(DirectiveTransaction<'a>),
+    Note(DirectiveNote<'a>),
+    Document(DirectiveDocument<'a>),
+    Event(DirectiveEvent<'a>),
+    Query(DirectiveQuery<'a>),
+    Custom(DirectiveCustom<'a>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Directive<'a> {
     date: NaiveDate,
     content: DirectiveVariant<'a>,
-    // TODO directives can have metadata
+    metadata: Metadata<'a>,
+    span: IgnoredSpan,
 }
 
 impl<'a> Directive<'a> {
     pub fn new(date: NaiveDate, content: DirectiveVariant<'a>) -> Self {
-        Self { date, content }
+        Self {
+            date,
+            content,
+            metadata: Metadata::new(),
+            span: IgnoredSpan::default(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata<'a>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Source location of the directive. Ignored when comparing directives.
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = IgnoredSpan::new(span);
+        self
     }
 
     pub fn new_open(date: NaiveDate, open: DirectiveOpen<'a>) -> Self {
         Self::new(date, DirectiveVariant::Open(open))
     }
 
+    pub fn new_close(date: NaiveDate, close: DirectiveClose<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Close(close))
+    }
+
+    pub fn new_commodity(date: NaiveDate, commodity: DirectiveCommodity<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Commodity(commodity))
+    }
+
+    pub fn new_pad(date: NaiveDate, pad: DirectivePad<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Pad(pad))
+    }
+
     pub fn new_balance(date: NaiveDate, balance: DirectiveBalance<'a>) -> Self {
         Self::new(date, DirectiveVariant::Balance(balance))
     }
 
+    pub fn new_price(date: NaiveDate, price: DirectivePrice<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Price(price))
+    }
+
     pub fn new_transaction(date: NaiveDate, transaction: DirectiveTransaction<'a>) -> Self {
         Self::new(date, DirectiveVariant::Transaction(transaction))
     }
 
+    pub fn new_note(date: NaiveDate, note: DirectiveNote<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Note(note))
+    }
+
+    pub fn new_document(date: NaiveDate, document: DirectiveDocument<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Document(document))
+    }
+
+    pub fn new_event(date: NaiveDate, event: DirectiveEvent<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Event(event))
+    }
+
+    pub fn new_query(date: NaiveDate, query: DirectiveQuery<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Query(query))
+    }
+
+    pub fn new_custom(date: NaiveDate, custom: DirectiveCustom<'a>) -> Self {
+        Self::new(date, DirectiveVariant::Custom(custom))
+    }
+
     pub fn date(&self) -> &NaiveDate {
         &self.date
     }
@@ -41,6 +109,26 @@ impl<'a> Directive<'a> {
         &self.content
     }
 
+    pub fn content_mut(&mut self) -> &mut DirectiveVariant<'a> {
+        &mut self.content
+    }
+
+    pub fn metadata(&self) -> &Metadata<'a> {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata<'a> {
+        &mut self.metadata
+    }
+
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.get()
+    }
+
+    pub fn span_mut(&mut self) -> Option<&mut SourceSpan> {
+        self.span.get_mut()
+    }
+
     pub fn as_open(&self) -> Option<&DirectiveOpen<'a>> {
         match &self.content {
             DirectiveVariant::Open(open) => Some(open),
@@ -55,6 +143,48 @@ impl<'a> Directive<'a> {
         }
     }
 
+    pub fn as_close(&self) -> Option<&DirectiveClose<'a>> {
+        match &self.content {
+            DirectiveVariant::Close(close) => Some(close),
+            _ => None,
+        }
+    }
+
+    pub fn into_close(self) -> Option<DirectiveClose<'a>> {
+        match self.content {
+            DirectiveVariant::Close(close) => Some(close),
+            _ => None,
+        }
+    }
+
+    pub fn as_commodity(&self) -> Option<&DirectiveCommodity<'a>> {
+        match &self.content {
+            DirectiveVariant::Commodity(commodity) => Some(commodity),
+            _ => None,
+        }
+    }
+
+    pub fn into_commodity(self) -> Option<DirectiveCommodity<'a>> {
+        match self.content {
+            DirectiveVariant::Commodity(commodity) => Some(commodity),
+            _ => None,
+        }
+    }
+
+    pub fn as_pad(&self) -> Option<&DirectivePad<'a>> {
+        match &self.content {
+            DirectiveVariant::Pad(pad) => Some(pad),
+            _ => None,
+        }
+    }
+
+    pub fn into_pad(self) -> Option<DirectivePad<'a>> {
+        match self.content {
+            DirectiveVariant::Pad(pad) => Some(pad),
+            _ => None,
+        }
+    }
+
     pub fn as_balance(&self) -> Option<&DirectiveBalance<'a>> {
         match &self.content {
             DirectiveVariant::Balance(balance) => Some(balance),
@@ -69,6 +199,20 @@ impl<'a> Directive<'a> {
         }
     }
 
+    pub fn as_price(&self) -> Option<&DirectivePrice<'a>> {
+        match &self.content {
+            DirectiveVariant::Price(price) => Some(price),
+            _ => None,
+        }
+    }
+
+    pub fn into_price(self) -> Option<DirectivePrice<'a>> {
+        match self.content {
+            DirectiveVariant::Price(price) => Some(price),
+            _ => None,
+        }
+    }
+
     pub fn as_transaction(&self) -> Option<&DirectiveTransaction<'a>> {
         match &self.content {
             DirectiveVariant::Transaction(transaction) => Some(transaction),
@@ -82,14 +226,85 @@ impl<'a> Directive<'a> {
             _ => None,
         }
     }
+
+    pub fn as_note(&self) -> Option<&DirectiveNote<'a>> {
+        match &self.content {
+            DirectiveVariant::Note(note) => Some(note),
+            _ => None,
+        }
+    }
+
+    pub fn into_note(self) -> Option<DirectiveNote<'a>> {
+        match self.content {
+            DirectiveVariant::Note(note) => Some(note),
+            _ => None,
+        }
+    }
+
+    pub fn as_document(&self) -> Option<&DirectiveDocument<'a>> {
+        match &self.content {
+            DirectiveVariant::Document(document) => Some(document),
+            _ => None,
+        }
+    }
+
+    pub fn into_document(self) -> Option<DirectiveDocument<'a>> {
+        match self.content {
+            DirectiveVariant::Document(document) => Some(document),
+            _ => None,
+        }
+    }
+
+    pub fn as_event(&self) -> Option<&DirectiveEvent<'a>> {
+        match &self.content {
+            DirectiveVariant::Event(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    pub fn into_event(self) -> Option<DirectiveEvent<'a>> {
+        match self.content {
+            DirectiveVariant::Event(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    pub fn as_query(&self) -> Option<&DirectiveQuery<'a>> {
+        match &self.content {
+            DirectiveVariant::Query(query) => Some(query),
+            _ => None,
+        }
+    }
+
+    pub fn into_query(self) -> Option<DirectiveQuery<'a>> {
+        match self.content {
+            DirectiveVariant::Query(query) => Some(query),
+            _ => None,
+        }
+    }
+
+    pub fn as_custom(&self) -> Option<&DirectiveCustom<'a>> {
+        match &self.content {
+            DirectiveVariant::Custom(custom) => Some(custom),
+            _ => None,
+        }
+    }
+
+    pub fn into_custom(self) -> Option<DirectiveCustom<'a>> {
+        match self.content {
+            DirectiveVariant::Custom(custom) => Some(custom),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{account, commodity};
+    use crate::model::{Amount, account, commodity};
     use chrono::NaiveDate;
     use common_macros::hash_set;
+    use rust_decimal_macros::dec;
 
     #[test]
     fn test_new_directive_open() {
@@ -105,6 +320,23 @@ mod tests {
         assert_eq!(directive.as_open().unwrap().account(), &account);
     }
 
+    #[test]
+    fn test_directive_metadata() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let close = DirectiveClose::new(account!(Assets:Cash));
+        let key = crate::model::MetadataKey::new("reason").unwrap();
+        let value = crate::model::MetadataValue::String("Moved".into());
+
+        let mut directive = Directive::new_close(date, close);
+        assert!(directive.metadata().is_empty());
+
+        directive.metadata_mut().insert(key.clone(), value.clone());
+        assert_eq!(directive.metadata().get("reason"), Some(&value));
+
+        let directive = directive.with_metadata(Metadata::new());
+        assert!(directive.metadata().is_empty());
+    }
+
     #[test]
     fn test_new_directive_with_content() {
         let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
@@ -133,6 +365,122 @@ mod tests {
         assert_eq!(extracted_open.account(), &account);
     }
 
+    #[test]
+    fn test_new_directive_close() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let account = account!(Assets:OldBank);
+        let close_directive = DirectiveClose::new(account.clone());
+
+        let directive = Directive::new_close(date, close_directive);
+
+        assert_eq!(directive.date(), &date);
+        assert!(directive.as_open().is_none());
+        assert_eq!(directive.as_close().unwrap().account(), &account);
+        assert_eq!(directive.into_close().unwrap().account(), &account);
+    }
+
+    #[test]
+    fn test_new_directive_commodity() {
+        let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+        let commodity_directive = DirectiveCommodity::new(commodity!(USD));
+
+        let directive = Directive::new_commodity(date, commodity_directive);
+
+        assert_eq!(directive.date(), &date);
+        assert!(directive.as_close().is_none());
+        assert_eq!(
+            directive.as_commodity().unwrap().commodity(),
+            &commodity!(USD)
+        );
+        assert_eq!(
+            directive.into_commodity().unwrap().commodity(),
+            &commodity!(USD)
+        );
+    }
+
+    #[test]
+    fn test_new_directive_pad() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let pad_directive =
+            DirectivePad::new(account!(Assets:Checking), account!(Equity:OpeningBalances));
+
+        let directive = Directive::new_pad(date, pad_directive.clone());
+
+        assert_eq!(directive.date(), &date);
+        assert!(directive.as_balance().is_none());
+        assert_eq!(directive.as_pad(), Some(&pad_directive));
+        assert_eq!(directive.into_pad(), Some(pad_directive));
+    }
+
+    #[test]
+    fn test_new_directive_price() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let price = Amount::new(dec!(579.18), commodity!(USD));
+        let price_directive = DirectivePrice::new(commodity!(HOOL), price.clone());
+
+        let directive = Directive::new_price(date, price_directive);
+
+        assert_eq!(directive.date(), &date);
+        assert!(directive.as_commodity().is_none());
+        assert_eq!(directive.as_price().unwrap().price(), &price);
+        assert_eq!(
+            directive.into_price().unwrap().commodity(),
+            &commodity!(HOOL)
+        );
+    }
+
+    #[test]
+    fn test_new_directive_note_and_document() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let note = DirectiveNote::new(account!(Assets:Checking), "Called bank about fee");
+        let document = DirectiveDocument::new(
+            account!(Assets:Checking),
+            std::path::Path::new("statements/2024-01.pdf"),
+        );
+
+        let note_directive = Directive::new_note(date, note.clone());
+        let document_directive = Directive::new_document(date, document.clone());
+
+        assert_eq!(note_directive.as_note(), Some(&note));
+        assert!(note_directive.as_document().is_none());
+        assert_eq!(note_directive.into_note(), Some(note));
+        assert_eq!(document_directive.as_document(), Some(&document));
+        assert_eq!(document_directive.into_document(), Some(document));
+    }
+
+    #[test]
+    fn test_new_directive_event_query_custom() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let event = DirectiveEvent::new("location", "Berlin");
+        let query = DirectiveQuery::new("cash", "SELECT 1");
+        let custom = DirectiveCustom::new("budget");
+
+        let event_directive = Directive::new_event(date, event.clone());
+        let query_directive = Directive::new_query(date, query.clone());
+        let custom_directive = Directive::new_custom(date, custom.clone());
+
+        assert_eq!(event_directive.as_event(), Some(&event));
+        assert!(event_directive.as_query().is_none());
+        assert_eq!(event_directive.into_event(), Some(event));
+        assert_eq!(query_directive.as_query(), Some(&query));
+        assert!(query_directive.as_custom().is_none());
+        assert_eq!(query_directive.into_query(), Some(query));
+        assert_eq!(custom_directive.as_custom(), Some(&custom));
+        assert_eq!(custom_directive.into_custom(), Some(custom));
+    }
+
+    #[test]
+    fn test_content_mut() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let mut directive =
+            Directive::new_note(date, DirectiveNote::new(account!(Assets:Cash), "a"));
+
+        *directive.content_mut() =
+            DirectiveVariant::Note(DirectiveNote::new(account!(Assets:Cash), "b"));
+
+        assert_eq!(directive.as_note().unwrap().comment(), "b");
+    }
+
     #[test]
     fn test_clone_and_equality() {
         let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
@@ -0,0 +1,103 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+use crate::model::Account;
+
+/// Document (e.g. a statement) attached to an account
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveDocument<'a> {
+    account: Account<'a>,
+    path: Cow<'a, Path>,
+}
+
+impl<'a> DirectiveDocument<'a> {
+    pub fn new(account: Account<'a>, path: impl Into<Cow<'a, Path>>) -> Self {
+        Self {
+            account,
+            path: path.into(),
+        }
+    }
+
+    /// Create a document directive from a path given as a string, e.g. from a ledger file
+    pub fn from_str_path(account: Account<'a>, path: Cow<'a, str>) -> Self {
+        let path = match path {
+            Cow::Borrowed(path) => Cow::Borrowed(Path::new(path)),
+            Cow::Owned(path) => Cow::Owned(PathBuf::from(path)),
+        };
+        Self::new(account, path)
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    /// The path to the document. This can be relative to the ledger file that contained the directive,
+    /// see [DirectiveDocument::resolve_path].
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Resolve a relative path against the given directory. Absolute paths are kept as they are.
+    pub fn resolve_path(&mut self, base_dir: &Path) {
+        if self.path.is_relative() {
+            self.path = Cow::Owned(base_dir.join(&self.path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+
+    #[test]
+    fn test_new_directive_document() {
+        let directive = DirectiveDocument::new(
+            account!(Assets:Checking),
+            Path::new("statements/2024-01.pdf"),
+        );
+
+        assert_eq!(*directive.account(), account!(Assets:Checking));
+        assert_eq!(directive.path(), Path::new("statements/2024-01.pdf"));
+    }
+
+    #[test]
+    fn test_from_str_path() {
+        let borrowed = DirectiveDocument::from_str_path(
+            account!(Assets:Checking),
+            Cow::Borrowed("statements/2024-01.pdf"),
+        );
+        let owned = DirectiveDocument::from_str_path(
+            account!(Assets:Checking),
+            Cow::Owned("statements/2024-01.pdf".to_string()),
+        );
+
+        assert_eq!(borrowed, owned);
+        assert_eq!(borrowed.path(), Path::new("statements/2024-01.pdf"));
+    }
+
+    #[test]
+    fn test_resolve_relative_path() {
+        let mut directive = DirectiveDocument::new(
+            account!(Assets:Checking),
+            Path::new("statements/2024-01.pdf"),
+        );
+        directive.resolve_path(Path::new("/home/user/ledger"));
+
+        assert_eq!(
+            directive.path(),
+            Path::new("/home/user/ledger/statements/2024-01.pdf")
+        );
+    }
+
+    #[test]
+    fn test_resolve_absolute_path() {
+        let mut directive =
+            DirectiveDocument::new(account!(Assets:Checking), Path::new("/tmp/2024-01.pdf"));
+        directive.resolve_path(Path::new("/home/user/ledger"));
+
+        assert_eq!(directive.path(), Path::new("/tmp/2024-01.pdf"));
+    }
+}
@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+/// Change of a named variable over time, e.g. the current location
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveEvent<'a> {
+    event_type: Cow<'a, str>,
+    description: Cow<'a, str>,
+}
+
+impl<'a> DirectiveEvent<'a> {
+    pub fn new(event_type: impl Into<Cow<'a, str>>, description: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            event_type: event_type.into(),
+            description: description.into(),
+        }
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_directive_event() {
+        let directive = DirectiveEvent::new("location", "Berlin");
+
+        assert_eq!(directive.event_type(), "location");
+        assert_eq!(directive.description(), "Berlin");
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveEvent::new("location", "Berlin");
+        let directive2 = directive1.clone();
+        let directive3 = DirectiveEvent::new("location".to_string(), "Paris".to_string());
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
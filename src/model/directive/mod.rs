@@ -1,9 +1,30 @@
 mod balance;
+mod close;
+mod commodity;
+mod custom;
 mod directive;
+mod document;
+mod event;
+mod note;
 mod open;
+mod pad;
+mod price;
+mod query;
 mod transaction;
 
 pub use balance::DirectiveBalance;
+pub use close::DirectiveClose;
+pub use commodity::{DirectiveCommodity, declared_commodities};
+pub use custom::{CustomValue, DirectiveCustom};
 pub use directive::{Directive, DirectiveVariant};
+pub use document::DirectiveDocument;
+pub use event::DirectiveEvent;
+pub use note::DirectiveNote;
 pub use open::DirectiveOpen;
-pub use transaction::{DirectiveTransaction, Flag, Posting, PostingAmount, TransactionDescription};
+pub use pad::DirectivePad;
+pub use price::DirectivePrice;
+pub use query::DirectiveQuery;
+pub use transaction::{
+    CostSpec, DirectiveTransaction, Flag, Posting, PostingAmount, PostingPrice,
+    TransactionDescription,
+};
@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+use crate::model::Account;
+
+/// Free-form comment attached to an account
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveNote<'a> {
+    account: Account<'a>,
+    comment: Cow<'a, str>,
+}
+
+impl<'a> DirectiveNote<'a> {
+    pub fn new(account: Account<'a>, comment: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            account,
+            comment: comment.into(),
+        }
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+
+    #[test]
+    fn test_new_directive_note() {
+        let directive = DirectiveNote::new(account!(Assets:Checking), "Called bank about fee");
+
+        assert_eq!(*directive.account(), account!(Assets:Checking));
+        assert_eq!(directive.comment(), "Called bank about fee");
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveNote::new(account!(Assets:Checking), "a");
+        let directive2 = directive1.clone();
+        let directive3 = DirectiveNote::new(account!(Assets:Checking), "b".to_string());
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
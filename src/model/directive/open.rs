@@ -1,12 +1,12 @@
 use std::collections::HashSet;
 
-use crate::model::{Account, Commodity};
+use crate::model::{Account, BookingMethod, Commodity, Options};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DirectiveOpen<'a> {
     account: Account<'a>,
     commodity_constraints: HashSet<Commodity<'a>>,
-    // TODO booking_method: BookingMethod,
+    booking_method: Option<BookingMethod>,
 }
 
 impl<'a> DirectiveOpen<'a> {
@@ -14,17 +14,32 @@ impl<'a> DirectiveOpen<'a> {
         Self {
             account,
             commodity_constraints,
+            booking_method: None,
         }
     }
 
+    pub fn with_booking_method(mut self, booking_method: BookingMethod) -> Self {
+        self.booking_method = Some(booking_method);
+        self
+    }
+
     pub fn account(&self) -> &Account<'a> {
         &self.account
     }
 
-    pub fn commodity_constraints(
-        &self,
-    ) -> impl Iterator<Item = &'_ Commodity<'a>> + ExactSizeIterator {
-        self.commodity_constraints.iter().map(|c| c)
+    pub fn commodity_constraints(&self) -> impl ExactSizeIterator<Item = &'_ Commodity<'a>> {
+        self.commodity_constraints.iter()
+    }
+
+    /// The booking method given in the directive, if any
+    pub fn booking_method(&self) -> Option<BookingMethod> {
+        self.booking_method
+    }
+
+    /// The booking method for the account, falling back to the `booking_method` option
+    pub fn effective_booking_method(&self, options: &Options) -> BookingMethod {
+        self.booking_method
+            .unwrap_or_else(|| options.booking_method())
     }
 }
 
@@ -33,7 +48,7 @@ mod tests {
     use common_macros::hash_set;
 
     use super::*;
-    use crate::model::account::account;
+    use crate::model::{PragmaOption, account::account};
 
     #[test]
     fn test_new_directive_open() {
@@ -56,6 +71,42 @@ mod tests {
         assert_eq!(directive.commodity_constraints().len(), 0);
     }
 
+    #[test]
+    fn test_booking_method() {
+        let directive = DirectiveOpen::new(account!(Assets:Stocks), hash_set![])
+            .with_booking_method(BookingMethod::Fifo);
+        let mut options = Options::new();
+        options
+            .set(PragmaOption::new("booking_method", "LIFO"))
+            .unwrap();
+
+        assert_eq!(directive.booking_method(), Some(BookingMethod::Fifo));
+        assert_eq!(
+            directive.effective_booking_method(&options),
+            BookingMethod::Fifo
+        );
+    }
+
+    #[test]
+    fn test_booking_method_falls_back_to_option() {
+        let directive = DirectiveOpen::new(account!(Assets:Stocks), hash_set![]);
+        let mut options = Options::new();
+
+        assert_eq!(directive.booking_method(), None);
+        assert_eq!(
+            directive.effective_booking_method(&options),
+            BookingMethod::Strict
+        );
+
+        options
+            .set(PragmaOption::new("booking_method", "LIFO"))
+            .unwrap();
+        assert_eq!(
+            directive.effective_booking_method(&options),
+            BookingMethod::Lifo
+        );
+    }
+
     #[test]
     fn test_clone_and_equality() {
         let account = account!(Expenses:Food);
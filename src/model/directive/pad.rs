@@ -0,0 +1,53 @@
+use crate::model::Account;
+
+/// Pad an account from a source account so that the next balance assertion on it passes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectivePad<'a> {
+    account: Account<'a>,
+    source_account: Account<'a>,
+}
+
+impl<'a> DirectivePad<'a> {
+    pub fn new(account: Account<'a>, source_account: Account<'a>) -> Self {
+        Self {
+            account,
+            source_account,
+        }
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    pub fn source_account(&self) -> &Account<'a> {
+        &self.source_account
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+
+    #[test]
+    fn test_new_directive_pad() {
+        let directive =
+            DirectivePad::new(account!(Assets:Checking), account!(Equity:OpeningBalances));
+
+        assert_eq!(*directive.account(), account!(Assets:Checking));
+        assert_eq!(
+            *directive.source_account(),
+            account!(Equity:OpeningBalances)
+        );
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectivePad::new(account!(Assets:Checking), account!(Equity:Opening));
+        let directive2 = directive1.clone();
+        let directive3 = DirectivePad::new(account!(Equity:Opening), account!(Assets:Checking));
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
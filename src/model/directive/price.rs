@@ -0,0 +1,50 @@
+use crate::model::{Amount, Commodity};
+
+/// Price of one unit of a commodity, quoted in another commodity
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectivePrice<'a> {
+    commodity: Commodity<'a>,
+    price: Amount<'a>,
+}
+
+impl<'a> DirectivePrice<'a> {
+    pub fn new(commodity: Commodity<'a>, price: Amount<'a>) -> Self {
+        Self { commodity, price }
+    }
+
+    pub fn commodity(&self) -> &Commodity<'a> {
+        &self.commodity
+    }
+
+    pub fn price(&self) -> &Amount<'a> {
+        &self.price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_new_directive_price() {
+        let price = Amount::new(dec!(579.18), commodity!(USD));
+        let directive = DirectivePrice::new(commodity!(HOOL), price.clone());
+
+        assert_eq!(*directive.commodity(), commodity!(HOOL));
+        assert_eq!(*directive.price(), price);
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 =
+            DirectivePrice::new(commodity!(HOOL), Amount::new(dec!(579.18), commodity!(USD)));
+        let directive2 = directive1.clone();
+        let directive3 =
+            DirectivePrice::new(commodity!(HOOL), Amount::new(dec!(580.00), commodity!(USD)));
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+/// Named, saved query
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveQuery<'a> {
+    name: Cow<'a, str>,
+    query: Cow<'a, str>,
+}
+
+impl<'a> DirectiveQuery<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>, query: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            name: name.into(),
+            query: query.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_directive_query() {
+        let directive = DirectiveQuery::new("cash", "SELECT account, sum(position)");
+
+        assert_eq!(directive.name(), "cash");
+        assert_eq!(directive.query(), "SELECT account, sum(position)");
+    }
+
+    #[test]
+    fn test_clone_and_equality() {
+        let directive1 = DirectiveQuery::new("cash", "SELECT 1");
+        let directive2 = directive1.clone();
+        let directive3 = DirectiveQuery::new("cash", "SELECT 2");
+
+        assert_eq!(directive1, directive2);
+        assert_ne!(directive1, directive3);
+    }
+}
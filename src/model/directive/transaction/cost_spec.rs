@@ -0,0 +1,164 @@
+use std::borrow::Cow;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::model::Commodity;
+
+/// Cost specification of a posting, i.e. the contents of `{...}` or `{{...}}`.
+///
+/// All components are optional. When augmenting a position, missing components are
+/// filled in (e.g. the date defaults to the transaction date). When reducing a position,
+/// the given components are used to select the lot, and an empty spec `{}` matches any lot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CostSpec<'a> {
+    per_unit: Option<Decimal>,
+    total: Option<Decimal>,
+    commodity: Option<Commodity<'a>>,
+    date: Option<NaiveDate>,
+    label: Option<Cow<'a, str>>,
+    merge: bool,
+}
+
+impl<'a> CostSpec<'a> {
+    /// Empty cost spec `{}`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cost spec with a per-unit cost, e.g. `{502.12 USD}`
+    pub fn per_unit(number: Decimal, commodity: Commodity<'a>) -> Self {
+        Self::new()
+            .with_per_unit_cost(number)
+            .with_commodity(commodity)
+    }
+
+    /// Cost spec with a total cost, e.g. `{{5021.20 USD}}`
+    pub fn total(number: Decimal, commodity: Commodity<'a>) -> Self {
+        Self::new()
+            .with_total_cost(number)
+            .with_commodity(commodity)
+    }
+
+    pub fn with_per_unit_cost(mut self, number: Decimal) -> Self {
+        self.per_unit = Some(number);
+        self
+    }
+
+    pub fn with_total_cost(mut self, number: Decimal) -> Self {
+        self.total = Some(number);
+        self
+    }
+
+    pub fn with_commodity(mut self, commodity: Commodity<'a>) -> Self {
+        self.commodity = Some(commodity);
+        self
+    }
+
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<Cow<'a, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_merge(mut self) -> Self {
+        self.merge = true;
+        self
+    }
+
+    pub fn per_unit_cost(&self) -> Option<&Decimal> {
+        self.per_unit.as_ref()
+    }
+
+    pub fn total_cost(&self) -> Option<&Decimal> {
+        self.total.as_ref()
+    }
+
+    pub fn commodity(&self) -> Option<&Commodity<'a>> {
+        self.commodity.as_ref()
+    }
+
+    pub fn date(&self) -> Option<&NaiveDate> {
+        self.date.as_ref()
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Whether this is the merge marker `{*}`, averaging all lots of the position
+    pub fn is_merge(&self) -> bool {
+        self.merge
+    }
+
+    /// Whether no component is given, i.e. `{}`
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_empty_cost_spec() {
+        let cost_spec = CostSpec::new();
+
+        assert!(cost_spec.is_empty());
+        assert!(!cost_spec.is_merge());
+        assert_eq!(cost_spec.per_unit_cost(), None);
+        assert_eq!(cost_spec.total_cost(), None);
+        assert_eq!(cost_spec.commodity(), None);
+        assert_eq!(cost_spec.date(), None);
+        assert_eq!(cost_spec.label(), None);
+    }
+
+    #[test]
+    fn test_per_unit_cost_spec() {
+        let cost_spec = CostSpec::per_unit(dec!(502.12), commodity!(USD));
+
+        assert!(!cost_spec.is_empty());
+        assert_eq!(cost_spec.per_unit_cost(), Some(&dec!(502.12)));
+        assert_eq!(cost_spec.total_cost(), None);
+        assert_eq!(cost_spec.commodity(), Some(&commodity!(USD)));
+    }
+
+    #[test]
+    fn test_total_cost_spec() {
+        let cost_spec = CostSpec::total(dec!(5021.20), commodity!(USD));
+
+        assert_eq!(cost_spec.per_unit_cost(), None);
+        assert_eq!(cost_spec.total_cost(), Some(&dec!(5021.20)));
+        assert_eq!(cost_spec.commodity(), Some(&commodity!(USD)));
+    }
+
+    #[test]
+    fn test_full_cost_spec() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let cost_spec = CostSpec::per_unit(dec!(502.12), commodity!(USD))
+            .with_total_cost(dec!(9.95))
+            .with_date(date)
+            .with_label("first-lot");
+
+        assert_eq!(cost_spec.per_unit_cost(), Some(&dec!(502.12)));
+        assert_eq!(cost_spec.total_cost(), Some(&dec!(9.95)));
+        assert_eq!(cost_spec.date(), Some(&date));
+        assert_eq!(cost_spec.label(), Some("first-lot"));
+        assert!(!cost_spec.is_merge());
+    }
+
+    #[test]
+    fn test_merge_cost_spec() {
+        let cost_spec = CostSpec::new().with_merge();
+
+        assert!(cost_spec.is_merge());
+        assert!(!cost_spec.is_empty());
+    }
+}
@@ -18,4 +18,6 @@ impl Flag {
     pub const HASH: Self = Flag::new('#');
     pub const QUESTION: Self = Flag::new('?');
     pub const PERCENT: Self = Flag::new('%');
+    /// Flag of transactions synthesized from a pad directive
+    pub const PADDING: Self = Flag::new('P');
 }
@@ -1,11 +1,15 @@
+mod cost_spec;
 mod description;
 mod flag;
 mod posting;
 mod posting_amount;
+mod posting_price;
 mod transaction;
 
+pub use cost_spec::CostSpec;
 pub use description::TransactionDescription;
 pub use flag::Flag;
 pub use posting::Posting;
 pub use posting_amount::PostingAmount;
+pub use posting_price::PostingPrice;
 pub use transaction::DirectiveTransaction;
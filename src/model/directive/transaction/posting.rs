@@ -1,11 +1,13 @@
-use crate::model::{Account, Flag, directive::PostingAmount};
+use crate::model::{Account, Flag, IgnoredSpan, Metadata, SourceSpan, directive::PostingAmount};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Posting<'a> {
     account: Account<'a>,
     flag: Option<Flag>,
     amount: Option<PostingAmount<'a>>,
-    // TODO In beancount-parser-lima, postings can have metadata?
+    metadata: Metadata<'a>,
+    interpolated: bool,
+    span: IgnoredSpan,
 }
 
 impl<'a> Posting<'a> {
@@ -14,6 +16,9 @@ impl<'a> Posting<'a> {
             account,
             flag: None,
             amount: Some(amount),
+            metadata: Metadata::new(),
+            interpolated: false,
+            span: IgnoredSpan::default(),
         }
     }
 
@@ -22,6 +27,9 @@ impl<'a> Posting<'a> {
             account,
             flag: None,
             amount: None,
+            metadata: Metadata::new(),
+            interpolated: false,
+            span: IgnoredSpan::default(),
         }
     }
 
@@ -30,6 +38,29 @@ impl<'a> Posting<'a> {
         self
     }
 
+    pub fn with_amount(mut self, amount: PostingAmount<'a>) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata<'a>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Mark the units of the posting as filled in by interpolation,
+    /// see [interpolate_transaction](crate::processing::interpolate_transaction)
+    pub fn with_interpolated_units(mut self) -> Self {
+        self.interpolated = true;
+        self
+    }
+
+    /// Source location of the posting. Ignored when comparing postings.
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = IgnoredSpan::new(span);
+        self
+    }
+
     pub fn account(&self) -> &Account<'a> {
         &self.account
     }
@@ -42,6 +73,22 @@ impl<'a> Posting<'a> {
         self.amount.as_ref()
     }
 
+    pub fn metadata(&self) -> &Metadata<'a> {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata<'a> {
+        &mut self.metadata
+    }
+
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.get()
+    }
+
+    pub fn span_mut(&mut self) -> Option<&mut SourceSpan> {
+        self.span.get_mut()
+    }
+
     pub fn has_amount(&self) -> bool {
         self.amount.is_some()
     }
@@ -49,12 +96,17 @@ impl<'a> Posting<'a> {
     pub fn has_flag(&self) -> bool {
         self.flag.is_some()
     }
+
+    /// Whether the units were filled in by interpolation rather than given in the ledger
+    pub fn has_interpolated_units(&self) -> bool {
+        self.interpolated
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Amount, account, commodity};
+    use crate::model::{Amount, CostSpec, PostingPrice, account, commodity};
     use rust_decimal_macros::dec;
 
     #[test]
@@ -67,11 +119,25 @@ mod tests {
 
         assert_eq!(posting.account(), &account);
         assert_eq!(posting.amount(), Some(&posting_amount));
-        assert_eq!(posting.amount().unwrap().amount(), &amount);
+        assert_eq!(posting.amount().unwrap().amount(), Some(&amount));
         assert!(posting.has_amount());
         assert!(!posting.has_flag());
     }
 
+    #[test]
+    fn test_posting_with_metadata() {
+        let key = crate::model::MetadataKey::new("receipt").unwrap();
+        let value = crate::model::MetadataValue::String("scan-42.pdf".into());
+        let mut posting = Posting::new_without_amount(account!(Expenses:Restaurant));
+        assert!(posting.metadata().is_empty());
+
+        posting.metadata_mut().insert(key.clone(), value.clone());
+        assert_eq!(posting.metadata().get("receipt"), Some(&value));
+
+        let posting = posting.with_metadata(Metadata::new().with_entry(key, value.clone()));
+        assert_eq!(posting.metadata().len(), 1);
+    }
+
     #[test]
     fn test_new_posting_without_amount() {
         let account = account!(Expenses:Restaurant);
@@ -93,7 +159,10 @@ mod tests {
 
         assert_eq!(posting.account(), &account);
         assert_eq!(posting.amount(), Some(&posting_amount));
-        assert_eq!(*posting.amount().unwrap().amount().number(), dec!(-37.45));
+        assert_eq!(
+            *posting.amount().unwrap().amount().unwrap().number(),
+            dec!(-37.45)
+        );
     }
 
     #[test]
@@ -186,12 +255,12 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
         let posting_amount = PostingAmount::new(amount.clone()).with_cost(cost.clone());
         let posting = Posting::new(account, posting_amount.clone());
 
         assert_eq!(posting.amount(), Some(&posting_amount));
-        assert_eq!(posting.amount().unwrap().amount(), &amount);
+        assert_eq!(posting.amount().unwrap().amount(), Some(&amount));
         assert_eq!(posting.amount().unwrap().cost(), Some(&cost));
         assert!(!posting.amount().unwrap().has_price());
     }
@@ -202,12 +271,12 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let price = Amount::new(dec!(55.00), usd);
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
         let posting_amount = PostingAmount::new(amount.clone()).with_price(price.clone());
         let posting = Posting::new(account, posting_amount.clone());
 
         assert_eq!(posting.amount(), Some(&posting_amount));
-        assert_eq!(posting.amount().unwrap().amount(), &amount);
+        assert_eq!(posting.amount().unwrap().amount(), Some(&amount));
         assert_eq!(posting.amount().unwrap().price(), Some(&price));
         assert!(!posting.amount().unwrap().has_cost());
     }
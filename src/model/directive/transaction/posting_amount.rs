@@ -1,44 +1,121 @@
-use crate::model::Amount;
+use rust_decimal::Decimal;
+
+use crate::model::{Amount, CostSpec, IncompleteAmount, PostingPrice};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PostingAmount<'a> {
-    amount: Amount<'a>,
-    // TODO I think beancount supports total cost vs per-item cost, with {} or {{}}.
-    cost: Option<Amount<'a>>,
-    price: Option<Amount<'a>>,
+    units: IncompleteAmount<'a>,
+    cost: Option<CostSpec<'a>>,
+    price: Option<PostingPrice<'a>>,
 }
 
 impl<'a> PostingAmount<'a> {
-    pub fn new(amount: Amount<'a>) -> Self {
+    pub fn new(units: impl Into<IncompleteAmount<'a>>) -> Self {
         Self {
-            amount,
+            units: units.into(),
             cost: None,
             price: None,
         }
     }
 
-    pub fn with_cost(mut self, cost: Amount<'a>) -> Self {
+    pub fn with_cost(mut self, cost: CostSpec<'a>) -> Self {
         self.cost = Some(cost);
         self
     }
 
-    pub fn with_price(mut self, price: Amount<'a>) -> Self {
+    pub fn with_price(mut self, price: PostingPrice<'a>) -> Self {
         self.price = Some(price);
         self
     }
 
-    pub fn amount(&self) -> &Amount<'a> {
-        &self.amount
+    /// The units of the posting, which may be missing the number or commodity
+    pub fn units(&self) -> &IncompleteAmount<'a> {
+        &self.units
+    }
+
+    /// The units of the posting, if they are complete
+    pub fn amount(&self) -> Option<&Amount<'a>> {
+        self.units.as_complete()
     }
 
-    pub fn cost(&self) -> Option<&Amount<'a>> {
+    /// Whether the units, cost and price are fully specified and need no interpolation
+    pub fn is_complete(&self) -> bool {
+        let cost_is_complete = self.cost.as_ref().is_none_or(|cost| {
+            cost.commodity().is_some()
+                && (cost.per_unit_cost().is_some() || cost.total_cost().is_some())
+        });
+        let price_is_complete = self
+            .price
+            .as_ref()
+            .is_none_or(|price| price.amount().is_complete());
+        self.units.is_complete() && cost_is_complete && price_is_complete
+    }
+
+    pub fn cost(&self) -> Option<&CostSpec<'a>> {
         self.cost.as_ref()
     }
 
-    pub fn price(&self) -> Option<&Amount<'a>> {
+    pub fn price(&self) -> Option<&PostingPrice<'a>> {
         self.price.as_ref()
     }
 
+    /// The price normalized to a per-unit price, see [PostingPrice::per_unit]
+    pub fn per_unit_price(&self) -> Option<Amount<'a>> {
+        self.price
+            .as_ref()
+            .and_then(|price| price.per_unit(self.units.number()?))
+    }
+
+    /// The amount this posting contributes to the balance of its transaction.
+    /// Like in beancount, this is the total cost if the posting is held at cost, otherwise the
+    /// converted amount if it has a price, otherwise the units.
+    /// Returns None if a number or commodity needed for it is missing.
+    pub fn weight(&self) -> Option<Amount<'a>> {
+        let units = self.amount()?;
+        let number = *units.number();
+        // total costs and prices carry the sign of the units
+        let signed = |total: Decimal| {
+            if number.is_sign_negative() {
+                -total
+            } else {
+                total
+            }
+        };
+
+        if let Some(cost) = &self.cost {
+            if cost.per_unit_cost().is_none() && cost.total_cost().is_none() {
+                return None;
+            }
+            let per_unit = match cost.per_unit_cost() {
+                Some(per_unit) => number.checked_mul(*per_unit)?,
+                None => Decimal::ZERO,
+            };
+            let total = cost.total_cost().copied().map_or(Decimal::ZERO, signed);
+            return Some(Amount::new(
+                per_unit.checked_add(total)?,
+                cost.commodity()?.clone(),
+            ));
+        }
+
+        match &self.price {
+            Some(PostingPrice::PerUnit(price)) => {
+                let price = price.as_complete()?;
+                Some(Amount::new(
+                    number.checked_mul(*price.number())?,
+                    price.commodity().clone(),
+                ))
+            }
+            Some(PostingPrice::Total(price)) => {
+                let price = price.as_complete()?;
+                Some(Amount::new(
+                    signed(*price.number()),
+                    price.commodity().clone(),
+                ))
+            }
+            None => Some(Amount::new(number, units.commodity().clone())),
+        }
+    }
+
     pub fn has_cost(&self) -> bool {
         self.cost.is_some()
     }
@@ -51,7 +128,8 @@ impl<'a> PostingAmount<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Amount, commodity};
+    use crate::model::{Amount, Commodity, PostingPrice, commodity};
+    use rstest::rstest;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -60,11 +138,12 @@ mod tests {
         let amount = Amount::new(dec!(100.50), commodity);
         let posting_amount = PostingAmount::new(amount.clone());
 
-        assert_eq!(posting_amount.amount(), &amount);
+        assert_eq!(posting_amount.amount(), Some(&amount));
         assert!(!posting_amount.has_cost());
         assert!(!posting_amount.has_price());
         assert_eq!(posting_amount.cost(), None);
         assert_eq!(posting_amount.price(), None);
+        assert_eq!(posting_amount.per_unit_price(), None);
     }
 
     #[test]
@@ -72,11 +151,11 @@ mod tests {
         let usd = commodity!(USD);
         let stock = commodity!(STOCK);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
 
         let posting_amount = PostingAmount::new(amount.clone()).with_cost(cost.clone());
 
-        assert_eq!(posting_amount.amount(), &amount);
+        assert_eq!(posting_amount.amount(), Some(&amount));
         assert!(posting_amount.has_cost());
         assert!(!posting_amount.has_price());
         assert_eq!(posting_amount.cost(), Some(&cost));
@@ -88,11 +167,11 @@ mod tests {
         let usd = commodity!(USD);
         let stock = commodity!(STOCK);
         let amount = Amount::new(dec!(10), stock);
-        let price = Amount::new(dec!(55.00), usd);
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
 
         let posting_amount = PostingAmount::new(amount.clone()).with_price(price.clone());
 
-        assert_eq!(posting_amount.amount(), &amount);
+        assert_eq!(posting_amount.amount(), Some(&amount));
         assert!(!posting_amount.has_cost());
         assert!(posting_amount.has_price());
         assert_eq!(posting_amount.cost(), None);
@@ -104,26 +183,111 @@ mod tests {
         let usd = commodity!(USD);
         let stock = commodity!(STOCK);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd.clone());
-        let price = Amount::new(dec!(55.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd.clone());
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
 
         let posting_amount = PostingAmount::new(amount.clone())
             .with_cost(cost.clone())
             .with_price(price.clone());
 
-        assert_eq!(posting_amount.amount(), &amount);
+        assert_eq!(posting_amount.amount(), Some(&amount));
         assert!(posting_amount.has_cost());
         assert!(posting_amount.has_price());
         assert_eq!(posting_amount.cost(), Some(&cost));
         assert_eq!(posting_amount.price(), Some(&price));
     }
 
+    #[test]
+    fn test_posting_amount_with_total_price() {
+        let amount = Amount::new(dec!(-10), commodity!(HOOL));
+        let price = PostingPrice::Total(Amount::new(dec!(5000), commodity!(USD)).into());
+
+        let posting_amount = PostingAmount::new(amount).with_price(price.clone());
+
+        assert_eq!(posting_amount.price(), Some(&price));
+        assert_eq!(
+            posting_amount.per_unit_price(),
+            Some(Amount::new(dec!(500), commodity!(USD)))
+        );
+    }
+
+    #[test]
+    fn test_posting_amount_is_complete() {
+        let amount = Amount::new(dec!(10), commodity!(HOOL));
+        let complete = PostingAmount::new(amount.clone())
+            .with_cost(CostSpec::per_unit(dec!(50), commodity!(USD)))
+            .with_price(PostingPrice::PerUnit(
+                Amount::new(dec!(55), commodity!(USD)).into(),
+            ));
+        assert!(complete.is_complete());
+
+        let missing_number = PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(HOOL)));
+        assert!(!missing_number.is_complete());
+        assert_eq!(missing_number.amount(), None);
+
+        let empty_cost = PostingAmount::new(amount.clone()).with_cost(CostSpec::new());
+        assert!(!empty_cost.is_complete());
+
+        let incomplete_price = PostingAmount::new(amount).with_price(PostingPrice::PerUnit(
+            IncompleteAmount::MissingCommodity(dec!(55)),
+        ));
+        assert!(!incomplete_price.is_complete());
+    }
+
+    #[rstest]
+    #[case::units(PostingAmount::new(Amount::new(dec!(-20.00), commodity!(USD))), Some((dec!(-20.00), "USD")))]
+    #[case::per_unit_cost(
+        PostingAmount::new(Amount::new(dec!(10), commodity!(HOOL)))
+            .with_cost(CostSpec::per_unit(dec!(502.12), commodity!(USD)))
+            .with_price(PostingPrice::PerUnit(Amount::new(dec!(510), commodity!(USD)).into())),
+        Some((dec!(5021.20), "USD"))
+    )]
+    #[case::total_cost(
+        PostingAmount::new(Amount::new(dec!(-10), commodity!(HOOL)))
+            .with_cost(CostSpec::total(dec!(5021.20), commodity!(USD))),
+        Some((dec!(-5021.20), "USD"))
+    )]
+    #[case::compound_cost(
+        PostingAmount::new(Amount::new(dec!(10), commodity!(HOOL)))
+            .with_cost(CostSpec::per_unit(dec!(502.12), commodity!(USD)).with_total_cost(dec!(9.95))),
+        Some((dec!(5031.15), "USD"))
+    )]
+    #[case::per_unit_price(
+        PostingAmount::new(Amount::new(dec!(-100), commodity!(EUR)))
+            .with_price(PostingPrice::PerUnit(Amount::new(dec!(1.10), commodity!(USD)).into())),
+        Some((dec!(-110.00), "USD"))
+    )]
+    #[case::total_price(
+        PostingAmount::new(Amount::new(dec!(-100), commodity!(EUR)))
+            .with_price(PostingPrice::Total(Amount::new(dec!(110), commodity!(USD)).into())),
+        Some((dec!(-110), "USD"))
+    )]
+    #[case::missing_number(PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(USD))), None)]
+    #[case::empty_cost(
+        PostingAmount::new(Amount::new(dec!(-10), commodity!(HOOL))).with_cost(CostSpec::new()),
+        None
+    )]
+    #[case::incomplete_price(
+        PostingAmount::new(Amount::new(dec!(-100), commodity!(EUR)))
+            .with_price(PostingPrice::PerUnit(IncompleteAmount::MissingNumber(commodity!(USD)))),
+        None
+    )]
+    fn test_posting_amount_weight(
+        #[case] posting_amount: PostingAmount,
+        #[case] expected: Option<(Decimal, &str)>,
+    ) {
+        let expected = expected.map(|(number, commodity)| {
+            Amount::new(number, Commodity::try_from(commodity).unwrap())
+        });
+        assert_eq!(posting_amount.weight(), expected);
+    }
+
     #[test]
     fn test_posting_amount_equality() {
         let usd = commodity!(USD);
         let stock = commodity!(STOCK);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
 
         let posting_amount1 = PostingAmount::new(amount.clone()).with_cost(cost.clone());
         let posting_amount2 = PostingAmount::new(amount).with_cost(cost);
@@ -136,7 +300,7 @@ mod tests {
         let usd = commodity!(USD);
         let stock = commodity!(STOCK);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
 
         let posting_amount1 = PostingAmount::new(amount).with_cost(cost);
         let posting_amount2 = posting_amount1.clone();
@@ -0,0 +1,84 @@
+use rust_decimal::Decimal;
+
+use crate::model::{Amount, IncompleteAmount};
+
+/// Price annotation of a posting, either per unit (`@`) or for all units of the posting (`@@`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PostingPrice<'a> {
+    PerUnit(IncompleteAmount<'a>),
+    Total(IncompleteAmount<'a>),
+}
+
+impl<'a> PostingPrice<'a> {
+    /// The annotated amount, without distinguishing per-unit and total prices
+    pub fn amount(&self) -> &IncompleteAmount<'a> {
+        match self {
+            PostingPrice::PerUnit(amount) | PostingPrice::Total(amount) => amount,
+        }
+    }
+
+    pub fn is_total(&self) -> bool {
+        matches!(self, PostingPrice::Total(_))
+    }
+
+    /// Normalize to a per-unit price for a posting of the given number of units.
+    /// Returns None for an incomplete price or a total price on zero units.
+    pub fn per_unit(&self, units: &Decimal) -> Option<Amount<'a>> {
+        match self {
+            PostingPrice::PerUnit(amount) => amount.as_complete().cloned(),
+            PostingPrice::Total(amount) => {
+                let amount = amount.as_complete()?;
+                let number = amount.number().checked_div(units.abs())?;
+                Some(Amount::new(number, amount.commodity().clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_per_unit_price() {
+        let amount = Amount::new(dec!(500), commodity!(USD));
+        let price = PostingPrice::PerUnit(amount.clone().into());
+
+        assert_eq!(price.amount().as_complete(), Some(&amount));
+        assert!(!price.is_total());
+        assert_eq!(price.per_unit(&dec!(-10)), Some(amount));
+    }
+
+    #[test]
+    fn test_total_price() {
+        let amount = Amount::new(dec!(5000), commodity!(USD));
+        let price = PostingPrice::Total(amount.clone().into());
+
+        assert_eq!(price.amount().as_complete(), Some(&amount));
+        assert!(price.is_total());
+        assert_eq!(
+            price.per_unit(&dec!(-10)),
+            Some(Amount::new(dec!(500), commodity!(USD)))
+        );
+        assert_eq!(
+            price.per_unit(&dec!(3)),
+            Some(Amount::new(dec!(5000) / dec!(3), commodity!(USD)))
+        );
+    }
+
+    #[test]
+    fn test_total_price_on_zero_units() {
+        let price = PostingPrice::Total(Amount::new(dec!(5000), commodity!(USD)).into());
+
+        assert_eq!(price.per_unit(&dec!(0)), None);
+    }
+
+    #[test]
+    fn test_incomplete_price() {
+        let price = PostingPrice::PerUnit(IncompleteAmount::MissingNumber(commodity!(USD)));
+
+        assert_eq!(price.per_unit(&dec!(10)), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::model::{
-    Flag,
+    Flag, Link, Tag,
     directive::{Posting, transaction::TransactionDescription},
 };
 
@@ -7,6 +7,8 @@ use crate::model::{
 pub struct DirectiveTransaction<'a> {
     flag: Flag,
     description: Option<TransactionDescription<'a>>,
+    tags: Vec<Tag<'a>>,
+    links: Vec<Link<'a>>,
     postings: Vec<Posting<'a>>,
 }
 
@@ -15,15 +17,16 @@ impl<'a> DirectiveTransaction<'a> {
         Self {
             flag,
             description: None,
+            tags: Vec::new(),
+            links: Vec::new(),
             postings: Vec::new(),
         }
     }
 
     pub fn with_description(self, description: TransactionDescription<'a>) -> Self {
         Self {
-            flag: self.flag,
             description: Some(description),
-            postings: self.postings,
+            ..self
         }
     }
 
@@ -35,10 +38,48 @@ impl<'a> DirectiveTransaction<'a> {
         self.description.as_ref()
     }
 
+    /// Tags in the order they were added, without duplicates
+    pub fn tags(&self) -> &[Tag<'a>] {
+        &self.tags
+    }
+
+    /// Add a tag, unless the transaction already has it
+    pub fn add_tag(&mut self, tag: Tag<'a>) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    pub fn with_tag(mut self, tag: Tag<'a>) -> Self {
+        self.add_tag(tag);
+        self
+    }
+
+    /// Links in the order they were added, without duplicates
+    pub fn links(&self) -> &[Link<'a>] {
+        &self.links
+    }
+
+    /// Add a link, unless the transaction already has it
+    pub fn add_link(&mut self, link: Link<'a>) {
+        if !self.links.contains(&link) {
+            self.links.push(link);
+        }
+    }
+
+    pub fn with_link(mut self, link: Link<'a>) -> Self {
+        self.add_link(link);
+        self
+    }
+
     pub fn postings(&self) -> &[Posting<'a>] {
         &self.postings
     }
 
+    pub fn postings_mut(&mut self) -> &mut [Posting<'a>] {
+        &mut self.postings
+    }
+
     pub fn add_posting(&mut self, posting: Posting<'a>) {
         self.postings.push(posting);
     }
@@ -130,6 +171,30 @@ mod tests {
         assert_eq!(transaction.postings()[1], posting2);
     }
 
+    #[test]
+    fn test_add_tag_deduplicates() {
+        let trip = Tag::new("trip-japan").unwrap();
+        let food = Tag::new("food").unwrap();
+
+        let mut transaction = DirectiveTransaction::new(Flag::ASTERISK)
+            .with_tag(trip.clone())
+            .with_tag(food.clone());
+        transaction.add_tag(trip.clone());
+
+        assert_eq!(transaction.tags(), [trip, food]);
+    }
+
+    #[test]
+    fn test_add_link_deduplicates() {
+        let invoice = Link::new("invoice-42").unwrap();
+
+        let mut transaction = DirectiveTransaction::new(Flag::ASTERISK).with_link(invoice.clone());
+        transaction.add_link(invoice.clone());
+
+        assert_eq!(transaction.links(), [invoice]);
+        assert!(transaction.tags().is_empty());
+    }
+
     #[test]
     fn test_add_posting() {
         let mut transaction = DirectiveTransaction::new(Flag::ASTERISK);
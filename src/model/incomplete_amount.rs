@@ -0,0 +1,86 @@
+use rust_decimal::Decimal;
+
+use crate::model::{Amount, Commodity};
+
+/// An amount in a posting or price annotation where the number or the commodity may be
+/// omitted, e.g. `Assets:Cash  USD` or `Assets:Cash  10`. The missing part is filled in
+/// by interpolation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IncompleteAmount<'a> {
+    Complete(Amount<'a>),
+    MissingNumber(Commodity<'a>),
+    MissingCommodity(Decimal),
+}
+
+impl<'a> IncompleteAmount<'a> {
+    pub fn number(&self) -> Option<&Decimal> {
+        match self {
+            IncompleteAmount::Complete(amount) => Some(amount.number()),
+            IncompleteAmount::MissingNumber(_) => None,
+            IncompleteAmount::MissingCommodity(number) => Some(number),
+        }
+    }
+
+    pub fn commodity(&self) -> Option<&Commodity<'a>> {
+        match self {
+            IncompleteAmount::Complete(amount) => Some(amount.commodity()),
+            IncompleteAmount::MissingNumber(commodity) => Some(commodity),
+            IncompleteAmount::MissingCommodity(_) => None,
+        }
+    }
+
+    pub fn as_complete(&self) -> Option<&Amount<'a>> {
+        match self {
+            IncompleteAmount::Complete(amount) => Some(amount),
+            _ => None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, IncompleteAmount::Complete(_))
+    }
+}
+
+impl<'a> From<Amount<'a>> for IncompleteAmount<'a> {
+    fn from(amount: Amount<'a>) -> Self {
+        IncompleteAmount::Complete(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_complete() {
+        let amount = Amount::new(dec!(10), commodity!(USD));
+        let incomplete = IncompleteAmount::from(amount.clone());
+
+        assert!(incomplete.is_complete());
+        assert_eq!(incomplete.as_complete(), Some(&amount));
+        assert_eq!(incomplete.number(), Some(&dec!(10)));
+        assert_eq!(incomplete.commodity(), Some(&commodity!(USD)));
+    }
+
+    #[test]
+    fn test_missing_number() {
+        let incomplete = IncompleteAmount::MissingNumber(commodity!(USD));
+
+        assert!(!incomplete.is_complete());
+        assert_eq!(incomplete.as_complete(), None);
+        assert_eq!(incomplete.number(), None);
+        assert_eq!(incomplete.commodity(), Some(&commodity!(USD)));
+    }
+
+    #[test]
+    fn test_missing_commodity() {
+        let incomplete = IncompleteAmount::MissingCommodity(dec!(10));
+
+        assert!(!incomplete.is_complete());
+        assert_eq!(incomplete.as_complete(), None);
+        assert_eq!(incomplete.number(), Some(&dec!(10)));
+        assert_eq!(incomplete.commodity(), None);
+    }
+}
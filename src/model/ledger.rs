@@ -0,0 +1,36 @@
+use crate::model::{Directive, Pragma};
+
+/// Contents of a ledger file.
+/// Scoping pragmas like `pushtag` are already applied to the directives and aren't kept as pragmas.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger<'a> {
+    directives: Vec<Directive<'a>>,
+    pragmas: Vec<Pragma<'a>>,
+}
+
+impl<'a> Ledger<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directives in the order they appear in the file
+    pub fn directives(&self) -> &[Directive<'a>] {
+        &self.directives
+    }
+
+    pub fn pragmas(&self) -> &[Pragma<'a>] {
+        &self.pragmas
+    }
+
+    pub fn add_directive(&mut self, directive: Directive<'a>) {
+        self.directives.push(directive);
+    }
+
+    pub fn add_pragma(&mut self, pragma: Pragma<'a>) {
+        self.pragmas.push(pragma);
+    }
+
+    pub fn into_directives(self) -> Vec<Directive<'a>> {
+        self.directives
+    }
+}
@@ -0,0 +1,83 @@
+use derive_more::Display;
+use std::borrow::Cow;
+use thiserror::Error;
+
+use super::tag::is_valid_tag_char;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InvalidLinkError {
+    #[error("Link cannot be empty")]
+    Empty,
+    #[error("Link can only contain letters, numbers, dashes, underscores, slashes or periods")]
+    InvalidCharacter,
+}
+
+/// A link like `^invoice-2024-03`, stored without the leading `^`
+#[derive(Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Link<'a> {
+    link: Cow<'a, str>,
+}
+
+impl<'a> Link<'a> {
+    pub fn new(link: impl Into<Cow<'a, str>>) -> Result<Self, InvalidLinkError> {
+        let this = Self { link: link.into() };
+        this.validate()?;
+        Ok(this)
+    }
+
+    fn validate(&self) -> Result<(), InvalidLinkError> {
+        if self.link.is_empty() {
+            return Err(InvalidLinkError::Empty);
+        }
+        if !self.link.chars().all(is_valid_tag_char) {
+            return Err(InvalidLinkError::InvalidCharacter);
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for Link<'static> {
+    type Error = InvalidLinkError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Link::new(value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Link<'a> {
+    type Error = InvalidLinkError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Link::new(value)
+    }
+}
+
+impl AsRef<str> for Link<'_> {
+    fn as_ref(&self) -> &str {
+        &self.link
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_links() {
+        assert!(Link::new("invoice-2024-03").is_ok());
+        assert!(Link::new("receipts/2024.03_01").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_links() {
+        assert_eq!(Link::new("").unwrap_err(), InvalidLinkError::Empty);
+        assert_eq!(
+            Link::new("^invoice").unwrap_err(),
+            InvalidLinkError::InvalidCharacter
+        );
+        assert_eq!(
+            Link::new("invoice 42").unwrap_err(),
+            InvalidLinkError::InvalidCharacter
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::model::{MetadataKey, MetadataValue};
+
+/// Key/value metadata, keeping the entries in the order they were added.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Metadata<'a> {
+    entries: Vec<(MetadataKey<'a>, MetadataValue<'a>)>,
+}
+
+impl<'a> Metadata<'a> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Set the value for a key. If the key already exists, its value is replaced in place
+    /// and the previous value is returned.
+    pub fn insert(
+        &mut self,
+        key: MetadataKey<'a>,
+        value: MetadataValue<'a>,
+    ) -> Option<MetadataValue<'a>> {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn with_entry(mut self, key: MetadataKey<'a>, value: MetadataValue<'a>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&MetadataValue<'a>> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.as_ref() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&MetadataKey<'a>, &MetadataValue<'a>)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<'a> FromIterator<(MetadataKey<'a>, MetadataValue<'a>)> for Metadata<'a> {
+    fn from_iter<T: IntoIterator<Item = (MetadataKey<'a>, MetadataValue<'a>)>>(iter: T) -> Self {
+        let mut metadata = Metadata::new();
+        for (key, value) in iter {
+            metadata.insert(key, value);
+        }
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn key(key: &str) -> MetadataKey<'_> {
+        MetadataKey::new(key).unwrap()
+    }
+
+    #[test]
+    fn test_new_metadata() {
+        let metadata = Metadata::new();
+        assert!(metadata.is_empty());
+        assert_eq!(metadata.len(), 0);
+        assert_eq!(metadata.get("name"), None);
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut metadata = Metadata::new();
+        assert_eq!(
+            metadata.insert(key("name"), MetadataValue::String("US Dollar".into())),
+            None
+        );
+        assert_eq!(
+            metadata.insert(key("precision"), MetadataValue::Number(dec!(2))),
+            None
+        );
+
+        assert_eq!(metadata.len(), 2);
+        assert!(metadata.contains_key("name"));
+        assert_eq!(
+            metadata.get("name").and_then(|v| v.as_str()),
+            Some("US Dollar")
+        );
+        assert_eq!(
+            metadata.get("precision").and_then(|v| v.as_number()),
+            Some(&dec!(2))
+        );
+        assert!(!metadata.contains_key("missing"));
+    }
+
+    #[test]
+    fn test_insert_replaces_in_place() {
+        let mut metadata = Metadata::new()
+            .with_entry(key("a"), MetadataValue::Bool(true))
+            .with_entry(key("b"), MetadataValue::Bool(true));
+
+        let previous = metadata.insert(key("a"), MetadataValue::Bool(false));
+
+        assert_eq!(previous, Some(MetadataValue::Bool(true)));
+        assert_eq!(metadata.len(), 2);
+        let keys: Vec<&str> = metadata.iter().map(|(k, _)| k.as_ref()).collect();
+        assert_eq!(keys, ["a", "b"]);
+        assert_eq!(metadata.get("a"), Some(&MetadataValue::Bool(false)));
+    }
+
+    #[test]
+    fn test_iteration_keeps_insertion_order() {
+        let metadata: Metadata = [
+            (key("zeta"), MetadataValue::Number(dec!(1))),
+            (key("alpha"), MetadataValue::Number(dec!(2))),
+            (key("mid"), MetadataValue::Number(dec!(3))),
+        ]
+        .into_iter()
+        .collect();
+
+        let keys: Vec<&str> = metadata.iter().map(|(k, _)| k.as_ref()).collect();
+        assert_eq!(keys, ["zeta", "alpha", "mid"]);
+    }
+
+    #[test]
+    fn test_equality() {
+        let metadata1 = Metadata::new().with_entry(key("name"), MetadataValue::Bool(true));
+        let metadata2 = metadata1.clone();
+        let metadata3 = Metadata::new().with_entry(key("name"), MetadataValue::Bool(false));
+
+        assert_eq!(metadata1, metadata2);
+        assert_ne!(metadata1, metadata3);
+    }
+}
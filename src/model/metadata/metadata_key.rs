@@ -0,0 +1,141 @@
+use derive_more::Display;
+use std::borrow::Cow;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InvalidMetadataKeyError {
+    #[error("Metadata key cannot be empty")]
+    Empty,
+    #[error("Metadata key must start with a lowercase letter")]
+    InvalidStart,
+    #[error("Metadata key can only contain letters, numbers, dashes or underscores")]
+    InvalidCharacter,
+}
+
+#[derive(Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MetadataKey<'a> {
+    key: Cow<'a, str>,
+}
+
+impl<'a> MetadataKey<'a> {
+    pub fn new(key: impl Into<Cow<'a, str>>) -> Result<Self, InvalidMetadataKeyError> {
+        let this = Self { key: key.into() };
+        this.validate()?;
+        Ok(this)
+    }
+
+    fn validate(&self) -> Result<(), InvalidMetadataKeyError> {
+        let mut chars = self.key.chars();
+
+        // first character needs to be a lowercase letter
+        match chars.next() {
+            None => return Err(InvalidMetadataKeyError::Empty),
+            Some(c) => {
+                if !c.is_ascii_lowercase() {
+                    return Err(InvalidMetadataKeyError::InvalidStart);
+                }
+            }
+        }
+
+        // later characters need to be letters, numbers, dashes or underscores
+        for c in chars {
+            if !c.is_alphanumeric() && c != '-' && c != '_' {
+                return Err(InvalidMetadataKeyError::InvalidCharacter);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for MetadataKey<'static> {
+    type Error = InvalidMetadataKeyError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        MetadataKey::new(value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MetadataKey<'a> {
+    type Error = InvalidMetadataKeyError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        MetadataKey::new(value)
+    }
+}
+
+impl AsRef<str> for MetadataKey<'_> {
+    fn as_ref(&self) -> &str {
+        &self.key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_keys() {
+        assert!(MetadataKey::new("name").is_ok());
+        assert!(MetadataKey::new("precision").is_ok());
+        assert!(MetadataKey::new("export-name").is_ok());
+        assert!(MetadataKey::new("price_source").is_ok());
+        assert!(MetadataKey::new("isin2").is_ok());
+        assert!(MetadataKey::new("camelCase").is_ok());
+        assert!(MetadataKey::new("a").is_ok());
+    }
+
+    #[test]
+    fn test_empty_key() {
+        let result = MetadataKey::new("");
+        assert_eq!(result.unwrap_err(), InvalidMetadataKeyError::Empty);
+    }
+
+    #[test]
+    fn test_invalid_start() {
+        assert_eq!(
+            MetadataKey::new("Name").unwrap_err(),
+            InvalidMetadataKeyError::InvalidStart
+        );
+        assert_eq!(
+            MetadataKey::new("1name").unwrap_err(),
+            InvalidMetadataKeyError::InvalidStart
+        );
+        assert_eq!(
+            MetadataKey::new("-name").unwrap_err(),
+            InvalidMetadataKeyError::InvalidStart
+        );
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        assert_eq!(
+            MetadataKey::new("na:me").unwrap_err(),
+            InvalidMetadataKeyError::InvalidCharacter
+        );
+        assert_eq!(
+            MetadataKey::new("na me").unwrap_err(),
+            InvalidMetadataKeyError::InvalidCharacter
+        );
+        assert_eq!(
+            MetadataKey::new("na.me").unwrap_err(),
+            InvalidMetadataKeyError::InvalidCharacter
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        let key: Result<MetadataKey, _> = "name".try_into();
+        assert!(key.is_ok());
+
+        let key: Result<MetadataKey, _> = "Name".to_string().try_into();
+        assert!(key.is_err());
+    }
+
+    #[test]
+    fn test_as_ref_and_display() {
+        let key = MetadataKey::new("precision").unwrap();
+        assert_eq!(key.as_ref(), "precision");
+        assert_eq!(format!("{}", key), "precision");
+    }
+}
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::model::{Account, Amount, Commodity, Tag};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MetadataValue<'a> {
+    String(Cow<'a, str>),
+    Number(Decimal),
+    Amount(Amount<'a>),
+    Date(NaiveDate),
+    Account(Account<'a>),
+    Commodity(Commodity<'a>),
+    Tag(Tag<'a>),
+    Bool(bool),
+}
+
+impl<'a> MetadataValue<'a> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MetadataValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Decimal> {
+        match self {
+            MetadataValue::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn as_amount(&self) -> Option<&Amount<'a>> {
+        match self {
+            MetadataValue::Amount(amount) => Some(amount),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<&NaiveDate> {
+        match self {
+            MetadataValue::Date(date) => Some(date),
+            _ => None,
+        }
+    }
+
+    pub fn as_account(&self) -> Option<&Account<'a>> {
+        match self {
+            MetadataValue::Account(account) => Some(account),
+            _ => None,
+        }
+    }
+
+    pub fn as_commodity(&self) -> Option<&Commodity<'a>> {
+        match self {
+            MetadataValue::Commodity(commodity) => Some(commodity),
+            _ => None,
+        }
+    }
+
+    pub fn as_tag(&self) -> Option<&Tag<'a>> {
+        match self {
+            MetadataValue::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MetadataValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{account, commodity};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_accessors() {
+        let string = MetadataValue::String("US Dollar".into());
+        assert_eq!(string.as_str(), Some("US Dollar"));
+        assert_eq!(string.as_number(), None);
+
+        let number = MetadataValue::Number(dec!(2));
+        assert_eq!(number.as_number(), Some(&dec!(2)));
+        assert_eq!(number.as_str(), None);
+
+        let commodity = MetadataValue::Commodity(commodity!(USD));
+        assert_eq!(commodity.as_commodity(), Some(&commodity!(USD)));
+        assert_eq!(commodity.as_bool(), None);
+
+        let amount = MetadataValue::Amount(Amount::new(dec!(10), commodity!(USD)));
+        assert_eq!(
+            amount.as_amount(),
+            Some(&Amount::new(dec!(10), commodity!(USD)))
+        );
+        assert_eq!(amount.as_number(), None);
+
+        let date = MetadataValue::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert_eq!(date.as_date(), NaiveDate::from_ymd_opt(2024, 3, 1).as_ref());
+        assert_eq!(date.as_str(), None);
+
+        let account = MetadataValue::Account(account!(Assets:Cash));
+        assert_eq!(account.as_account(), Some(&account!(Assets:Cash)));
+        assert_eq!(account.as_commodity(), None);
+
+        let tag = MetadataValue::Tag(Tag::new("trip").unwrap());
+        assert_eq!(tag.as_tag(), Some(&Tag::new("trip").unwrap()));
+        assert_eq!(tag.as_str(), None);
+
+        let boolean = MetadataValue::Bool(true);
+        assert_eq!(boolean.as_bool(), Some(true));
+        assert_eq!(boolean.as_commodity(), None);
+    }
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(
+            MetadataValue::String("a".into()),
+            MetadataValue::String("a".to_string().into())
+        );
+        assert_ne!(
+            MetadataValue::String("TRUE".into()),
+            MetadataValue::Bool(true)
+        );
+    }
+}
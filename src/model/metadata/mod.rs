@@ -0,0 +1,7 @@
+mod metadata;
+mod metadata_key;
+mod metadata_value;
+
+pub use metadata::Metadata;
+pub use metadata_key::{InvalidMetadataKeyError, MetadataKey};
+pub use metadata_value::MetadataValue;
@@ -7,10 +7,39 @@ pub use amount::Amount;
 mod amount_with_tolerance;
 pub use amount_with_tolerance::AmountWithTolerance;
 
+mod booking_method;
+pub use booking_method::{BookingMethod, InvalidBookingMethodError};
+
 mod commodity;
 pub use commodity::{Commodity, InvalidCommodityError, commodity};
 
 pub mod directive;
 pub use directive::{
-    Directive, DirectiveBalance, DirectiveOpen, DirectiveTransaction, DirectiveVariant, Flag,
+    CostSpec, Directive, DirectiveBalance, DirectiveClose, DirectiveCommodity, DirectiveCustom,
+    DirectiveDocument, DirectiveEvent, DirectiveNote, DirectiveOpen, DirectivePad, DirectivePrice,
+    DirectiveQuery, DirectiveTransaction, DirectiveVariant, Flag, PostingPrice,
 };
+
+mod incomplete_amount;
+pub use incomplete_amount::IncompleteAmount;
+
+mod ledger;
+pub use ledger::Ledger;
+
+mod link;
+pub use link::{InvalidLinkError, Link};
+
+mod metadata;
+pub use metadata::{InvalidMetadataKeyError, Metadata, MetadataKey, MetadataValue};
+
+pub mod pragma;
+pub use pragma::{
+    InvalidOptionError, Options, Pragma, PragmaInclude, PragmaOption, PragmaPlugin, PragmaPushMeta,
+};
+
+mod source_span;
+pub(crate) use source_span::IgnoredSpan;
+pub use source_span::{FileId, SourceSpan};
+
+mod tag;
+pub use tag::{InvalidTagError, Tag};
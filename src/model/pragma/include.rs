@@ -0,0 +1,48 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+/// Include another ledger file. Relative paths are relative to the including file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PragmaInclude<'a> {
+    path: Cow<'a, Path>,
+}
+
+impl<'a> PragmaInclude<'a> {
+    pub fn new(path: impl Into<Cow<'a, Path>>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Create an include pragma from a path given as a string, e.g. from a ledger file
+    pub fn from_str_path(path: Cow<'a, str>) -> Self {
+        match path {
+            Cow::Borrowed(path) => Self::new(Path::new(path)),
+            Cow::Owned(path) => Self::new(PathBuf::from(path)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_pragma_include() {
+        let include = PragmaInclude::new(Path::new("2023.beancount"));
+
+        assert_eq!(include.path(), Path::new("2023.beancount"));
+    }
+
+    #[test]
+    fn test_from_str_path() {
+        let borrowed = PragmaInclude::from_str_path(Cow::Borrowed("2023.beancount"));
+        let owned = PragmaInclude::from_str_path(Cow::Owned("2023.beancount".to_string()));
+
+        assert_eq!(borrowed, owned);
+    }
+}
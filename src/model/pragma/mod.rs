@@ -0,0 +1,13 @@
+mod include;
+mod option;
+mod options;
+mod plugin;
+mod pragma;
+mod push_meta;
+
+pub use include::PragmaInclude;
+pub use option::PragmaOption;
+pub use options::{InvalidOptionError, Options};
+pub use plugin::PragmaPlugin;
+pub use pragma::Pragma;
+pub use push_meta::PragmaPushMeta;
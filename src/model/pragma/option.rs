@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use crate::model::{IgnoredSpan, SourceSpan};
+
+/// A raw `option "name" "value"` line, see [crate::model::Options] for the interpreted options
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PragmaOption<'a> {
+    name: Cow<'a, str>,
+    value: Cow<'a, str>,
+    span: IgnoredSpan,
+}
+
+impl<'a> PragmaOption<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            span: IgnoredSpan::default(),
+        }
+    }
+
+    /// Source location of the option. Ignored when comparing options.
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = IgnoredSpan::new(span);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.get()
+    }
+
+    pub fn span_mut(&mut self) -> Option<&mut SourceSpan> {
+        self.span.get_mut()
+    }
+
+    pub fn into_value(self) -> Cow<'a, str> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_pragma_option() {
+        let option = PragmaOption::new("title", "Our Ledger");
+
+        assert_eq!(option.name(), "title");
+        assert_eq!(option.value(), "Our Ledger");
+        assert_eq!(option.into_value(), "Our Ledger");
+    }
+}
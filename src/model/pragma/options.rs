@@ -0,0 +1,436 @@
+use std::{borrow::Cow, collections::BTreeMap, str::FromStr};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+use crate::model::{
+    AccountType, BookingMethod, Commodity, InvalidBookingMethodError, InvalidCommodityError,
+    pragma::PragmaOption,
+};
+
+const OPTION_TITLE: &str = "title";
+const OPTION_OPERATING_CURRENCY: &str = "operating_currency";
+const OPTION_INFERRED_TOLERANCE_DEFAULT: &str = "inferred_tolerance_default";
+const OPTION_INFERRED_TOLERANCE_MULTIPLIER: &str = "inferred_tolerance_multiplier";
+const OPTION_INFER_TOLERANCE_FROM_COST: &str = "infer_tolerance_from_cost";
+const OPTION_BOOKING_METHOD: &str = "booking_method";
+
+const ANY_COMMODITY: &str = "*";
+const DEFAULT_INFERRED_TOLERANCE_MULTIPLIER: Decimal = dec!(0.5);
+
+const ACCOUNT_TYPE_OPTIONS: [(AccountType, &str); 5] = [
+    (AccountType::Assets, "name_assets"),
+    (AccountType::Liabilities, "name_liabilities"),
+    (AccountType::Income, "name_income"),
+    (AccountType::Expenses, "name_expenses"),
+    (AccountType::Equity, "name_equity"),
+];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InvalidOptionError {
+    #[error("Invalid commodity in option {option}: {error}")]
+    InvalidCommodity {
+        option: String,
+        error: InvalidCommodityError,
+    },
+
+    #[error("Invalid number in option {option}: {value}")]
+    InvalidNumber { option: String, value: String },
+
+    #[error("Invalid boolean in option {option}: {value}, expected TRUE or FALSE")]
+    InvalidBool { option: String, value: String },
+
+    #[error(
+        "Invalid tolerance in option {OPTION_INFERRED_TOLERANCE_DEFAULT}: {0}, expected <commodity>:<number>"
+    )]
+    InvalidToleranceDefault(String),
+
+    #[error(transparent)]
+    InvalidBookingMethod(#[from] InvalidBookingMethodError),
+}
+
+/// Ledger options set by `option` pragmas.
+/// Known options are interpreted, unknown ones are kept as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options<'a> {
+    title: Option<Cow<'a, str>>,
+    account_type_names: BTreeMap<AccountType, Cow<'a, str>>,
+    operating_currencies: Vec<Commodity<'a>>,
+    default_tolerance: Option<Decimal>,
+    inferred_tolerance_defaults: BTreeMap<Commodity<'a>, Decimal>,
+    inferred_tolerance_multiplier: Option<Decimal>,
+    infer_tolerance_from_cost: Option<bool>,
+    booking_method: Option<BookingMethod>,
+    unknown_options: Vec<PragmaOption<'a>>,
+}
+
+impl<'a> Options<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_pragmas(
+        options: impl IntoIterator<Item = PragmaOption<'a>>,
+    ) -> Result<Self, InvalidOptionError> {
+        let mut result = Self::new();
+        for option in options {
+            result.set(option)?;
+        }
+        Ok(result)
+    }
+
+    /// Apply the options one by one like [Options::from_pragmas], but skip invalid options
+    /// instead of failing, so that the valid ones still apply.
+    /// Returns the invalid options together with their errors.
+    pub fn from_valid_pragmas(
+        options: impl IntoIterator<Item = PragmaOption<'a>>,
+    ) -> (Self, Vec<(PragmaOption<'a>, InvalidOptionError)>) {
+        let mut result = Self::new();
+        let mut errors = Vec::new();
+        for option in options {
+            if let Err(error) = result.set(option.clone()) {
+                errors.push((option, error));
+            }
+        }
+        (result, errors)
+    }
+
+    /// Apply an option. Options that can be given multiple times (e.g. `operating_currency`)
+    /// accumulate, others overwrite the previous value.
+    pub fn set(&mut self, option: PragmaOption<'a>) -> Result<(), InvalidOptionError> {
+        if let Some((account_type, _)) = ACCOUNT_TYPE_OPTIONS
+            .iter()
+            .find(|(_, name)| *name == option.name())
+        {
+            self.account_type_names
+                .insert(*account_type, option.into_value());
+            return Ok(());
+        }
+
+        match option.name() {
+            OPTION_TITLE => self.title = Some(option.into_value()),
+            OPTION_OPERATING_CURRENCY => {
+                let commodity = parse_commodity(OPTION_OPERATING_CURRENCY, option.into_value())?;
+                if !self.operating_currencies.contains(&commodity) {
+                    self.operating_currencies.push(commodity);
+                }
+            }
+            OPTION_INFERRED_TOLERANCE_DEFAULT => {
+                let (commodity, tolerance) =
+                    split_tolerance_default(option.value()).ok_or_else(|| {
+                        InvalidOptionError::InvalidToleranceDefault(option.value().to_string())
+                    })?;
+                if commodity == ANY_COMMODITY {
+                    self.default_tolerance = Some(tolerance);
+                } else {
+                    let commodity = parse_commodity(
+                        OPTION_INFERRED_TOLERANCE_DEFAULT,
+                        Cow::Owned(commodity.to_string()),
+                    )?;
+                    self.inferred_tolerance_defaults
+                        .insert(commodity, tolerance);
+                }
+            }
+            OPTION_INFERRED_TOLERANCE_MULTIPLIER => {
+                self.inferred_tolerance_multiplier = Some(parse_decimal(
+                    OPTION_INFERRED_TOLERANCE_MULTIPLIER,
+                    option.value(),
+                )?);
+            }
+            OPTION_INFER_TOLERANCE_FROM_COST => {
+                self.infer_tolerance_from_cost = Some(parse_bool(
+                    OPTION_INFER_TOLERANCE_FROM_COST,
+                    option.value(),
+                )?);
+            }
+            OPTION_BOOKING_METHOD => {
+                self.booking_method = Some(BookingMethod::from_str(option.value())?);
+            }
+            _ => self.unknown_options.push(option),
+        }
+        Ok(())
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Name of the root account of the given type, e.g. "Assets"
+    pub fn account_type_name(&self, account_type: AccountType) -> &str {
+        self.account_type_names
+            .get(&account_type)
+            .map(|name| name.as_ref())
+            .unwrap_or(account_type.default_name())
+    }
+
+    pub fn operating_currencies(&self) -> &[Commodity<'a>] {
+        &self.operating_currencies
+    }
+
+    /// Tolerance to use for amounts in the given commodity, if configured
+    pub fn inferred_tolerance_default(&self, commodity: &Commodity) -> Option<Decimal> {
+        self.inferred_tolerance_defaults
+            .get(commodity)
+            .copied()
+            .or(self.default_tolerance)
+    }
+
+    pub fn inferred_tolerance_multiplier(&self) -> Decimal {
+        self.inferred_tolerance_multiplier
+            .unwrap_or(DEFAULT_INFERRED_TOLERANCE_MULTIPLIER)
+    }
+
+    pub fn infer_tolerance_from_cost(&self) -> bool {
+        self.infer_tolerance_from_cost.unwrap_or(false)
+    }
+
+    pub fn booking_method(&self) -> BookingMethod {
+        self.booking_method.unwrap_or_default()
+    }
+
+    /// Options that aren't interpreted, in the order they were given
+    pub fn unknown_options(&self) -> &[PragmaOption<'a>] {
+        &self.unknown_options
+    }
+
+    /// The option pragmas that reproduce these options. Options that were never set are omitted.
+    pub fn to_pragmas(&self) -> Vec<PragmaOption<'a>> {
+        let mut pragmas = Vec::new();
+        if let Some(title) = &self.title {
+            pragmas.push(PragmaOption::new(OPTION_TITLE, title.clone()));
+        }
+        for (account_type, option_name) in ACCOUNT_TYPE_OPTIONS {
+            if let Some(name) = self.account_type_names.get(&account_type) {
+                pragmas.push(PragmaOption::new(option_name, name.clone()));
+            }
+        }
+        for commodity in &self.operating_currencies {
+            pragmas.push(PragmaOption::new(
+                OPTION_OPERATING_CURRENCY,
+                commodity.to_string(),
+            ));
+        }
+        if let Some(tolerance) = self.default_tolerance {
+            pragmas.push(PragmaOption::new(
+                OPTION_INFERRED_TOLERANCE_DEFAULT,
+                format!("{ANY_COMMODITY}:{tolerance}"),
+            ));
+        }
+        for (commodity, tolerance) in &self.inferred_tolerance_defaults {
+            pragmas.push(PragmaOption::new(
+                OPTION_INFERRED_TOLERANCE_DEFAULT,
+                format!("{commodity}:{tolerance}"),
+            ));
+        }
+        if let Some(multiplier) = self.inferred_tolerance_multiplier {
+            pragmas.push(PragmaOption::new(
+                OPTION_INFERRED_TOLERANCE_MULTIPLIER,
+                multiplier.to_string(),
+            ));
+        }
+        if let Some(from_cost) = self.infer_tolerance_from_cost {
+            pragmas.push(PragmaOption::new(
+                OPTION_INFER_TOLERANCE_FROM_COST,
+                if from_cost { "TRUE" } else { "FALSE" },
+            ));
+        }
+        if let Some(booking_method) = self.booking_method {
+            pragmas.push(PragmaOption::new(
+                OPTION_BOOKING_METHOD,
+                booking_method.to_string(),
+            ));
+        }
+        pragmas.extend(self.unknown_options.iter().cloned());
+        pragmas
+    }
+}
+
+fn split_tolerance_default(value: &str) -> Option<(&str, Decimal)> {
+    let (commodity, tolerance) = value.split_once(':')?;
+    Some((commodity, Decimal::from_str(tolerance).ok()?))
+}
+
+fn parse_commodity<'a>(
+    option: &str,
+    value: Cow<'a, str>,
+) -> Result<Commodity<'a>, InvalidOptionError> {
+    Commodity::new(value).map_err(|error| InvalidOptionError::InvalidCommodity {
+        option: option.to_string(),
+        error,
+    })
+}
+
+fn parse_decimal(option: &str, value: &str) -> Result<Decimal, InvalidOptionError> {
+    Decimal::from_str(value).map_err(|_| InvalidOptionError::InvalidNumber {
+        option: option.to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn parse_bool(option: &str, value: &str) -> Result<bool, InvalidOptionError> {
+    match value {
+        "TRUE" => Ok(true),
+        "FALSE" => Ok(false),
+        _ => Err(InvalidOptionError::InvalidBool {
+            option: option.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+
+    fn options(
+        pragmas: &[(&'static str, &'static str)],
+    ) -> Result<Options<'static>, InvalidOptionError> {
+        Options::from_pragmas(
+            pragmas
+                .iter()
+                .map(|(name, value)| PragmaOption::new(*name, *value)),
+        )
+    }
+
+    #[test]
+    fn defaults() {
+        let options = Options::new();
+
+        assert_eq!(options.title(), None);
+        assert_eq!(options.account_type_name(AccountType::Assets), "Assets");
+        assert!(options.operating_currencies().is_empty());
+        assert_eq!(options.inferred_tolerance_default(&commodity!(USD)), None);
+        assert_eq!(options.inferred_tolerance_multiplier(), dec!(0.5));
+        assert!(!options.infer_tolerance_from_cost());
+        assert_eq!(options.booking_method(), BookingMethod::Strict);
+        assert!(options.unknown_options().is_empty());
+        assert!(options.to_pragmas().is_empty());
+    }
+
+    #[test]
+    fn known_options() {
+        let options = options(&[
+            ("title", "Our Ledger"),
+            ("name_assets", "Aktiva"),
+            ("operating_currency", "EUR"),
+            ("operating_currency", "USD"),
+            ("inferred_tolerance_default", "*:0.001"),
+            ("inferred_tolerance_default", "USD:0.005"),
+            ("inferred_tolerance_multiplier", "1.1"),
+            ("infer_tolerance_from_cost", "TRUE"),
+            ("booking_method", "FIFO"),
+        ])
+        .unwrap();
+
+        assert_eq!(options.title(), Some("Our Ledger"));
+        assert_eq!(options.account_type_name(AccountType::Assets), "Aktiva");
+        assert_eq!(
+            options.account_type_name(AccountType::Liabilities),
+            "Liabilities"
+        );
+        assert_eq!(
+            options.operating_currencies(),
+            [commodity!(EUR), commodity!(USD)]
+        );
+        assert_eq!(
+            options.inferred_tolerance_default(&commodity!(USD)),
+            Some(dec!(0.005))
+        );
+        assert_eq!(
+            options.inferred_tolerance_default(&commodity!(EUR)),
+            Some(dec!(0.001))
+        );
+        assert_eq!(options.inferred_tolerance_multiplier(), dec!(1.1));
+        assert!(options.infer_tolerance_from_cost());
+        assert_eq!(options.booking_method(), BookingMethod::Fifo);
+        assert!(options.unknown_options().is_empty());
+    }
+
+    #[test]
+    fn unknown_options_are_preserved() {
+        let options = options(&[
+            ("render_commas", "TRUE"),
+            ("title", "Ledger"),
+            ("documents", "docs/"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options.unknown_options(),
+            [
+                PragmaOption::new("render_commas", "TRUE"),
+                PragmaOption::new("documents", "docs/"),
+            ]
+        );
+    }
+
+    #[test]
+    fn later_option_overwrites() {
+        let options = options(&[("title", "First"), ("title", "Second")]).unwrap();
+
+        assert_eq!(options.title(), Some("Second"));
+    }
+
+    #[test]
+    fn to_pragmas_round_trip() {
+        let original = options(&[
+            ("title", "Our Ledger"),
+            ("name_equity", "Eigenkapital"),
+            ("operating_currency", "EUR"),
+            ("inferred_tolerance_default", "USD:0.005"),
+            ("inferred_tolerance_default", "*:0.001"),
+            ("inferred_tolerance_multiplier", "1.1"),
+            ("infer_tolerance_from_cost", "FALSE"),
+            ("booking_method", "STRICT"),
+            ("render_commas", "TRUE"),
+        ])
+        .unwrap();
+
+        let reparsed = Options::from_pragmas(original.to_pragmas()).unwrap();
+
+        assert_eq!(original, reparsed);
+    }
+
+    #[test]
+    fn valid_options_apply_despite_invalid_ones() {
+        let (options, errors) = Options::from_valid_pragmas([
+            PragmaOption::new("operating_currency", "EUR"),
+            PragmaOption::new("inferred_tolerance_multiplier", "a lot"),
+            PragmaOption::new("infer_tolerance_from_cost", "TRUE"),
+            PragmaOption::new("booking_method", "fifo"),
+        ]);
+
+        assert_eq!(options.operating_currencies(), [commodity!(EUR)]);
+        assert_eq!(options.inferred_tolerance_multiplier(), dec!(0.5));
+        assert!(options.infer_tolerance_from_cost());
+        assert_eq!(options.booking_method(), BookingMethod::Strict);
+        let invalid: Vec<&str> = errors.iter().map(|(option, _)| option.name()).collect();
+        assert_eq!(invalid, ["inferred_tolerance_multiplier", "booking_method"]);
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!(matches!(
+            options(&[("operating_currency", "eur")]),
+            Err(InvalidOptionError::InvalidCommodity { .. })
+        ));
+        assert!(matches!(
+            options(&[("inferred_tolerance_multiplier", "a lot")]),
+            Err(InvalidOptionError::InvalidNumber { .. })
+        ));
+        assert!(matches!(
+            options(&[("infer_tolerance_from_cost", "yes")]),
+            Err(InvalidOptionError::InvalidBool { .. })
+        ));
+        assert!(matches!(
+            options(&[("inferred_tolerance_default", "0.005")]),
+            Err(InvalidOptionError::InvalidToleranceDefault(_))
+        ));
+        assert!(matches!(
+            options(&[("booking_method", "fifo")]),
+            Err(InvalidOptionError::InvalidBookingMethod(_))
+        ));
+    }
+}
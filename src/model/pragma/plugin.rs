@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PragmaPlugin<'a> {
+    module_name: Cow<'a, str>,
+    config: Option<Cow<'a, str>>,
+}
+
+impl<'a> PragmaPlugin<'a> {
+    pub fn new(module_name: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            module_name: module_name.into(),
+            config: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: impl Into<Cow<'a, str>>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    pub fn config(&self) -> Option<&str> {
+        self.config.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_pragma_plugin() {
+        let plugin = PragmaPlugin::new("beancount.plugins.auto_accounts");
+
+        assert_eq!(plugin.module_name(), "beancount.plugins.auto_accounts");
+        assert_eq!(plugin.config(), None);
+    }
+
+    #[test]
+    fn test_with_config() {
+        let plugin = PragmaPlugin::new("beancount.plugins.check_commodity").with_config("USD");
+
+        assert_eq!(plugin.config(), Some("USD"));
+    }
+}
@@ -0,0 +1,64 @@
+use super::{PragmaInclude, PragmaOption, PragmaPlugin, PragmaPushMeta};
+use crate::model::{MetadataKey, Tag};
+
+/// Undated line of a ledger file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pragma<'a> {
+    Option(PragmaOption<'a>),
+    Plugin(PragmaPlugin<'a>),
+    Include(PragmaInclude<'a>),
+    /// Add a tag to all transactions until the matching [Pragma::PopTag]
+    PushTag(Tag<'a>),
+    PopTag(Tag<'a>),
+    PushMeta(PragmaPushMeta<'a>),
+    PopMeta(MetadataKey<'a>),
+}
+
+impl<'a> Pragma<'a> {
+    pub fn as_option(&self) -> Option<&PragmaOption<'a>> {
+        match self {
+            Pragma::Option(option) => Some(option),
+            _ => None,
+        }
+    }
+
+    pub fn as_plugin(&self) -> Option<&PragmaPlugin<'a>> {
+        match self {
+            Pragma::Plugin(plugin) => Some(plugin),
+            _ => None,
+        }
+    }
+
+    pub fn as_include(&self) -> Option<&PragmaInclude<'a>> {
+        match self {
+            Pragma::Include(include) => Some(include),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_accessors() {
+        let option = Pragma::Option(PragmaOption::new("title", "Ledger"));
+        let plugin = Pragma::Plugin(PragmaPlugin::new("beancount.plugins.auto_accounts"));
+        let include = Pragma::Include(PragmaInclude::new(Path::new("2023.beancount")));
+
+        assert_eq!(option.as_option().unwrap().name(), "title");
+        assert!(option.as_plugin().is_none());
+        assert_eq!(
+            plugin.as_plugin().unwrap().module_name(),
+            "beancount.plugins.auto_accounts"
+        );
+        assert!(plugin.as_include().is_none());
+        assert_eq!(
+            include.as_include().unwrap().path(),
+            Path::new("2023.beancount")
+        );
+        assert!(include.as_option().is_none());
+    }
+}
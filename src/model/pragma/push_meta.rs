@@ -0,0 +1,42 @@
+use crate::model::{MetadataKey, MetadataValue};
+
+/// A `pushmeta key: value` line. The entry is added to all directives until the matching `popmeta`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PragmaPushMeta<'a> {
+    key: MetadataKey<'a>,
+    value: MetadataValue<'a>,
+}
+
+impl<'a> PragmaPushMeta<'a> {
+    pub fn new(key: MetadataKey<'a>, value: MetadataValue<'a>) -> Self {
+        Self { key, value }
+    }
+
+    pub fn key(&self) -> &MetadataKey<'a> {
+        &self.key
+    }
+
+    pub fn value(&self) -> &MetadataValue<'a> {
+        &self.value
+    }
+
+    pub fn into_entry(self) -> (MetadataKey<'a>, MetadataValue<'a>) {
+        (self.key, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_pragma_push_meta() {
+        let key = MetadataKey::new("trip").unwrap();
+        let value = MetadataValue::String("Japan".into());
+        let push_meta = PragmaPushMeta::new(key.clone(), value.clone());
+
+        assert_eq!(push_meta.key(), &key);
+        assert_eq!(push_meta.value(), &value);
+        assert_eq!(push_meta.into_entry(), (key, value));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+/// Identifier of a source file a value was parsed from.
+/// The default id refers to the main file of a ledger.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(usize);
+
+impl FileId {
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for FileId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "file #{}", self.0)
+    }
+}
+
+/// Location of a parsed value, given as a byte range within a source file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceSpan {
+    file_id: FileId,
+    start: usize,
+    end: usize,
+}
+
+impl SourceSpan {
+    pub fn new(file_id: FileId, byte_range: Range<usize>) -> Self {
+        Self {
+            file_id,
+            start: byte_range.start,
+            end: byte_range.end,
+        }
+    }
+
+    pub fn set_file_id(&mut self, file_id: FileId) {
+        self.file_id = file_id;
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn byte_range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// 1-based line and column (in characters) of the start of the span within `source`,
+    /// which must be the content of the file this span refers to.
+    pub fn line_column(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.start.min(source.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+}
+
+impl ariadne::Span for SourceSpan {
+    type SourceId = FileId;
+
+    fn source(&self) -> &FileId {
+        &self.file_id
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Optional source span of a model value. Like in lima, the span is invisible with respect to
+/// equality, ordering and hashing, so parsed values compare equal to manually constructed ones.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoredSpan(Option<SourceSpan>);
+
+impl IgnoredSpan {
+    pub fn new(span: SourceSpan) -> Self {
+        Self(Some(span))
+    }
+
+    pub fn get(&self) -> Option<&SourceSpan> {
+        self.0.as_ref()
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut SourceSpan> {
+        self.0.as_mut()
+    }
+}
+
+impl PartialEq for IgnoredSpan {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for IgnoredSpan {}
+
+impl PartialOrd for IgnoredSpan {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IgnoredSpan {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for IgnoredSpan {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, (1, 1))]
+    #[case(4, (1, 5))]
+    #[case(11, (2, 1))]
+    #[case(16, (2, 6))]
+    #[case(22, (3, 3))]
+    fn test_line_column(#[case] start: usize, #[case] expected: (usize, usize)) {
+        let source = "first line\nsecond\n\u{e4}\u{f6}x";
+        let span = SourceSpan::new(FileId::default(), start..start);
+
+        assert_eq!(span.line_column(source), expected);
+    }
+
+    #[test]
+    fn test_span_ignored_in_comparisons() {
+        let span = IgnoredSpan::new(SourceSpan::new(FileId::new(1), 3..7));
+
+        assert_eq!(span, IgnoredSpan::default());
+        assert_eq!(span.get().unwrap().byte_range(), 3..7);
+        assert_eq!(span.get().unwrap().file_id(), FileId::new(1));
+    }
+}
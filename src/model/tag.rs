@@ -0,0 +1,102 @@
+use derive_more::Display;
+use std::borrow::Cow;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InvalidTagError {
+    #[error("Tag cannot be empty")]
+    Empty,
+    #[error("Tag can only contain letters, numbers, dashes, underscores, slashes or periods")]
+    InvalidCharacter,
+}
+
+/// A tag like `#trip-japan`, stored without the leading `#`
+#[derive(Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tag<'a> {
+    tag: Cow<'a, str>,
+}
+
+impl<'a> Tag<'a> {
+    pub fn new(tag: impl Into<Cow<'a, str>>) -> Result<Self, InvalidTagError> {
+        let this = Self { tag: tag.into() };
+        this.validate()?;
+        Ok(this)
+    }
+
+    fn validate(&self) -> Result<(), InvalidTagError> {
+        if self.tag.is_empty() {
+            return Err(InvalidTagError::Empty);
+        }
+        if !self.tag.chars().all(is_valid_tag_char) {
+            return Err(InvalidTagError::InvalidCharacter);
+        }
+        Ok(())
+    }
+}
+
+/// Tags and links share the same character set
+pub(super) fn is_valid_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '/' | '.')
+}
+
+impl TryFrom<String> for Tag<'static> {
+    type Error = InvalidTagError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Tag::new(value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Tag<'a> {
+    type Error = InvalidTagError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Tag::new(value)
+    }
+}
+
+impl AsRef<str> for Tag<'_> {
+    fn as_ref(&self) -> &str {
+        &self.tag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_tags() {
+        assert!(Tag::new("trip-japan").is_ok());
+        assert!(Tag::new("trip_2024").is_ok());
+        assert!(Tag::new("invoices/2024.03").is_ok());
+        assert!(Tag::new("Urlaub").is_ok());
+        assert!(Tag::new("a").is_ok());
+    }
+
+    #[test]
+    fn test_empty_tag() {
+        assert_eq!(Tag::new("").unwrap_err(), InvalidTagError::Empty);
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        assert_eq!(
+            Tag::new("#trip").unwrap_err(),
+            InvalidTagError::InvalidCharacter
+        );
+        assert_eq!(
+            Tag::new("trip japan").unwrap_err(),
+            InvalidTagError::InvalidCharacter
+        );
+        assert_eq!(
+            Tag::new("trip:japan").unwrap_err(),
+            InvalidTagError::InvalidCharacter
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Tag::new("trip-japan").unwrap().to_string(), "trip-japan");
+    }
+}
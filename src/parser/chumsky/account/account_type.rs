@@ -7,13 +7,7 @@ use crate::{
 };
 
 const fn account_type_str(account_type: AccountType) -> &'static str {
-    match account_type {
-        AccountType::Assets => "Assets",
-        AccountType::Liabilities => "Liabilities",
-        AccountType::Income => "Income",
-        AccountType::Expenses => "Expenses",
-        AccountType::Equity => "Equity",
-    }
+    account_type.default_name()
 }
 
 pub fn parse_account_type<'a>() -> impl Parser<'a, &'a str, AccountType, extra::Err<Rich<'a, char>>>
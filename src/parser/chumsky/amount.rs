@@ -1,23 +1,38 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
     model::Amount,
     parser::chumsky::{
         commodity::{marshal_commodity, parse_commodity},
-        decimal::{marshal_decimal, parse_decimal},
+        decimal::{marshal_decimal, parse_decimal, parse_decimal_literal},
     },
 };
 
+/// Parser for amount
+/// Syntax: <number> <commodity>
+/// If the number is an arithmetic expression, its source text is retained in the amount.
 pub fn parse_amount<'a>() -> impl Parser<'a, &'a str, Amount<'a>, extra::Err<Rich<'a, char>>> {
-    parse_decimal()
-        .then_ignore(whitespace().at_least(1))
+    let literal = parse_decimal_literal()
+        .then_ignore(inline_whitespace().at_least(1))
         .then(parse_commodity())
-        .map(|(number, commodity)| Amount::new(number, commodity))
+        .map(|(number, commodity)| Amount::new(number, commodity));
+    let expression = parse_decimal()
+        .map_with(|number, e| (number, e.slice()))
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_commodity())
+        .map(|((number, expression), commodity)| {
+            Amount::new(number, commodity).with_expression(expression)
+        });
+
+    literal.or(expression)
 }
 
 pub fn marshal_amount(amount: &Amount, writer: &mut impl Write) -> std::fmt::Result {
-    marshal_decimal(amount.number(), writer)?;
+    match amount.expression() {
+        Some(expression) => write!(writer, "{expression}")?,
+        None => marshal_decimal(amount.number(), writer)?,
+    }
     write!(writer, " ")?;
     marshal_commodity(amount.commodity(), writer)
 }
@@ -133,6 +148,42 @@ mod tests {
         assert_eq!(amount.commodity().as_ref(), "BTC");
     }
 
+    #[rstest]
+    #[case("(40.00 / 3) USD", dec!(40.00) / dec!(3), "USD", "(40.00 / 3)")]
+    #[case("12 * 3.50 EUR", dec!(42.00), "EUR", "12 * 3.50")]
+    #[case("-(1 + 2) EUR", dec!(-3), "EUR", "-(1 + 2)")]
+    fn parse_and_marshal_amount_expression(
+        #[case] input: &str,
+        #[case] expected_number: rust_decimal::Decimal,
+        #[case] expected_commodity: &str,
+        #[case] expected_expression: &str,
+    ) {
+        let parsed = parse_amount().parse(input).into_result().unwrap();
+
+        assert_eq!(*parsed.number(), expected_number);
+        assert_eq!(parsed.commodity().as_ref(), expected_commodity);
+        assert_eq!(parsed.expression(), Some(expected_expression));
+
+        let mut marshalled = String::new();
+        marshal_amount(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[test]
+    fn parse_amount_literal_has_no_expression() {
+        let parsed = parse_amount().parse("-5.00 USD").into_result().unwrap();
+
+        assert_eq!(parsed.expression(), None);
+    }
+
+    #[test]
+    fn parse_amount_division_by_zero() {
+        let errors = parse_amount().parse("1 / 0 USD").into_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Division by zero");
+    }
+
     #[rstest]
     #[case("USD")] // Missing number
     #[case("100.50")] // Missing commodity
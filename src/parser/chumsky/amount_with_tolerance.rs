@@ -1,4 +1,4 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
@@ -14,12 +14,16 @@ use crate::{
 pub fn parse_amount_with_tolerance<'a>()
 -> impl Parser<'a, &'a str, AmountWithTolerance<'a>, extra::Err<Rich<'a, char>>> {
     let tolerance = just('~')
-        .ignore_then(whitespace().at_least(1))
+        .ignore_then(inline_whitespace().at_least(1))
         .ignore_then(parse_positive_decimal());
 
     parse_decimal()
-        .then_ignore(whitespace().at_least(1))
-        .then(tolerance.then_ignore(whitespace().at_least(1)).or_not())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(
+            tolerance
+                .then_ignore(inline_whitespace().at_least(1))
+                .or_not(),
+        )
         .then(parse_commodity())
         .map(|((number, tolerance), commodity)| {
             AmountWithTolerance::new(Amount::new(number, commodity), tolerance)
@@ -75,7 +79,7 @@ mod tests {
         let parsed = result.into_result().unwrap();
         assert_eq!(*parsed.number(), expected_number);
         assert_eq!(parsed.commodity().as_ref(), expected_commodity);
-        assert_eq!(parsed.tolerance().map(|t| *t), expected_tolerance);
+        assert_eq!(parsed.tolerance().copied(), expected_tolerance);
     }
 
     #[apply(valid_amount_with_tolerance_template)]
@@ -0,0 +1,63 @@
+use chumsky::{prelude::*, text::inline_whitespace};
+
+/// Parser for a comment up to (not including) the end of the line
+/// Syntax: ";" <any text>
+pub fn parse_comment<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> + Clone {
+    just(';').then(none_of('\n').repeated()).ignored()
+}
+
+/// Parser for the end of a line inside a multi-line directive.
+/// Allows a trailing comment, and skips following lines that only hold an indented comment.
+/// Syntax: [<comment>] "\n" { <indentation> <comment> "\n" }
+pub fn parse_line_end<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> + Clone {
+    let end_of_line = inline_whitespace()
+        .then(parse_comment().or_not())
+        .then(just('\n'));
+    let comment_line = inline_whitespace()
+        .at_least(1)
+        .then(parse_comment())
+        .then(just('\n'));
+
+    end_of_line.then(comment_line.repeated()).ignored()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(";")]
+    #[case("; a comment")]
+    #[case(";;; with ; semicolons")]
+    fn parse_comment_valid(#[case] input: &str) {
+        assert!(parse_comment().parse(input).has_output());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("a comment")]
+    #[case("; first\n; second")]
+    fn parse_comment_invalid(#[case] input: &str) {
+        assert!(!parse_comment().parse(input).has_output());
+    }
+
+    #[rstest]
+    #[case("\n")]
+    #[case("   \n")]
+    #[case(" ; trailing comment\n")]
+    #[case("\n  ; indented comment\n")]
+    #[case("; trailing\n  ; first\n\t; second\n")]
+    fn parse_line_end_valid(#[case] input: &str) {
+        assert!(parse_line_end().parse(input).has_output());
+    }
+
+    #[rstest]
+    #[case("")] // No line break
+    #[case("x\n")] // Content before the line break
+    #[case("\n; unindented comment\n")] // Unindented comments end the directive
+    #[case("\n  \n")] // Blank lines end the directive
+    fn parse_line_end_invalid(#[case] input: &str) {
+        assert!(!parse_line_end().parse(input).has_output());
+    }
+}
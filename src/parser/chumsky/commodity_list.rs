@@ -1,4 +1,4 @@
-use chumsky::prelude::*;
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::{collections::HashSet, fmt::Write};
 
 use crate::{
@@ -9,7 +9,7 @@ use crate::{
 pub fn parse_commodity_list<'a>()
 -> impl Parser<'a, &'a str, HashSet<Commodity<'a>>, extra::Err<Rich<'a, char>>> {
     parse_commodity()
-        .separated_by(just(',').padded())
+        .separated_by(just(',').padded_by(inline_whitespace()))
         .at_least(1)
         .collect::<Vec<_>>()
         .map(|commodities| commodities.into_iter().collect())
@@ -23,7 +23,7 @@ where
     'b: 'a,
 {
     let mut sorted_commodities: Vec<_> = commodities.collect();
-    sorted_commodities.sort_by(|a, b| a.cmp(b));
+    sorted_commodities.sort();
     let mut sorted_commodities = sorted_commodities.into_iter();
 
     if let Some(first) = sorted_commodities.next() {
@@ -164,7 +164,7 @@ mod tests {
     fn parse_commodity_list_invalid(#[case] input: &str, #[case] description: &str) {
         let result = parse_commodity_list().parse(input);
         assert!(
-            !result.has_output() || result.into_errors().len() > 0,
+            !result.has_output() || !result.into_errors().is_empty(),
             "{}: {}",
             description,
             input
@@ -1,23 +1,107 @@
-use chumsky::prelude::*;
+use chumsky::{prelude::*, text::inline_whitespace};
 use rust_decimal::Decimal;
 use std::fmt::Write;
 
+/// Parser for a number, which may be an arithmetic expression.
+/// The expression is evaluated at parse time.
+/// Syntax: <term> {("+" | "-") <term>}
+///   where <term> := <factor> {("*" | "/") <factor>}
+///   and <factor> := {("+" | "-")} (<positive_decimal> | "(" <expression> ")")
 pub fn parse_decimal<'a>() -> impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>> {
+    recursive(|expression| {
+        // Like beancount's lexer, don't read something shaped like a date as a subtraction
+        let number = parse_positive_decimal().and_is(parse_date_like().not());
+        let atom = number.or(expression
+            .padded_by(inline_whitespace())
+            .delimited_by(just('('), just(')')));
+
+        let factor = one_of("+-")
+            .then_ignore(inline_whitespace())
+            .repeated()
+            .collect::<Vec<char>>()
+            .then(atom)
+            .map(|(signs, number): (Vec<char>, Decimal)| {
+                let negations = signs.iter().filter(|sign| **sign == '-').count();
+                if negations % 2 == 1 { -number } else { number }
+            });
+
+        let term = parse_binary_operation(factor, "*/");
+        parse_binary_operation(term, "+-")
+    })
+}
+
+/// Parser for text shaped like a date, e.g. `2024-13-01`, whether or not it is a valid date
+fn parse_date_like<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> + Clone {
+    let digit = one_of('0'..='9');
+    let separator = one_of("-/");
+
+    digit
+        .clone()
+        .repeated()
+        .exactly(4)
+        .then(separator)
+        .then(digit.clone().repeated().exactly(2))
+        .then(separator)
+        .then(digit.repeated().exactly(2))
+        .ignored()
+}
+
+/// Parser for a left-associative chain of binary operations of the same precedence
+fn parse_binary_operation<'a>(
+    operand: impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>> + Clone,
+    operators: &'static str,
+) -> impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>> + Clone {
+    let operator = one_of(operators).padded_by(inline_whitespace());
+
+    operand
+        .clone()
+        .then(operator.then(operand).repeated().collect::<Vec<_>>())
+        .try_map(|(first, rest), span| {
+            rest.into_iter()
+                .try_fold(first, |lhs, (operator, rhs)| {
+                    evaluate_binary_operation(lhs, operator, rhs)
+                })
+                .map_err(|message| Rich::custom(span, message))
+        })
+}
+
+fn evaluate_binary_operation(
+    lhs: Decimal,
+    operator: char,
+    rhs: Decimal,
+) -> Result<Decimal, &'static str> {
+    let result = match operator {
+        '+' => lhs.checked_add(rhs),
+        '-' => lhs.checked_sub(rhs),
+        '*' => lhs.checked_mul(rhs),
+        '/' => {
+            if rhs.is_zero() {
+                return Err("Division by zero");
+            }
+            lhs.checked_div(rhs)
+        }
+        _ => unreachable!("Unknown operator {operator}"),
+    };
+    result.ok_or("Arithmetic overflow")
+}
+
+/// Parser for a plain number without arithmetic
+/// Syntax: ["+" | "-"] <positive_decimal>
+pub fn parse_decimal_literal<'a>() -> impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>>
+{
     let sign = one_of("+-").or_not();
 
     sign.then(parse_positive_decimal())
         .map(|(sign, mut number)| {
-            if let Some(s) = sign {
-                if s == '-' {
-                    number.set_sign_negative(true);
-                }
+            if let Some('-') = sign {
+                number.set_sign_negative(true);
             }
             number
         })
 }
 
-pub fn parse_positive_decimal<'a>() -> impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>>
-{
+pub fn parse_positive_decimal<'a>()
+-> impl Parser<'a, &'a str, Decimal, extra::Err<Rich<'a, char>>> + Clone {
     let digits = one_of('0'..='9').repeated().at_least(1);
     let decimal_part = just('.').then(digits.clone()).or_not();
 
@@ -104,6 +188,44 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("(40.00 / 3)", dec!(40.00) / dec!(3))]
+    #[case("12 * 3.50", dec!(42.00))]
+    #[case("12*3.50", dec!(42.00))]
+    #[case("1 + 2 * 3", dec!(7))]
+    #[case("(1 + 2) * 3", dec!(9))]
+    #[case("10 - 4 - 3", dec!(3))]
+    #[case("100 / 10 / 2", dec!(5))]
+    #[case("-(2 + 3)", dec!(-5))]
+    #[case("-2 * -3", dec!(6))]
+    #[case("--5", dec!(5))]
+    #[case("( ( 1.5 ) )", dec!(1.5))]
+    #[case("10-5", dec!(5))]
+    fn parse_decimal_expression(#[case] input: &str, #[case] expected: Decimal) {
+        let parsed = parse_decimal().parse(input).into_result().unwrap();
+        assert_eq!(parsed, expected);
+    }
+
+    #[rstest]
+    #[case("1 / 0", "Division by zero")]
+    #[case("1 / (2 - 2)", "Division by zero")]
+    #[case("79228162514264337593543950335 + 1", "Arithmetic overflow")]
+    #[case("79228162514264337593543950335 * 2", "Arithmetic overflow")]
+    fn parse_decimal_expression_error(#[case] input: &str, #[case] expected_message: &str) {
+        let errors = parse_decimal().parse(input).into_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), expected_message);
+    }
+
+    #[rstest]
+    #[case("1 + 2")]
+    #[case("(1)")]
+    #[case("2 * 3")]
+    fn parse_decimal_literal_rejects_expression(#[case] input: &str) {
+        let result = parse_decimal_literal().parse(input);
+        assert!(!result.has_output());
+    }
+
     #[rstest]
     #[case("")] // Empty input
     #[case("abc")] // Invalid characters
@@ -116,6 +238,10 @@ mod tests {
     #[case("12..34")] // Double dots
     #[case("1.2.3.4")] // Multiple dots
     #[case("12abc34")] // Mixed valid/invalid
+    #[case("(1 + 2")] // Unclosed parenthesis
+    #[case("1 +")] // Missing operand
+    #[case("1 % 2")] // Unknown operator
+    #[case("2024-13-01")] // Date-like
     fn parse_decimal_invalid(#[case] input: &str) {
         let result = parse_decimal().parse(input);
         assert!(
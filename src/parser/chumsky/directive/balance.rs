@@ -1,6 +1,6 @@
 use chumsky::{
     prelude::*,
-    text::{keyword, whitespace},
+    text::{inline_whitespace, keyword},
 };
 use std::fmt::Write;
 
@@ -19,9 +19,9 @@ const KEYWORD_BALANCE: &str = "balance";
 pub fn parse_balance_directive<'a>()
 -> impl Parser<'a, &'a str, DirectiveBalance<'a>, extra::Err<Rich<'a, char>>> {
     keyword(KEYWORD_BALANCE)
-        .then_ignore(whitespace().at_least(1))
+        .then_ignore(inline_whitespace().at_least(1))
         .ignore_then(parse_account())
-        .then_ignore(whitespace().at_least(1))
+        .then_ignore(inline_whitespace().at_least(1))
         .then(parse_amount_with_tolerance())
         .map(|(account, amount_with_tolerance)| {
             DirectiveBalance::new(account, amount_with_tolerance)
@@ -98,7 +98,7 @@ mod tests {
 
         // Validate tolerance
         assert_eq!(
-            parsed.amount_with_tolerance().tolerance().map(|t| *t),
+            parsed.amount_with_tolerance().tolerance().copied(),
             expected_tolerance
         );
     }
@@ -0,0 +1,119 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveClose,
+    parser::chumsky::account::{marshal_account, parse_account},
+};
+
+const KEYWORD_CLOSE: &str = "close";
+
+/// Parser for close directive (without date)
+/// Syntax: "close" <account>
+pub fn parse_close_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveClose<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_CLOSE)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_account())
+        .map(DirectiveClose::new)
+}
+
+/// Marshaller for close directive (without date)
+pub fn marshal_close_directive(
+    directive: &DirectiveClose,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_CLOSE} ")?;
+    marshal_account(directive.account().clone(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AccountType, account};
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("close Assets:OldBank", AccountType::Assets, vec!["OldBank"])]
+    #[case("close Liabilities:CreditCard:CapitalOne", AccountType::Liabilities, vec!["CreditCard", "CapitalOne"])]
+    #[case("close Expenses:Food", AccountType::Expenses, vec!["Food"])]
+    #[case("close Income:Salary", AccountType::Income, vec!["Salary"])]
+    #[case("close Equity:Opening-Balances", AccountType::Equity, vec!["Opening-Balances"])]
+    #[case("close   Assets:Checking", AccountType::Assets, vec!["Checking"])]
+    fn valid_close_directive_template(
+        #[case] input: &str,
+        #[case] expected_account_type: AccountType,
+        #[case] expected_account_components: Vec<&str>,
+    ) {
+    }
+
+    #[apply(valid_close_directive_template)]
+    fn parse_close_directive_valid(
+        #[case] input: &str,
+        #[case] expected_account_type: AccountType,
+        #[case] expected_account_components: Vec<&str>,
+    ) {
+        let result = parse_close_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse close directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.account().account_type(), expected_account_type);
+        let components: Vec<&str> = parsed.account().components().map(AsRef::as_ref).collect();
+        assert_eq!(components, expected_account_components);
+    }
+
+    #[apply(valid_close_directive_template)]
+    fn marshal_and_parse_close_directive(
+        #[case] input: &str,
+        #[case] _expected_account_type: AccountType,
+        #[case] _expected_account_components: Vec<&str>,
+    ) {
+        // First parse the original
+        let result = parse_close_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_close_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_close_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("close")] // Missing account
+    #[case("close ")] // Missing account
+    #[case("closeAssets:Cash")] // Missing space
+    #[case("close assets:cash")] // Invalid account
+    #[case("close Assets:Cash USD")] // Close takes no commodities
+    fn parse_close_directive_invalid(#[case] input: &str) {
+        let result = parse_close_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_close_directive_basic() {
+        let directive = DirectiveClose::new(account!(Assets:OldBank));
+
+        let mut output = String::new();
+        let result = marshal_close_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "close Assets:OldBank");
+    }
+}
@@ -0,0 +1,105 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveCommodity,
+    parser::chumsky::commodity::{marshal_commodity, parse_commodity},
+};
+
+const KEYWORD_COMMODITY: &str = "commodity";
+
+/// Parser for commodity directive (without date)
+/// Syntax: "commodity" <commodity>
+pub fn parse_commodity_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveCommodity<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_COMMODITY)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_commodity())
+        .map(DirectiveCommodity::new)
+}
+
+/// Marshaller for commodity directive (without date)
+pub fn marshal_commodity_directive(
+    directive: &DirectiveCommodity,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_COMMODITY} ")?;
+    marshal_commodity(directive.commodity(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::commodity;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("commodity USD", "USD")]
+    #[case("commodity   EUR", "EUR")]
+    #[case("commodity VTI", "VTI")]
+    #[case("commodity HOOL", "HOOL")]
+    fn valid_commodity_directive_template(#[case] input: &str, #[case] expected_commodity: &str) {}
+
+    #[apply(valid_commodity_directive_template)]
+    fn parse_commodity_directive_valid(#[case] input: &str, #[case] expected_commodity: &str) {
+        let result = parse_commodity_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse commodity directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.commodity().as_ref(), expected_commodity);
+    }
+
+    #[apply(valid_commodity_directive_template)]
+    fn marshal_and_parse_commodity_directive(
+        #[case] input: &str,
+        #[case] _expected_commodity: &str,
+    ) {
+        // First parse the original
+        let result = parse_commodity_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_commodity_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_commodity_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("commodity")] // Missing commodity
+    #[case("commodity ")] // Missing commodity
+    #[case("commodityUSD")] // Missing space
+    #[case("commodity usd")] // Invalid commodity
+    #[case("commodity USD EUR")] // Only one commodity
+    fn parse_commodity_directive_invalid(#[case] input: &str) {
+        let result = parse_commodity_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_commodity_directive_basic() {
+        let directive = DirectiveCommodity::new(commodity!(USD));
+
+        let mut output = String::new();
+        let result = marshal_commodity_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "commodity USD");
+    }
+}
@@ -0,0 +1,144 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveCustom,
+    parser::chumsky::{
+        directive::custom::custom_value::{marshal_custom_value, parse_custom_value},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+    },
+};
+
+const KEYWORD_CUSTOM: &str = "custom";
+
+/// Parser for custom directive (without date)
+/// Syntax: "custom" <quoted_string> { <value> }
+pub fn parse_custom_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveCustom<'a>, extra::Err<Rich<'a, char>>> {
+    let values = inline_whitespace()
+        .at_least(1)
+        .ignore_then(parse_custom_value())
+        .repeated()
+        .collect();
+
+    keyword(KEYWORD_CUSTOM)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .then(values)
+        .map(|(custom_type, values)| DirectiveCustom::new(custom_type).with_values(values))
+}
+
+/// Marshaller for custom directive (without date)
+pub fn marshal_custom_directive(
+    directive: &DirectiveCustom,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_CUSTOM} ")?;
+    marshal_quoted_string(directive.custom_type(), writer)?;
+    for value in directive.values() {
+        write!(writer, " ")?;
+        marshal_custom_value(value, writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, account, commodity, directive::CustomValue};
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use rust_decimal_macros::dec;
+
+    #[template]
+    #[rstest]
+    #[case("custom \"fava-option\"", "fava-option", vec![])]
+    #[case(
+        "custom \"budget\" Expenses:Food \"monthly\" 400.00 EUR",
+        "budget",
+        vec![
+            CustomValue::Account(account!(Expenses:Food)),
+            CustomValue::String("monthly".into()),
+            CustomValue::Amount(Amount::new(dec!(400.00), commodity!(EUR))),
+        ]
+    )]
+    #[case(
+        "custom   \"flags\"   TRUE  FALSE 3",
+        "flags",
+        vec![CustomValue::Bool(true), CustomValue::Bool(false), CustomValue::Number(dec!(3))]
+    )]
+    fn valid_custom_directive_template(
+        #[case] input: &str,
+        #[case] expected_type: &str,
+        #[case] expected_values: Vec<CustomValue>,
+    ) {
+    }
+
+    #[apply(valid_custom_directive_template)]
+    fn parse_custom_directive_valid(
+        #[case] input: &str,
+        #[case] expected_type: &str,
+        #[case] expected_values: Vec<CustomValue>,
+    ) {
+        let result = parse_custom_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse custom directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.custom_type(), expected_type);
+        assert_eq!(parsed.values(), expected_values);
+    }
+
+    #[apply(valid_custom_directive_template)]
+    fn marshal_and_parse_custom_directive(
+        #[case] input: &str,
+        #[case] _expected_type: &str,
+        #[case] _expected_values: Vec<CustomValue>,
+    ) {
+        // First parse the original
+        let result = parse_custom_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_custom_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_custom_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("custom")] // Missing type
+    #[case("custom budget")] // Type not quoted
+    #[case("custom \"budget\" monthly")] // Invalid value
+    #[case("custom \"budget\"\n\"monthly\"")] // Values must be on the same line
+    fn parse_custom_directive_invalid(#[case] input: &str) {
+        let result = parse_custom_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_custom_directive_basic() {
+        let directive = DirectiveCustom::new("budget")
+            .with_value(CustomValue::Account(account!(Expenses:Food)))
+            .with_value(CustomValue::Amount(Amount::new(dec!(400), commodity!(EUR))));
+
+        let mut output = String::new();
+        let result = marshal_custom_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "custom \"budget\" Expenses:Food 400 EUR");
+    }
+}
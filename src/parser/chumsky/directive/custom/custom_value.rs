@@ -0,0 +1,97 @@
+use chumsky::{prelude::*, text::keyword};
+use std::fmt::Write;
+
+use crate::{
+    model::directive::CustomValue,
+    parser::chumsky::{
+        account::{marshal_account, parse_account},
+        amount::{marshal_amount, parse_amount},
+        date::{marshal_date, parse_date},
+        decimal::{marshal_decimal, parse_decimal},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+    },
+};
+
+const KEYWORD_TRUE: &str = "TRUE";
+const KEYWORD_FALSE: &str = "FALSE";
+
+/// Parser for a single value of a custom directive
+/// Syntax: <quoted_string> | <date> | TRUE | FALSE | <amount> | <number> | <account>
+pub fn parse_custom_value<'a>()
+-> impl Parser<'a, &'a str, CustomValue<'a>, extra::Err<Rich<'a, char>>> {
+    choice((
+        parse_quoted_string().map(CustomValue::String),
+        parse_date().map(CustomValue::Date),
+        keyword(KEYWORD_TRUE).to(CustomValue::Bool(true)),
+        keyword(KEYWORD_FALSE).to(CustomValue::Bool(false)),
+        parse_amount().map(CustomValue::Amount),
+        parse_decimal().map(CustomValue::Number),
+        parse_account().map(CustomValue::Account),
+    ))
+}
+
+pub fn marshal_custom_value(value: &CustomValue, writer: &mut impl Write) -> std::fmt::Result {
+    match value {
+        CustomValue::String(string) => marshal_quoted_string(string, writer),
+        CustomValue::Date(date) => marshal_date(date, writer),
+        CustomValue::Bool(true) => write!(writer, "{KEYWORD_TRUE}"),
+        CustomValue::Bool(false) => write!(writer, "{KEYWORD_FALSE}"),
+        CustomValue::Amount(amount) => marshal_amount(amount, writer),
+        CustomValue::Number(number) => marshal_decimal(number, writer),
+        CustomValue::Account(account) => marshal_account(account.clone(), writer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, account, commodity};
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use rust_decimal_macros::dec;
+
+    #[template]
+    #[rstest]
+    #[case("\"monthly\"", CustomValue::String("monthly".into()))]
+    #[case("2024-01-01", CustomValue::Date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()))]
+    #[case("TRUE", CustomValue::Bool(true))]
+    #[case("FALSE", CustomValue::Bool(false))]
+    #[case("400.00 EUR", CustomValue::Amount(Amount::new(dec!(400.00), commodity!(EUR))))]
+    #[case("-12", CustomValue::Number(dec!(-12)))]
+    #[case("0.5", CustomValue::Number(dec!(0.5)))]
+    #[case("Expenses:Food", CustomValue::Account(account!(Expenses:Food)))]
+    fn valid_custom_value_template(#[case] input: &str, #[case] expected: CustomValue) {}
+
+    #[apply(valid_custom_value_template)]
+    fn parse_custom_value_valid(#[case] input: &str, #[case] expected: CustomValue) {
+        let result = parse_custom_value().parse(input);
+        assert!(result.has_output(), "Failed to parse value: {}", input);
+        assert_eq!(result.into_result().unwrap(), expected);
+    }
+
+    #[apply(valid_custom_value_template)]
+    fn marshal_and_parse_custom_value(#[case] input: &str, #[case] _expected: CustomValue) {
+        let original = parse_custom_value().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_custom_value(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_custom_value()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("")] // Empty
+    #[case("monthly")] // Unquoted string
+    #[case("true")] // Bools are uppercase
+    #[case("USD")] // Bare commodity
+    #[case("2024-13-01")] // Invalid date
+    fn parse_custom_value_invalid(#[case] input: &str) {
+        let result = parse_custom_value().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
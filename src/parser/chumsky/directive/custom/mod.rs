@@ -0,0 +1,4 @@
+mod custom;
+mod custom_value;
+
+pub use custom::{marshal_custom_directive, parse_custom_directive};
@@ -1,35 +1,69 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
-    model::directive::{Directive, DirectiveVariant},
+    model::{
+        FileId, Metadata, SourceSpan,
+        directive::{Directive, DirectiveVariant},
+    },
     parser::chumsky::{
         date::parse_date,
         directive::{
             balance::{marshal_balance_directive, parse_balance_directive},
+            close::{marshal_close_directive, parse_close_directive},
+            commodity::{marshal_commodity_directive, parse_commodity_directive},
+            custom::{marshal_custom_directive, parse_custom_directive},
+            document::{marshal_document_directive, parse_document_directive},
+            event::{marshal_event_directive, parse_event_directive},
+            note::{marshal_note_directive, parse_note_directive},
             open::{marshal_open_directive, parse_open_directive},
-            transaction::{marshal_transaction_directive, parse_transaction_directive},
+            pad::{marshal_pad_directive, parse_pad_directive},
+            price::{marshal_price_directive, parse_price_directive},
+            query::{marshal_query_directive, parse_query_directive},
+            transaction::{
+                marshal_transaction_directive_with_metadata,
+                parse_transaction_directive_with_metadata,
+            },
         },
+        metadata::{marshal_metadata, parse_metadata},
     },
 };
 
 /// Parser for complete directive with date
-/// Syntax: <date> <directive_content>
+/// Syntax: <date> <directive_content> <metadata>
+/// For transactions, the metadata comes before the postings.
 pub fn parse_directive<'a>() -> impl Parser<'a, &'a str, Directive<'a>, extra::Err<Rich<'a, char>>>
 {
+    let transaction = parse_transaction_directive_with_metadata()
+        .map(|(transaction, metadata)| (DirectiveVariant::Transaction(transaction), metadata));
+    let other = parse_directive_variant().then(parse_metadata());
+
     parse_date()
-        .then_ignore(whitespace().at_least(1))
-        .then(parse_directive_variant())
-        .map(|(date, content)| Directive::new(date, content))
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(transaction.or(other))
+        .map_with(|(date, (content, metadata)), e| {
+            let span: SimpleSpan = e.span();
+            Directive::new(date, content)
+                .with_metadata(metadata)
+                .with_span(SourceSpan::new(FileId::default(), span.into_range()))
+        })
 }
 
+/// Parser for all directive contents except transactions, see [parse_directive]
 fn parse_directive_variant<'a>()
 -> impl Parser<'a, &'a str, DirectiveVariant<'a>, extra::Err<Rich<'a, char>>> {
     choice((
         parse_open_directive().map(DirectiveVariant::Open),
+        parse_close_directive().map(DirectiveVariant::Close),
+        parse_commodity_directive().map(DirectiveVariant::Commodity),
+        parse_pad_directive().map(DirectiveVariant::Pad),
         parse_balance_directive().map(DirectiveVariant::Balance),
-        parse_transaction_directive().map(DirectiveVariant::Transaction),
-        // TODO: Add more directive types here as they're implemented
+        parse_price_directive().map(DirectiveVariant::Price),
+        parse_note_directive().map(DirectiveVariant::Note),
+        parse_document_directive().map(DirectiveVariant::Document),
+        parse_event_directive().map(DirectiveVariant::Event),
+        parse_query_directive().map(DirectiveVariant::Query),
+        parse_custom_directive().map(DirectiveVariant::Custom),
     ))
 }
 
@@ -38,7 +72,15 @@ pub fn marshal_directive(directive: &Directive, writer: &mut impl Write) -> std:
     write!(writer, " ")?;
 
     // Marshal directive content
-    marshal_directive_content(directive.content(), writer)
+    match directive.content() {
+        DirectiveVariant::Transaction(transaction) => {
+            marshal_transaction_directive_with_metadata(transaction, directive.metadata(), writer)
+        }
+        content => {
+            marshal_directive_content(content, writer)?;
+            marshal_metadata(directive.metadata(), writer)
+        }
+    }
 }
 
 fn marshal_directive_content(
@@ -47,10 +89,19 @@ fn marshal_directive_content(
 ) -> std::fmt::Result {
     match content {
         DirectiveVariant::Open(open) => marshal_open_directive(open, writer),
+        DirectiveVariant::Close(close) => marshal_close_directive(close, writer),
+        DirectiveVariant::Commodity(commodity) => marshal_commodity_directive(commodity, writer),
+        DirectiveVariant::Pad(pad) => marshal_pad_directive(pad, writer),
         DirectiveVariant::Balance(balance) => marshal_balance_directive(balance, writer),
+        DirectiveVariant::Price(price) => marshal_price_directive(price, writer),
         DirectiveVariant::Transaction(transaction) => {
-            marshal_transaction_directive(transaction, writer)
+            marshal_transaction_directive_with_metadata(transaction, &Metadata::new(), writer)
         }
+        DirectiveVariant::Note(note) => marshal_note_directive(note, writer),
+        DirectiveVariant::Document(document) => marshal_document_directive(document, writer),
+        DirectiveVariant::Event(event) => marshal_event_directive(event, writer),
+        DirectiveVariant::Query(query) => marshal_query_directive(query, writer),
+        DirectiveVariant::Custom(custom) => marshal_custom_directive(custom, writer),
     }
 }
 
@@ -73,15 +124,38 @@ mod tests {
     #[case("2024-01-01 open Expenses:Food")]
     #[case("2024-06-15 open Income:Salary")]
     #[case("2024-01-01 open Equity:Opening-Balances")]
+    #[case("2020-01-01 close Assets:OldBank")]
+    #[case("2024-12-31 close Liabilities:CreditCard:CapitalOne")]
+    #[case("2010-01-01 commodity USD")]
+    #[case("2010-01-01 commodity USD\n  name: \"US Dollar\"\n  precision: 2")]
+    #[case(
+        "2024-01-01 open Assets:Cash USD\n  institution: \"Sparkasse\"\n  opened-by: Equity:Opening"
+    )]
+    #[case("2024-01-01 balance Assets:Cash 10 USD\n  checked: 2024-01-02\n  statement: 10.00 USD")]
+    #[case("2024-01-01 note Assets:Cash \"Moved\"\n  topic: #admin")]
+    #[case(
+        "2024-01-15 * \"Cafe\"\n  receipt: \"scan-42.pdf\"\n  Assets:Cash  -9.00 USD\n    rate: 1.1\n  Expenses:Food"
+    )]
+    #[case("2024-01-01 pad Assets:Checking Equity:Opening-Balances")]
     #[case("2024-12-26 balance Liabilities:CreditCard -3492.02 USD")]
     #[case("2024-01-01 balance Assets:Checking 1000.50 USD")]
     #[case("2023-09-20 balance Assets:Investment 319.020 ~ 0.002 RGAGX")]
     #[case("2024-06-30 balance Assets:Cash 0 USD")]
+    #[case("2024-01-02 price HOOL 579.18 USD")]
+    #[case("2024-01-02 price EUR 1.09 USD")]
+    #[case("2024-01-05 note Assets:Checking \"Called bank about fee\"")]
+    #[case("2024-02-01 document Assets:Checking \"statements/2024-01.pdf\"")]
+    #[case("2024-03-01 event \"location\" \"Berlin\"")]
+    #[case("2024-03-01 query \"cash\" \"SELECT account, sum(position)\"")]
+    #[case("2024-03-01 custom \"budget\" Expenses:Food \"monthly\" 400.00 EUR")]
     #[case(
         "2024-01-15 * \"Cafe Mogador\" \"Lamb tagine with wine\"\n  Liabilities:CreditCard  -37.45 USD\n  Expenses:Restaurant"
     )]
     #[case("2024-02-01 ! \"Direct deposit\"\n  Assets:Checking  2500.00 USD\n  Income:Salary")]
     #[case("2024-03-10 *\n  Assets:Cash  -20.00 USD\n  Expenses:Coffee  20.00 USD")]
+    #[case(
+        "2024-04-02 * \"Ramen\" #trip-japan ^receipt-12\n  Assets:Cash  -12.00 USD\n  Expenses:Food"
+    )]
     fn valid_directive_template(#[case] input: &str) {}
 
     #[apply(valid_directive_template)]
@@ -217,12 +291,39 @@ mod tests {
                 assert_eq!(components, ["Cash"]);
                 assert_eq!(open.commodity_constraints().len(), 1);
             }
+            DirectiveVariant::Close(_) => {
+                panic!("Expected Open directive, got Close");
+            }
+            DirectiveVariant::Commodity(_) => {
+                panic!("Expected Open directive, got Commodity");
+            }
+            DirectiveVariant::Price(_) => {
+                panic!("Expected Open directive, got Price");
+            }
+            DirectiveVariant::Pad(_) => {
+                panic!("Expected Open directive, got Pad");
+            }
             DirectiveVariant::Balance(_) => {
                 panic!("Expected Open directive, got Balance");
             }
             DirectiveVariant::Transaction(_) => {
                 panic!("Expected Open directive, got Transaction");
             }
+            DirectiveVariant::Note(_) => {
+                panic!("Expected Open directive, got Note");
+            }
+            DirectiveVariant::Document(_) => {
+                panic!("Expected Open directive, got Document");
+            }
+            DirectiveVariant::Event(_) => {
+                panic!("Expected Open directive, got Event");
+            }
+            DirectiveVariant::Query(_) => {
+                panic!("Expected Open directive, got Query");
+            }
+            DirectiveVariant::Custom(_) => {
+                panic!("Expected Open directive, got Custom");
+            }
         }
     }
 
@@ -267,6 +368,56 @@ mod tests {
         assert!(!result.has_output());
     }
 
+    #[test]
+    fn parse_directive_close_with_date() {
+        let input = "2020-01-01 close Assets:OldBank";
+        let result = parse_directive().parse(input);
+        assert!(result.has_output());
+        let directive = result.into_result().unwrap();
+
+        assert_eq!(
+            directive.date(),
+            &NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+        );
+        let close = directive.as_close().unwrap();
+        let components: Vec<&str> = close.account().components().map(AsRef::as_ref).collect();
+        assert_eq!(components, ["OldBank"]);
+    }
+
+    #[test]
+    fn marshal_directive_close() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let close_directive = crate::model::DirectiveClose::new(account!(Assets:OldBank));
+        let directive = Directive::new_close(date, close_directive);
+
+        let mut output = String::new();
+        let result = marshal_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "2020-01-01 close Assets:OldBank");
+    }
+
+    #[test]
+    fn parse_directive_commodity_with_metadata() {
+        let input = "2010-01-01 commodity USD\n  name: \"US Dollar\"\n  precision: 2";
+        let result = parse_directive().parse(input);
+        assert!(result.has_output());
+        let directive = result.into_result().unwrap();
+
+        let commodity = directive.as_commodity().unwrap();
+        assert_eq!(commodity.commodity().as_ref(), "USD");
+        assert_eq!(
+            directive.metadata().get("name").and_then(|v| v.as_str()),
+            Some("US Dollar")
+        );
+        assert_eq!(
+            directive
+                .metadata()
+                .get("precision")
+                .and_then(|v| v.as_number()),
+            Some(&rust_decimal::Decimal::new(2, 0))
+        );
+    }
+
     #[test]
     fn parse_directive_variant_balance() {
         let input = "balance Assets:Checking 1000.50 USD";
@@ -289,9 +440,36 @@ mod tests {
             DirectiveVariant::Open(_) => {
                 panic!("Expected Balance directive, got Open");
             }
+            DirectiveVariant::Close(_) => {
+                panic!("Expected Balance directive, got Close");
+            }
+            DirectiveVariant::Commodity(_) => {
+                panic!("Expected Balance directive, got Commodity");
+            }
+            DirectiveVariant::Price(_) => {
+                panic!("Expected Balance directive, got Price");
+            }
+            DirectiveVariant::Pad(_) => {
+                panic!("Expected Balance directive, got Pad");
+            }
             DirectiveVariant::Transaction(_) => {
                 panic!("Expected Balance directive, got Transaction");
             }
+            DirectiveVariant::Note(_) => {
+                panic!("Expected Balance directive, got Note");
+            }
+            DirectiveVariant::Document(_) => {
+                panic!("Expected Balance directive, got Document");
+            }
+            DirectiveVariant::Event(_) => {
+                panic!("Expected Balance directive, got Event");
+            }
+            DirectiveVariant::Query(_) => {
+                panic!("Expected Balance directive, got Query");
+            }
+            DirectiveVariant::Custom(_) => {
+                panic!("Expected Balance directive, got Custom");
+            }
         }
     }
 
@@ -0,0 +1,130 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveDocument,
+    parser::chumsky::{
+        account::{marshal_account, parse_account},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+    },
+};
+
+const KEYWORD_DOCUMENT: &str = "document";
+
+/// Parser for document directive (without date)
+/// Syntax: "document" <account> <quoted_string>
+pub fn parse_document_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveDocument<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_DOCUMENT)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_account())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_quoted_string())
+        .map(|(account, path)| DirectiveDocument::from_str_path(account, path))
+}
+
+/// Marshaller for document directive (without date)
+pub fn marshal_document_directive(
+    directive: &DirectiveDocument,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_DOCUMENT} ")?;
+    marshal_account(directive.account().clone(), writer)?;
+    write!(writer, " ")?;
+    marshal_quoted_string(&directive.path().to_string_lossy(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use std::path::Path;
+
+    #[template]
+    #[rstest]
+    #[case("document Assets:Checking \"statements/2024-01.pdf\"", vec!["Checking"], "statements/2024-01.pdf")]
+    #[case("document   Liabilities:CreditCard   \"/tmp/card.pdf\"", vec!["CreditCard"], "/tmp/card.pdf")]
+    #[case("document Assets:Bank:Savings \"my statement.pdf\"", vec!["Bank", "Savings"], "my statement.pdf")]
+    fn valid_document_directive_template(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_path: &str,
+    ) {
+    }
+
+    #[apply(valid_document_directive_template)]
+    fn parse_document_directive_valid(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_path: &str,
+    ) {
+        let result = parse_document_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse document directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        let components: Vec<&str> = parsed.account().components().map(AsRef::as_ref).collect();
+        assert_eq!(components, expected_account_components);
+        assert_eq!(parsed.path(), Path::new(expected_path));
+    }
+
+    #[apply(valid_document_directive_template)]
+    fn marshal_and_parse_document_directive(
+        #[case] input: &str,
+        #[case] _expected_account_components: Vec<&str>,
+        #[case] _expected_path: &str,
+    ) {
+        // First parse the original
+        let result = parse_document_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_document_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_document_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("document")] // Missing account and path
+    #[case("document Assets:Checking")] // Missing path
+    #[case("document Assets:Checking statement.pdf")] // Path not quoted
+    #[case("documentAssets:Checking \"x.pdf\"")] // Missing space
+    #[case("document \"x.pdf\"")] // Missing account
+    fn parse_document_directive_invalid(#[case] input: &str) {
+        let result = parse_document_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_document_directive_basic() {
+        let directive = DirectiveDocument::new(
+            account!(Assets:Checking),
+            Path::new("statements/2024-01.pdf"),
+        );
+
+        let mut output = String::new();
+        let result = marshal_document_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(
+            output,
+            "document Assets:Checking \"statements/2024-01.pdf\""
+        );
+    }
+}
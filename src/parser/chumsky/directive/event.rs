@@ -0,0 +1,117 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveEvent,
+    parser::chumsky::quoted_string::{marshal_quoted_string, parse_quoted_string},
+};
+
+const KEYWORD_EVENT: &str = "event";
+
+/// Parser for event directive (without date)
+/// Syntax: "event" <quoted_string> <quoted_string>
+pub fn parse_event_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveEvent<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_EVENT)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_quoted_string())
+        .map(|(event_type, description)| DirectiveEvent::new(event_type, description))
+}
+
+/// Marshaller for event directive (without date)
+pub fn marshal_event_directive(
+    directive: &DirectiveEvent,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_EVENT} ")?;
+    marshal_quoted_string(directive.event_type(), writer)?;
+    write!(writer, " ")?;
+    marshal_quoted_string(directive.description(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("event \"location\" \"Berlin\"", "location", "Berlin")]
+    #[case("event   \"employer\"   \"Hooli Inc.\"", "employer", "Hooli Inc.")]
+    #[case("event \"location\" \"\"", "location", "")]
+    fn valid_event_directive_template(
+        #[case] input: &str,
+        #[case] expected_event_type: &str,
+        #[case] expected_description: &str,
+    ) {
+    }
+
+    #[apply(valid_event_directive_template)]
+    fn parse_event_directive_valid(
+        #[case] input: &str,
+        #[case] expected_event_type: &str,
+        #[case] expected_description: &str,
+    ) {
+        let result = parse_event_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse event directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.event_type(), expected_event_type);
+        assert_eq!(parsed.description(), expected_description);
+    }
+
+    #[apply(valid_event_directive_template)]
+    fn marshal_and_parse_event_directive(
+        #[case] input: &str,
+        #[case] _expected_event_type: &str,
+        #[case] _expected_description: &str,
+    ) {
+        // First parse the original
+        let result = parse_event_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_event_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_event_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("event")] // Missing type and description
+    #[case("event \"location\"")] // Missing description
+    #[case("event location \"Berlin\"")] // Type not quoted
+    #[case("event\"location\" \"Berlin\"")] // Missing space
+    fn parse_event_directive_invalid(#[case] input: &str) {
+        let result = parse_event_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_event_directive_basic() {
+        let directive = DirectiveEvent::new("location", "Berlin");
+
+        let mut output = String::new();
+        let result = marshal_event_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "event \"location\" \"Berlin\"");
+    }
+}
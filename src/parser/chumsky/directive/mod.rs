@@ -1,6 +1,15 @@
 mod balance;
+mod close;
+mod commodity;
+mod custom;
 mod directive;
+mod document;
+mod event;
+mod note;
 mod open;
+mod pad;
+mod price;
+mod query;
 mod transaction;
 
 pub use directive::{marshal_directive, parse_directive};
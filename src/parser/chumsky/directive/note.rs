@@ -0,0 +1,124 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveNote,
+    parser::chumsky::{
+        account::{marshal_account, parse_account},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+    },
+};
+
+const KEYWORD_NOTE: &str = "note";
+
+/// Parser for note directive (without date)
+/// Syntax: "note" <account> <quoted_string>
+pub fn parse_note_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveNote<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_NOTE)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_account())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_quoted_string())
+        .map(|(account, comment)| DirectiveNote::new(account, comment))
+}
+
+/// Marshaller for note directive (without date)
+pub fn marshal_note_directive(
+    directive: &DirectiveNote,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_NOTE} ")?;
+    marshal_account(directive.account().clone(), writer)?;
+    write!(writer, " ")?;
+    marshal_quoted_string(directive.comment(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("note Assets:Checking \"Called bank about fee\"", vec!["Checking"], "Called bank about fee")]
+    #[case("note   Liabilities:CreditCard   \"\"", vec!["CreditCard"], "")]
+    #[case("note Assets:Bank:Savings \"Rate is \\\"2%\\\"\"", vec!["Bank", "Savings"], "Rate is \"2%\"")]
+    fn valid_note_directive_template(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_comment: &str,
+    ) {
+    }
+
+    #[apply(valid_note_directive_template)]
+    fn parse_note_directive_valid(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_comment: &str,
+    ) {
+        let result = parse_note_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse note directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        let components: Vec<&str> = parsed.account().components().map(AsRef::as_ref).collect();
+        assert_eq!(components, expected_account_components);
+        assert_eq!(parsed.comment(), expected_comment);
+    }
+
+    #[apply(valid_note_directive_template)]
+    fn marshal_and_parse_note_directive(
+        #[case] input: &str,
+        #[case] _expected_account_components: Vec<&str>,
+        #[case] _expected_comment: &str,
+    ) {
+        // First parse the original
+        let result = parse_note_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_note_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_note_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("note")] // Missing account and comment
+    #[case("note Assets:Checking")] // Missing comment
+    #[case("note Assets:Checking Called bank")] // Comment not quoted
+    #[case("noteAssets:Checking \"x\"")] // Missing space
+    #[case("note Assets:Checking\"x\"")] // Missing space
+    #[case("note \"x\"")] // Missing account
+    fn parse_note_directive_invalid(#[case] input: &str) {
+        let result = parse_note_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_note_directive_basic() {
+        let directive = DirectiveNote::new(account!(Assets:Checking), "Called bank about fee");
+
+        let mut output = String::new();
+        let result = marshal_note_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "note Assets:Checking \"Called bank about fee\"");
+    }
+}
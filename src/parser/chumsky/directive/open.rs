@@ -1,28 +1,56 @@
-use chumsky::{prelude::*, text::keyword};
-use std::fmt::Write;
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::{fmt::Write, str::FromStr};
 
 use crate::{
-    model::DirectiveOpen,
+    model::{BookingMethod, DirectiveOpen},
     parser::chumsky::{
         account::{marshal_account, parse_account},
         commodity_list::{marshal_commodity_list, parse_commodity_list},
+        quoted_string::marshal_quoted_string,
     },
 };
 
 const KEYWORD_OPEN: &str = "open";
 
 /// Parser for open directive (without date)
-/// Syntax: "open" <account> [<commodity_list>]
+/// Syntax: "open" <account> [<commodity_list>] [<quoted_booking_method>]
 pub fn parse_open_directive<'a>()
 -> impl Parser<'a, &'a str, DirectiveOpen<'a>, extra::Err<Rich<'a, char>>> {
     keyword(KEYWORD_OPEN)
-        .ignore_then(parse_account().padded())
+        .ignore_then(parse_account().padded_by(inline_whitespace()))
         .then(
             parse_commodity_list()
                 .or_not()
                 .map(|opt| opt.unwrap_or_default()),
         )
-        .map(|(account, commodity_constraints)| DirectiveOpen::new(account, commodity_constraints))
+        .then(
+            inline_whitespace()
+                .ignore_then(parse_booking_method())
+                .or_not(),
+        )
+        .map(|((account, commodity_constraints), booking_method)| {
+            let directive = DirectiveOpen::new(account, commodity_constraints);
+            match booking_method {
+                Some(booking_method) => directive.with_booking_method(booking_method),
+                None => directive,
+            }
+        })
+}
+
+fn parse_booking_method<'a>() -> impl Parser<'a, &'a str, BookingMethod, extra::Err<Rich<'a, char>>>
+{
+    // the name is validated inside the quotes, so that an unknown booking method is reported
+    // rather than the quoted string failing to parse as a commodity
+    let booking_method = none_of("\"\n")
+        .repeated()
+        .to_slice()
+        .try_map(|booking_method, span| {
+            BookingMethod::from_str(booking_method).map_err(|e| Rich::custom(span, e.to_string()))
+        });
+    booking_method.delimited_by(just('"'), just('"'))
 }
 
 /// Marshaller for open directive (without date)
@@ -40,6 +68,11 @@ pub fn marshal_open_directive(
         marshal_commodity_list(directive.commodity_constraints(), writer)?;
     }
 
+    if let Some(booking_method) = directive.booking_method() {
+        write!(writer, " ")?;
+        marshal_quoted_string(&booking_method.to_string(), writer)?;
+    }
+
     Ok(())
 }
 
@@ -156,4 +189,52 @@ mod tests {
         // Commodities should be sorted alphabetically
         assert_eq!(output, "open Assets:Investment EUR,GBP,USD");
     }
+
+    #[rstest]
+    #[case("open Assets:Stocks HOOL \"FIFO\"", BookingMethod::Fifo)]
+    #[case(
+        "open Assets:Stocks HOOL,USD  \"STRICT_WITH_SIZE\"",
+        BookingMethod::StrictWithSize
+    )]
+    #[case("open Assets:Stocks \"NONE\"", BookingMethod::None)]
+    fn parse_open_directive_with_booking_method(
+        #[case] input: &str,
+        #[case] expected_booking_method: BookingMethod,
+    ) {
+        let parsed = parse_open_directive().parse(input).into_result().unwrap();
+
+        assert_eq!(parsed.booking_method(), Some(expected_booking_method));
+    }
+
+    #[test]
+    fn parse_open_directive_without_booking_method() {
+        let parsed = parse_open_directive()
+            .parse("open Assets:Stocks HOOL")
+            .into_result()
+            .unwrap();
+
+        assert_eq!(parsed.booking_method(), None);
+    }
+
+    #[rstest]
+    #[case("open Assets:Stocks HOOL \"fifo\"")] // Booking methods are uppercase
+    #[case("open Assets:Stocks HOOL \"FIRST\"")] // Unknown booking method
+    #[case("open Assets:Stocks HOOL FIFO \"FIFO\"")] // Unquoted extra token
+    fn parse_open_directive_invalid_booking_method(#[case] input: &str) {
+        let result = parse_open_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_open_directive_with_booking_method() {
+        let directive = DirectiveOpen::new(
+            account!(Assets:Stocks),
+            hash_set![Commodity::try_from("HOOL").unwrap()],
+        )
+        .with_booking_method(BookingMethod::Lifo);
+
+        let mut output = String::new();
+        marshal_open_directive(&directive, &mut output).unwrap();
+        assert_eq!(output, "open Assets:Stocks HOOL \"LIFO\"");
+    }
 }
@@ -0,0 +1,135 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectivePad,
+    parser::chumsky::account::{marshal_account, parse_account},
+};
+
+const KEYWORD_PAD: &str = "pad";
+
+/// Parser for pad directive (without date)
+/// Syntax: "pad" <account> <source_account>
+pub fn parse_pad_directive<'a>()
+-> impl Parser<'a, &'a str, DirectivePad<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_PAD)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_account())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_account())
+        .map(|(account, source_account)| DirectivePad::new(account, source_account))
+}
+
+/// Marshaller for pad directive (without date)
+pub fn marshal_pad_directive(
+    directive: &DirectivePad,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_PAD} ")?;
+    marshal_account(directive.account().clone(), writer)?;
+    write!(writer, " ")?;
+    marshal_account(directive.source_account().clone(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case(
+        "pad Assets:Checking Equity:Opening-Balances",
+        vec!["Checking"],
+        vec!["Opening-Balances"]
+    )]
+    #[case(
+        "pad   Assets:Bank:Savings   Equity:Opening-Balances",
+        vec!["Bank", "Savings"],
+        vec!["Opening-Balances"]
+    )]
+    #[case("pad Liabilities:CreditCard Expenses:Unknown", vec!["CreditCard"], vec!["Unknown"])]
+    fn valid_pad_directive_template(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_source_account_components: Vec<&str>,
+    ) {
+    }
+
+    #[apply(valid_pad_directive_template)]
+    fn parse_pad_directive_valid(
+        #[case] input: &str,
+        #[case] expected_account_components: Vec<&str>,
+        #[case] expected_source_account_components: Vec<&str>,
+    ) {
+        let result = parse_pad_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse pad directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        let components: Vec<&str> = parsed.account().components().map(AsRef::as_ref).collect();
+        assert_eq!(components, expected_account_components);
+        let source_components: Vec<&str> = parsed
+            .source_account()
+            .components()
+            .map(AsRef::as_ref)
+            .collect();
+        assert_eq!(source_components, expected_source_account_components);
+    }
+
+    #[apply(valid_pad_directive_template)]
+    fn marshal_and_parse_pad_directive(
+        #[case] input: &str,
+        #[case] _expected_account_components: Vec<&str>,
+        #[case] _expected_source_account_components: Vec<&str>,
+    ) {
+        // First parse the original
+        let result = parse_pad_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_pad_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_pad_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("pad")] // Missing accounts
+    #[case("pad Assets:Checking")] // Missing source account
+    #[case("pad Assets:Checking ")] // Missing source account
+    #[case("padAssets:Checking Equity:Opening")] // Missing space
+    #[case("pad Assets:Checking equity:opening")] // Invalid source account
+    #[case("pad Assets:Checking Equity:Opening USD")] // Pad takes no amount
+    fn parse_pad_directive_invalid(#[case] input: &str) {
+        let result = parse_pad_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_pad_directive_basic() {
+        let directive =
+            DirectivePad::new(account!(Assets:Checking), account!(Equity:OpeningBalances));
+
+        let mut output = String::new();
+        let result = marshal_pad_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "pad Assets:Checking Equity:OpeningBalances");
+    }
+}
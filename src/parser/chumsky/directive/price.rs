@@ -0,0 +1,136 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectivePrice,
+    parser::chumsky::{
+        amount::{marshal_amount, parse_amount},
+        commodity::{marshal_commodity, parse_commodity},
+    },
+};
+
+const KEYWORD_PRICE: &str = "price";
+
+/// Parser for price directive (without date)
+/// Syntax: "price" <commodity> <amount>
+pub fn parse_price_directive<'a>()
+-> impl Parser<'a, &'a str, DirectivePrice<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_PRICE)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_commodity())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_amount())
+        .map(|(commodity, price)| DirectivePrice::new(commodity, price))
+}
+
+/// Marshaller for price directive (without date)
+pub fn marshal_price_directive(
+    directive: &DirectivePrice,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_PRICE} ")?;
+    marshal_commodity(directive.commodity(), writer)?;
+    write!(writer, " ")?;
+    marshal_amount(directive.price(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, commodity};
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    #[template]
+    #[rstest]
+    #[case("price HOOL 579.18 USD", "HOOL", dec!(579.18), "USD")]
+    #[case("price USD 1.10 CAD", "USD", dec!(1.10), "CAD")]
+    #[case("price EUR   0.92 USD", "EUR", dec!(0.92), "USD")]
+    #[case("price   BTC 43000 USD", "BTC", dec!(43000), "USD")]
+    #[case("price VBMPX 10.045 USD", "VBMPX", dec!(10.045), "USD")]
+    fn valid_price_directive_template(
+        #[case] input: &str,
+        #[case] expected_commodity: &str,
+        #[case] expected_number: Decimal,
+        #[case] expected_quote_commodity: &str,
+    ) {
+    }
+
+    #[apply(valid_price_directive_template)]
+    fn parse_price_directive_valid(
+        #[case] input: &str,
+        #[case] expected_commodity: &str,
+        #[case] expected_number: Decimal,
+        #[case] expected_quote_commodity: &str,
+    ) {
+        let result = parse_price_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse price directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.commodity().as_ref(), expected_commodity);
+        assert_eq!(*parsed.price().number(), expected_number);
+        assert_eq!(
+            parsed.price().commodity().as_ref(),
+            expected_quote_commodity
+        );
+    }
+
+    #[apply(valid_price_directive_template)]
+    fn marshal_and_parse_price_directive(
+        #[case] input: &str,
+        #[case] _expected_commodity: &str,
+        #[case] _expected_number: Decimal,
+        #[case] _expected_quote_commodity: &str,
+    ) {
+        // First parse the original
+        let result = parse_price_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_price_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_price_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("price")] // Missing commodity and amount
+    #[case("price HOOL")] // Missing amount
+    #[case("price HOOL 579.18")] // Missing quote commodity
+    #[case("priceHOOL 579.18 USD")] // Missing space
+    #[case("price HOOL579.18 USD")] // Missing space
+    #[case("price hool 579.18 USD")] // Invalid commodity
+    #[case("price HOOL USD")] // Missing number
+    fn parse_price_directive_invalid(#[case] input: &str) {
+        let result = parse_price_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_price_directive_basic() {
+        let directive =
+            DirectivePrice::new(commodity!(HOOL), Amount::new(dec!(579.18), commodity!(USD)));
+
+        let mut output = String::new();
+        let result = marshal_price_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "price HOOL 579.18 USD");
+    }
+}
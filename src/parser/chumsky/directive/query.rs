@@ -0,0 +1,121 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::DirectiveQuery,
+    parser::chumsky::quoted_string::{marshal_quoted_string, parse_quoted_string},
+};
+
+const KEYWORD_QUERY: &str = "query";
+
+/// Parser for query directive (without date)
+/// Syntax: "query" <quoted_string> <quoted_string>
+pub fn parse_query_directive<'a>()
+-> impl Parser<'a, &'a str, DirectiveQuery<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_QUERY)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_quoted_string())
+        .map(|(name, query)| DirectiveQuery::new(name, query))
+}
+
+/// Marshaller for query directive (without date)
+pub fn marshal_query_directive(
+    directive: &DirectiveQuery,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_QUERY} ")?;
+    marshal_quoted_string(directive.name(), writer)?;
+    write!(writer, " ")?;
+    marshal_quoted_string(directive.query(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case(
+        "query \"france-balances\" \"SELECT account, sum(position) WHERE 'trip-france' IN tags\"",
+        "france-balances",
+        "SELECT account, sum(position) WHERE 'trip-france' IN tags"
+    )]
+    #[case("query   \"cash\"   \"SELECT 1\"", "cash", "SELECT 1")]
+    #[case("query \"quoted\" \"SELECT \\\"x\\\"\"", "quoted", "SELECT \"x\"")]
+    fn valid_query_directive_template(
+        #[case] input: &str,
+        #[case] expected_name: &str,
+        #[case] expected_query: &str,
+    ) {
+    }
+
+    #[apply(valid_query_directive_template)]
+    fn parse_query_directive_valid(
+        #[case] input: &str,
+        #[case] expected_name: &str,
+        #[case] expected_query: &str,
+    ) {
+        let result = parse_query_directive().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse query directive: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.name(), expected_name);
+        assert_eq!(parsed.query(), expected_query);
+    }
+
+    #[apply(valid_query_directive_template)]
+    fn marshal_and_parse_query_directive(
+        #[case] input: &str,
+        #[case] _expected_name: &str,
+        #[case] _expected_query: &str,
+    ) {
+        // First parse the original
+        let result = parse_query_directive().parse(input);
+        assert!(result.has_output());
+        let original = result.into_result().unwrap();
+
+        // Marshal it
+        let mut marshalled = String::new();
+        let marshal_result = marshal_query_directive(&original, &mut marshalled);
+        assert!(marshal_result.is_ok());
+
+        // Parse it back
+        let reparse_result = parse_query_directive().parse(&marshalled);
+        assert!(reparse_result.has_output());
+        let reparsed = reparse_result.into_result().unwrap();
+
+        // Should be equal
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("query")] // Missing name and query
+    #[case("query \"cash\"")] // Missing query
+    #[case("query cash \"SELECT 1\"")] // Name not quoted
+    #[case("query \"cash\" SELECT")] // Query not quoted
+    fn parse_query_directive_invalid(#[case] input: &str) {
+        let result = parse_query_directive().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_query_directive_basic() {
+        let directive = DirectiveQuery::new("cash", "SELECT 1");
+
+        let mut output = String::new();
+        let result = marshal_query_directive(&directive, &mut output);
+        assert!(result.is_ok());
+        assert_eq!(output, "query \"cash\" \"SELECT 1\"");
+    }
+}
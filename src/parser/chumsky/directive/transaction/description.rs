@@ -1,4 +1,4 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
@@ -12,7 +12,7 @@ pub fn parse_transaction_description<'a>()
 -> impl Parser<'a, &'a str, TransactionDescription<'a>, extra::Err<Rich<'a, char>>> {
     parse_quoted_string()
         .then(
-            whitespace()
+            inline_whitespace()
                 .at_least(1)
                 .ignore_then(parse_quoted_string())
                 .or_not(),
@@ -230,7 +230,7 @@ mod tests {
     fn parse_transaction_description_in_context() {
         // Test parsing description after a flag, simulating transaction parsing
         let flag_then_description = just('*')
-            .then_ignore(whitespace().at_least(1))
+            .then_ignore(inline_whitespace().at_least(1))
             .then(parse_transaction_description());
 
         let result = flag_then_description.parse("* \"Payee\" \"Description\"");
@@ -3,4 +3,6 @@ mod flag;
 mod posting;
 mod posting_amount;
 mod transaction;
-pub use transaction::{marshal_transaction_directive, parse_transaction_directive};
+pub use transaction::{
+    marshal_transaction_directive_with_metadata, parse_transaction_directive_with_metadata,
+};
@@ -1,29 +1,42 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
-    model::directive::Posting,
+    model::{FileId, SourceSpan, directive::Posting},
     parser::chumsky::{
         account::{marshal_account, parse_account},
         directive::transaction::flag::{marshal_flag, parse_flag},
         directive::transaction::posting_amount::{marshal_posting_amount, parse_posting_amount},
+        metadata::{marshal_posting_metadata, parse_metadata},
     },
 };
 
 /// Parser for posting line
-/// Syntax: <whitespace> [<flag>] <account> [<amount> [{<cost>}] [@ <price>]]
+/// Syntax: <whitespace> [<flag>] <account> [<amount> [{<cost>}] [@ <price>]] <metadata>
 pub fn parse_posting<'a>() -> impl Parser<'a, &'a str, Posting<'a>, extra::Err<Rich<'a, char>>> {
-    whitespace()
-        .at_least(1)
-        .ignore_then(parse_flag().then_ignore(whitespace().at_least(1)).or_not())
+    let posting_line = parse_flag()
+        .then_ignore(inline_whitespace().at_least(1))
+        .or_not()
         .then(parse_account())
         .then(
-            whitespace()
+            inline_whitespace()
                 .at_least(1)
                 .ignore_then(parse_posting_amount())
                 .or_not(),
         )
-        .map(|((flag, account), posting_amount)| {
+        .map_with(|posting, e| {
+            let span: SimpleSpan = e.span();
+            (
+                posting,
+                SourceSpan::new(FileId::default(), span.into_range()),
+            )
+        });
+
+    inline_whitespace()
+        .at_least(1)
+        .ignore_then(posting_line)
+        .then(parse_metadata())
+        .map(|((((flag, account), posting_amount), span), metadata)| {
             let mut posting = match posting_amount {
                 Some(amount) => Posting::new(account, amount),
                 None => Posting::new_without_amount(account),
@@ -33,7 +46,7 @@ pub fn parse_posting<'a>() -> impl Parser<'a, &'a str, Posting<'a>, extra::Err<R
                 posting = posting.with_flag(f);
             }
 
-            posting
+            posting.with_metadata(metadata).with_span(span)
         })
 }
 
@@ -53,17 +66,28 @@ pub fn marshal_posting(posting: &Posting, writer: &mut impl Write) -> std::fmt::
         marshal_posting_amount(posting_amount, writer)?;
     }
 
-    Ok(())
+    marshal_posting_metadata(posting.metadata(), writer)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{AccountType, Amount, Flag, account, commodity, directive::PostingAmount};
+    use crate::model::{
+        AccountType, Amount, CostSpec, Flag, PostingPrice, account, commodity,
+        directive::PostingAmount,
+    };
     use rstest::rstest;
     use rstest_reuse::*;
     use rust_decimal_macros::dec;
 
+    /// (number, commodity, cost, price)
+    type ExpectedAmount<'a> = Option<(
+        rust_decimal::Decimal,
+        &'a str,
+        Option<(rust_decimal::Decimal, &'a str)>,
+        Option<(rust_decimal::Decimal, &'a str)>,
+    )>;
+
     #[template]
     #[rstest]
     #[case("  Assets:Checking  100.50 USD", None, AccountType::Assets, vec!["Checking"], Some((dec!(100.50), "USD", None, None)))]
@@ -84,12 +108,7 @@ mod tests {
         #[case] expected_flag: Option<Flag>,
         #[case] expected_account_type: AccountType,
         #[case] expected_account_components: Vec<&str>,
-        #[case] expected_amount: Option<(
-            rust_decimal::Decimal,
-            &str,
-            Option<(rust_decimal::Decimal, &str)>,
-            Option<(rust_decimal::Decimal, &str)>,
-        )>,
+        #[case] expected_amount: ExpectedAmount<'_>,
     ) {
     }
 
@@ -99,12 +118,7 @@ mod tests {
         #[case] expected_flag: Option<Flag>,
         #[case] expected_account_type: AccountType,
         #[case] expected_account_components: Vec<&str>,
-        #[case] expected_amount: Option<(
-            rust_decimal::Decimal,
-            &str,
-            Option<(rust_decimal::Decimal, &str)>,
-            Option<(rust_decimal::Decimal, &str)>,
-        )>,
+        #[case] expected_amount: ExpectedAmount<'_>,
     ) {
         let result = parse_posting().parse(input);
         assert!(result.has_output(), "Failed to parse posting: {}", input);
@@ -123,15 +137,18 @@ mod tests {
             Some((exp_number, exp_commodity, exp_cost, exp_price)) => {
                 assert!(parsed.has_amount());
                 let posting_amount = parsed.amount().unwrap();
-                assert_eq!(*posting_amount.amount().number(), exp_number);
-                assert_eq!(posting_amount.amount().commodity().as_ref(), exp_commodity);
+                assert_eq!(*posting_amount.amount().unwrap().number(), exp_number);
+                assert_eq!(
+                    posting_amount.amount().unwrap().commodity().as_ref(),
+                    exp_commodity
+                );
 
                 // Validate cost
                 if let Some((cost_number, cost_commodity)) = exp_cost {
                     assert!(posting_amount.has_cost());
                     let cost = posting_amount.cost().unwrap();
-                    assert_eq!(*cost.number(), cost_number);
-                    assert_eq!(cost.commodity().as_ref(), cost_commodity);
+                    assert_eq!(*cost.per_unit_cost().unwrap(), cost_number);
+                    assert_eq!(cost.commodity().unwrap().as_ref(), cost_commodity);
                 } else {
                     assert!(!posting_amount.has_cost());
                 }
@@ -140,8 +157,11 @@ mod tests {
                 if let Some((price_number, price_commodity)) = exp_price {
                     assert!(posting_amount.has_price());
                     let price = posting_amount.price().unwrap();
-                    assert_eq!(*price.number(), price_number);
-                    assert_eq!(price.commodity().as_ref(), price_commodity);
+                    assert_eq!(*price.amount().number().unwrap(), price_number);
+                    assert_eq!(
+                        price.amount().commodity().unwrap().as_ref(),
+                        price_commodity
+                    );
                 } else {
                     assert!(!posting_amount.has_price());
                 }
@@ -158,12 +178,7 @@ mod tests {
         #[case] _expected_flag: Option<Flag>,
         #[case] _expected_account_type: AccountType,
         #[case] _expected_account_components: Vec<&str>,
-        #[case] _expected_amount: Option<(
-            rust_decimal::Decimal,
-            &str,
-            Option<(rust_decimal::Decimal, &str)>,
-            Option<(rust_decimal::Decimal, &str)>,
-        )>,
+        #[case] _expected_amount: ExpectedAmount<'_>,
     ) {
         // Parse the original
         let result = parse_posting().parse(input);
@@ -270,7 +285,7 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
         let posting_amount = PostingAmount::new(amount).with_cost(cost);
         let posting = Posting::new(account, posting_amount);
 
@@ -286,7 +301,7 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let price = Amount::new(dec!(55.00), usd);
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
         let posting_amount = PostingAmount::new(amount).with_price(price);
         let posting = Posting::new(account, posting_amount);
 
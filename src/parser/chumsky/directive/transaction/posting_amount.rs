@@ -1,16 +1,28 @@
-use chumsky::{prelude::*, text::whitespace};
-use std::fmt::Write;
+use chrono::NaiveDate;
+use chumsky::{prelude::*, text::inline_whitespace};
+use rust_decimal::Decimal;
+use std::{borrow::Cow, fmt::Write};
 
 use crate::{
-    model::{Amount, directive::PostingAmount},
-    parser::chumsky::amount::{marshal_amount, parse_amount},
+    model::{
+        Commodity,
+        directive::{CostSpec, PostingAmount, PostingPrice},
+    },
+    parser::chumsky::{
+        commodity::{marshal_commodity, parse_commodity},
+        date::{marshal_date, parse_date},
+        decimal::{marshal_decimal, parse_decimal},
+        incomplete_amount::{marshal_incomplete_amount, parse_incomplete_amount},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+    },
 };
 
-/// Parser for posting amount with optional cost and price
-/// Syntax: <amount> [{<cost>}] [@ <price>]
+/// Parser for posting amount with optional cost and price.
+/// The units and the price may be missing their number or commodity.
+/// Syntax: <incomplete_amount> [<cost_spec>] [(@ | @@) <incomplete_amount>]
 pub fn parse_posting_amount<'a>()
 -> impl Parser<'a, &'a str, PostingAmount<'a>, extra::Err<Rich<'a, char>>> {
-    parse_amount()
+    parse_incomplete_amount()
         .then(parse_cost().or_not())
         .then(parse_price().or_not())
         .map(|((amount, cost), price)| {
@@ -25,51 +37,234 @@ pub fn parse_posting_amount<'a>()
         })
 }
 
-fn parse_cost<'a>() -> impl Parser<'a, &'a str, Amount<'a>, extra::Err<Rich<'a, char>>> {
-    whitespace()
+/// Parser for cost specification, preceded by whitespace
+/// Syntax: "{" [<cost_component> {"," <cost_component>}] "}"
+///       | "{{" [<cost_component> {"," <cost_component>}] "}}"
+/// In the double brace form, the number is the total cost and "#" is not allowed.
+fn parse_cost<'a>() -> impl Parser<'a, &'a str, CostSpec<'a>, extra::Err<Rich<'a, char>>> {
+    let total_cost = parse_cost_components()
+        .delimited_by(just("{{"), just("}}"))
+        .map(|components| (true, components));
+    let per_unit_cost = parse_cost_components()
+        .delimited_by(just('{'), just('}'))
+        .map(|components| (false, components));
+
+    inline_whitespace()
         .at_least(1)
-        .ignore_then(just('{'))
-        .ignore_then(whitespace())
-        .ignore_then(parse_amount())
-        .then_ignore(whitespace())
-        .then_ignore(just('}'))
+        .ignore_then(total_cost.or(per_unit_cost))
+        .try_map(|(is_total, components), span| {
+            build_cost_spec(is_total, components).map_err(|message| Rich::custom(span, message))
+        })
 }
 
-fn parse_price<'a>() -> impl Parser<'a, &'a str, Amount<'a>, extra::Err<Rich<'a, char>>> {
-    whitespace()
+#[derive(Debug, Clone, PartialEq)]
+enum CostComponent<'a> {
+    /// Syntax: [<number>] ["#" [<number>]] [<commodity>]
+    Amount {
+        per_unit: Option<Decimal>,
+        total: Option<Option<Decimal>>,
+        commodity: Option<Commodity<'a>>,
+    },
+    Date(NaiveDate),
+    Label(Cow<'a, str>),
+    Merge,
+}
+
+fn parse_cost_components<'a>()
+-> impl Parser<'a, &'a str, Vec<CostComponent<'a>>, extra::Err<Rich<'a, char>>> {
+    parse_cost_component()
+        .padded_by(inline_whitespace())
+        .separated_by(just(','))
+        .collect::<Vec<_>>()
+        .padded_by(inline_whitespace())
+}
+
+fn parse_cost_component<'a>()
+-> impl Parser<'a, &'a str, CostComponent<'a>, extra::Err<Rich<'a, char>>> {
+    let amount = parse_decimal()
+        .or_not()
+        .then(
+            inline_whitespace()
+                .ignore_then(just('#'))
+                .ignore_then(inline_whitespace())
+                .ignore_then(parse_decimal().or_not())
+                .or_not(),
+        )
+        .then(inline_whitespace().ignore_then(parse_commodity()).or_not())
+        .try_map(|((per_unit, total), commodity), span| {
+            if per_unit.is_none() && total.is_none() && commodity.is_none() {
+                return Err(Rich::custom(span, "Expected cost component"));
+            }
+            Ok(CostComponent::Amount {
+                per_unit,
+                total,
+                commodity,
+            })
+        });
+
+    choice((
+        just('*').to(CostComponent::Merge),
+        parse_date().map(CostComponent::Date),
+        parse_quoted_string().map(CostComponent::Label),
+        amount,
+    ))
+}
+
+fn build_cost_spec<'a>(
+    is_total: bool,
+    components: Vec<CostComponent<'a>>,
+) -> Result<CostSpec<'a>, String> {
+    let mut cost_spec = CostSpec::new();
+    let mut has_amount = false;
+    for component in components {
+        match component {
+            CostComponent::Amount {
+                per_unit,
+                total,
+                commodity,
+            } => {
+                if has_amount {
+                    return Err("Duplicate cost amount".to_string());
+                }
+                has_amount = true;
+                match (is_total, per_unit, total) {
+                    (true, _, Some(_)) => {
+                        return Err("'#' is not allowed in total cost '{{...}}'".to_string());
+                    }
+                    (true, Some(number), None) => cost_spec = cost_spec.with_total_cost(number),
+                    (false, per_unit, total) => {
+                        if let Some(number) = per_unit {
+                            cost_spec = cost_spec.with_per_unit_cost(number);
+                        }
+                        if let Some(Some(number)) = total {
+                            cost_spec = cost_spec.with_total_cost(number);
+                        }
+                    }
+                    (true, None, None) => {}
+                }
+                if let Some(commodity) = commodity {
+                    cost_spec = cost_spec.with_commodity(commodity);
+                }
+            }
+            CostComponent::Date(date) => {
+                if cost_spec.date().is_some() {
+                    return Err("Duplicate cost date".to_string());
+                }
+                cost_spec = cost_spec.with_date(date);
+            }
+            CostComponent::Label(label) => {
+                if cost_spec.label().is_some() {
+                    return Err("Duplicate cost label".to_string());
+                }
+                cost_spec = cost_spec.with_label(label);
+            }
+            CostComponent::Merge => {
+                if cost_spec.is_merge() {
+                    return Err("Duplicate cost merge marker".to_string());
+                }
+                cost_spec = cost_spec.with_merge();
+            }
+        }
+    }
+    Ok(cost_spec)
+}
+
+/// Parser for price annotation, preceded by whitespace
+/// Syntax: "@" <incomplete_amount> | "@@" <incomplete_amount>
+fn parse_price<'a>() -> impl Parser<'a, &'a str, PostingPrice<'a>, extra::Err<Rich<'a, char>>> {
+    let total = just("@@")
+        .ignore_then(inline_whitespace().at_least(1))
+        .ignore_then(parse_incomplete_amount())
+        .map(PostingPrice::Total);
+    let per_unit = just('@')
+        .ignore_then(inline_whitespace().at_least(1))
+        .ignore_then(parse_incomplete_amount())
+        .map(PostingPrice::PerUnit);
+
+    inline_whitespace()
         .at_least(1)
-        .ignore_then(just('@'))
-        .ignore_then(whitespace().at_least(1))
-        .ignore_then(parse_amount())
+        .ignore_then(total.or(per_unit))
 }
 
 pub fn marshal_posting_amount(
     posting_amount: &PostingAmount,
     writer: &mut impl Write,
 ) -> std::fmt::Result {
-    // Write the amount
-    marshal_amount(posting_amount.amount(), writer)?;
+    // Write the units
+    marshal_incomplete_amount(posting_amount.units(), writer)?;
 
     // Write cost if present
     if let Some(cost) = posting_amount.cost() {
-        write!(writer, " {{")?;
-        marshal_amount(cost, writer)?;
-        write!(writer, "}}")?;
+        write!(writer, " ")?;
+        marshal_cost(cost, writer)?;
     }
 
     // Write price if present
-    if let Some(price) = posting_amount.price() {
-        write!(writer, " @ ")?;
-        marshal_amount(price, writer)?;
+    match posting_amount.price() {
+        Some(PostingPrice::PerUnit(price)) => {
+            write!(writer, " @ ")?;
+            marshal_incomplete_amount(price, writer)?;
+        }
+        Some(PostingPrice::Total(price)) => {
+            write!(writer, " @@ ")?;
+            marshal_incomplete_amount(price, writer)?;
+        }
+        None => {}
     }
 
     Ok(())
 }
 
+/// Marshaller for cost specification.
+/// A cost spec with only a total cost uses the double brace form "{{...}}".
+fn marshal_cost(cost: &CostSpec, writer: &mut impl Write) -> std::fmt::Result {
+    let is_total = cost.per_unit_cost().is_none() && cost.total_cost().is_some();
+    write!(writer, "{}", if is_total { "{{" } else { "{" })?;
+
+    let mut separator = "";
+    if is_total {
+        marshal_decimal(cost.total_cost().unwrap(), writer)?;
+        separator = " ";
+    } else {
+        if let Some(per_unit) = cost.per_unit_cost() {
+            marshal_decimal(per_unit, writer)?;
+            separator = " ";
+        }
+        if let Some(total) = cost.total_cost() {
+            write!(writer, "{separator}# ")?;
+            marshal_decimal(total, writer)?;
+            separator = " ";
+        }
+    }
+    if let Some(commodity) = cost.commodity() {
+        write!(writer, "{separator}")?;
+        marshal_commodity(commodity, writer)?;
+        separator = ", ";
+    } else if !separator.is_empty() {
+        separator = ", ";
+    }
+
+    if let Some(date) = cost.date() {
+        write!(writer, "{separator}")?;
+        marshal_date(date, writer)?;
+        separator = ", ";
+    }
+    if let Some(label) = cost.label() {
+        write!(writer, "{separator}")?;
+        marshal_quoted_string(label, writer)?;
+        separator = ", ";
+    }
+    if cost.is_merge() {
+        write!(writer, "{separator}*")?;
+    }
+
+    write!(writer, "{}", if is_total { "}}" } else { "}" })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::commodity;
+    use crate::model::{Amount, IncompleteAmount, commodity};
     use rstest::rstest;
     use rstest_reuse::*;
     use rust_decimal_macros::dec;
@@ -108,16 +303,19 @@ mod tests {
         let parsed = result.into_result().unwrap();
         
         // Validate amount
-        assert_eq!(*parsed.amount().number(), expected_number);
-        assert_eq!(parsed.amount().commodity().as_ref(), expected_commodity);
-        
+        assert_eq!(*parsed.amount().unwrap().number(), expected_number);
+        assert_eq!(
+            parsed.amount().unwrap().commodity().as_ref(),
+            expected_commodity
+        );
+
         // Validate cost
         match expected_cost {
             Some((cost_number, cost_commodity)) => {
                 assert!(parsed.has_cost());
                 let cost = parsed.cost().unwrap();
-                assert_eq!(*cost.number(), cost_number);
-                assert_eq!(cost.commodity().as_ref(), cost_commodity);
+                assert_eq!(*cost.per_unit_cost().unwrap(), cost_number);
+                assert_eq!(cost.commodity().unwrap().as_ref(), cost_commodity);
             }
             None => {
                 assert!(!parsed.has_cost());
@@ -129,8 +327,11 @@ mod tests {
             Some((price_number, price_commodity)) => {
                 assert!(parsed.has_price());
                 let price = parsed.price().unwrap();
-                assert_eq!(*price.number(), price_number);
-                assert_eq!(price.commodity().as_ref(), price_commodity);
+                assert_eq!(*price.amount().number().unwrap(), price_number);
+                assert_eq!(
+                    price.amount().commodity().unwrap().as_ref(),
+                    price_commodity
+                );
             }
             None => {
                 assert!(!parsed.has_price());
@@ -183,7 +384,7 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd);
         let posting_amount = PostingAmount::new(amount).with_cost(cost);
 
         let mut output = String::new();
@@ -197,7 +398,7 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let price = Amount::new(dec!(55.00), usd);
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
         let posting_amount = PostingAmount::new(amount).with_price(price);
 
         let mut output = String::new();
@@ -211,8 +412,8 @@ mod tests {
         let stock = commodity!(STOCK);
         let usd = commodity!(USD);
         let amount = Amount::new(dec!(10), stock);
-        let cost = Amount::new(dec!(50.00), usd.clone());
-        let price = Amount::new(dec!(55.00), usd);
+        let cost = CostSpec::per_unit(dec!(50.00), usd.clone());
+        let price = PostingPrice::PerUnit(Amount::new(dec!(55.00), usd).into());
         let posting_amount = PostingAmount::new(amount).with_cost(cost).with_price(price);
 
         let mut output = String::new();
@@ -222,13 +423,138 @@ mod tests {
     }
 
     #[rstest]
-    #[case("100.50")] // Missing commodity
-    #[case("STOCK {50.00 USD}")] // Missing amount number
-    #[case("10 STOCK {50.00}")] // Missing cost commodity
-    #[case("10 STOCK @ 55.00")] // Missing price commodity
+    #[case("-10 HOOL @@ 5000 USD", PostingPrice::Total(Amount::new(dec!(5000), commodity!(USD)).into()))]
+    #[case("-10 HOOL @@  5000 USD", PostingPrice::Total(Amount::new(dec!(5000), commodity!(USD)).into()))]
+    #[case("-10 HOOL @ 500 USD", PostingPrice::PerUnit(Amount::new(dec!(500), commodity!(USD)).into()))]
+    fn parse_price_kind(#[case] input: &str, #[case] expected_price: PostingPrice) {
+        let parsed = parse_posting_amount().parse(input).into_result().unwrap();
+
+        assert_eq!(parsed.price(), Some(&expected_price));
+        assert_eq!(
+            parsed.per_unit_price(),
+            Some(Amount::new(dec!(500), commodity!(USD)))
+        );
+    }
+
+    #[rstest]
+    #[case("USD", IncompleteAmount::MissingNumber(commodity!(USD)), None)]
+    #[case("10", IncompleteAmount::MissingCommodity(dec!(10)), None)]
+    #[case("HOOL {502.12 USD}", IncompleteAmount::MissingNumber(commodity!(HOOL)), None)]
+    #[case(
+        "10 HOOL @ USD",
+        IncompleteAmount::Complete(Amount::new(dec!(10), commodity!(HOOL))),
+        Some(PostingPrice::PerUnit(IncompleteAmount::MissingNumber(commodity!(USD))))
+    )]
+    #[case(
+        "10 HOOL @@ 5000",
+        IncompleteAmount::Complete(Amount::new(dec!(10), commodity!(HOOL))),
+        Some(PostingPrice::Total(IncompleteAmount::MissingCommodity(dec!(5000))))
+    )]
+    fn parse_and_marshal_incomplete_posting_amount(
+        #[case] input: &str,
+        #[case] expected_units: IncompleteAmount,
+        #[case] expected_price: Option<PostingPrice>,
+    ) {
+        let parsed = parse_posting_amount().parse(input).into_result().unwrap();
+        assert_eq!(parsed.units(), &expected_units);
+        assert_eq!(parsed.price(), expected_price.as_ref());
+        assert!(!parsed.is_complete());
+
+        let mut marshalled = String::new();
+        marshal_posting_amount(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[test]
+    fn marshal_posting_amount_with_total_price() {
+        let posting_amount = PostingAmount::new(Amount::new(dec!(-10), commodity!(HOOL)))
+            .with_cost(CostSpec::per_unit(dec!(450), commodity!(USD)))
+            .with_price(PostingPrice::Total(
+                Amount::new(dec!(5000), commodity!(USD)).into(),
+            ));
+
+        let mut output = String::new();
+        marshal_posting_amount(&posting_amount, &mut output).unwrap();
+        assert_eq!(output, "-10 HOOL {450 USD} @@ 5000 USD");
+    }
+
+    #[rstest]
+    #[case("10 STOCK {}", "10 STOCK {}", CostSpec::new())]
+    #[case("10 STOCK { }", "10 STOCK {}", CostSpec::new())]
+    #[case("10 STOCK {*}", "10 STOCK {*}", CostSpec::new().with_merge())]
+    #[case(
+        "10 STOCK {{502.12 USD}}",
+        "10 STOCK {{502.12 USD}}",
+        CostSpec::total(dec!(502.12), commodity!(USD))
+    )]
+    #[case(
+        "10 STOCK {502.12 # 9.95 USD}",
+        "10 STOCK {502.12 # 9.95 USD}",
+        CostSpec::per_unit(dec!(502.12), commodity!(USD)).with_total_cost(dec!(9.95))
+    )]
+    #[case(
+        "10 STOCK {# 9.95 USD}",
+        "10 STOCK {{9.95 USD}}",
+        CostSpec::total(dec!(9.95), commodity!(USD))
+    )]
+    #[case(
+        "10 STOCK {502.12 USD, 2024-01-15, \"first-lot\"}",
+        "10 STOCK {502.12 USD, 2024-01-15, \"first-lot\"}",
+        CostSpec::per_unit(dec!(502.12), commodity!(USD))
+            .with_date(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+            .with_label("first-lot")
+    )]
+    #[case(
+        "10 STOCK {\"first-lot\" , 2024-01-15}",
+        "10 STOCK {2024-01-15, \"first-lot\"}",
+        CostSpec::new()
+            .with_date(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+            .with_label("first-lot")
+    )]
+    #[case(
+        "10 STOCK {{1000 USD, 2024-01-15}}",
+        "10 STOCK {{1000 USD, 2024-01-15}}",
+        CostSpec::total(dec!(1000), commodity!(USD))
+            .with_date(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+    )]
+    #[case("10 STOCK {502.12}", "10 STOCK {502.12}", CostSpec::new().with_per_unit_cost(dec!(502.12)))]
+    #[case("10 STOCK {USD}", "10 STOCK {USD}", CostSpec::new().with_commodity(commodity!(USD)))]
+    #[case(
+        "10 STOCK {502.12 USD, *}",
+        "10 STOCK {502.12 USD, *}",
+        CostSpec::per_unit(dec!(502.12), commodity!(USD)).with_merge()
+    )]
+    fn parse_and_marshal_cost_spec(
+        #[case] input: &str,
+        #[case] expected_marshalled: &str,
+        #[case] expected_cost: CostSpec,
+    ) {
+        let parsed = parse_posting_amount().parse(input).into_result().unwrap();
+        assert_eq!(parsed.cost(), Some(&expected_cost));
+
+        let mut marshalled = String::new();
+        marshal_posting_amount(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, expected_marshalled);
+    }
+
+    #[rstest]
+    #[case("10 STOCK {{502.12 # 9.95 USD}}")] // '#' in total cost
+    #[case("10 STOCK {502.12 USD, 503 USD}")] // Duplicate amount
+    #[case("10 STOCK {2024-01-15, 2024-01-16}")] // Duplicate date
+    #[case("10 STOCK {\"a\", \"b\"}")] // Duplicate label
+    #[case("10 STOCK {502.12 USD,}")] // Trailing comma
+    #[case("10 STOCK {{502.12 USD}")] // Mismatched braces
+    fn parse_cost_spec_invalid(#[case] input: &str) {
+        let result = parse_posting_amount().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[rstest]
     #[case("10 STOCK {50.00 USD")] // Unclosed cost brace
     #[case("10 STOCK 50.00 USD}")] // Missing opening cost brace
     #[case("10 STOCK @")] // Missing price amount
+    #[case("10 STOCK @@")] // Missing total price amount
+    #[case("10 STOCK @@@ 5 USD")] // Triple at sign
     fn parse_posting_amount_invalid(#[case] input: &str) {
         let result = parse_posting_amount().parse(input);
         assert!(!result.has_output(), "Should fail to parse: {}", input);
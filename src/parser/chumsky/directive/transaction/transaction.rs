@@ -1,48 +1,110 @@
-use chumsky::{prelude::*, text::whitespace};
+use chumsky::{prelude::*, text::inline_whitespace};
 use std::fmt::Write;
 
 use crate::{
-    model::{DirectiveTransaction, Flag, directive::Posting},
-    parser::chumsky::directive::transaction::{
-        description::{marshal_transaction_description, parse_transaction_description},
-        flag::{marshal_flag, parse_flag},
-        posting::{marshal_posting, parse_posting},
+    model::{DirectiveTransaction, Flag, Link, Metadata, Tag, directive::Posting},
+    parser::chumsky::{
+        comment::parse_line_end,
+        directive::transaction::{
+            description::{marshal_transaction_description, parse_transaction_description},
+            flag::{marshal_flag, parse_flag},
+            posting::{marshal_posting, parse_posting},
+        },
+        link::{marshal_link, parse_link},
+        metadata::{marshal_metadata, parse_metadata},
+        tag::{marshal_tag, parse_tag},
     },
 };
 
 const KEYWORD_TXN: &str = "txn";
 
-/// Parser for transaction directive (without date)
-/// Syntax: <flag> [<description>] <postings>
-pub fn parse_transaction_directive<'a>()
+enum TagOrLink<'a> {
+    Tag(Tag<'a>),
+    Link(Link<'a>),
+}
+
+/// Parser for transaction directive (without date) with the directive metadata,
+/// which sits between the header and the postings
+/// Syntax: <transaction_header> <metadata> <postings>
+pub fn parse_transaction_directive_with_metadata<'a>()
+-> impl Parser<'a, &'a str, (DirectiveTransaction<'a>, Metadata<'a>), extra::Err<Rich<'a, char>>> {
+    parse_transaction_header()
+        .then(parse_metadata())
+        .then(parse_postings())
+        .map(|((transaction, metadata), postings)| (transaction.with_postings(postings), metadata))
+}
+
+/// Parser for the transaction up to the postings
+/// Syntax: <flag> [<description>] {<tag> | <link>} {<tags_and_links_line>}
+fn parse_transaction_header<'a>()
 -> impl Parser<'a, &'a str, DirectiveTransaction<'a>, extra::Err<Rich<'a, char>>> {
     let flag = just(KEYWORD_TXN).to(Flag::ASTERISK).or(parse_flag());
 
     flag.then(
-        whitespace()
+        inline_whitespace()
             .at_least(1)
             .ignore_then(parse_transaction_description())
             .or_not(),
     )
-    .then(parse_postings())
-    .map(|((flag, description), postings)| match description {
-        Some(desc) => DirectiveTransaction::new(flag)
-            .with_description(desc)
-            .with_postings(postings),
-        None => DirectiveTransaction::new(flag).with_postings(postings),
+    .then(parse_tags_and_links())
+    .map(|((flag, description), tags_and_links)| {
+        let mut transaction = DirectiveTransaction::new(flag);
+        if let Some(description) = description {
+            transaction = transaction.with_description(description);
+        }
+        for tag_or_link in tags_and_links {
+            match tag_or_link {
+                TagOrLink::Tag(tag) => transaction.add_tag(tag),
+                TagOrLink::Link(link) => transaction.add_link(link),
+            }
+        }
+        transaction
     })
 }
 
+/// Parser for the tags and links after the description, and on indented continuation lines before the postings
+fn parse_tags_and_links<'a>()
+-> impl Parser<'a, &'a str, Vec<TagOrLink<'a>>, extra::Err<Rich<'a, char>>> {
+    let same_line = inline_whitespace()
+        .at_least(1)
+        .ignore_then(parse_tag_or_link())
+        .repeated()
+        .collect::<Vec<_>>();
+    let continuation_line = parse_line_end()
+        .ignore_then(inline_whitespace().at_least(1))
+        .ignore_then(
+            parse_tag_or_link()
+                .separated_by(inline_whitespace().at_least(1))
+                .at_least(1)
+                .collect::<Vec<_>>(),
+        );
+
+    same_line
+        .then(continuation_line.repeated().collect::<Vec<_>>())
+        .map(|(mut tags_and_links, continuation_lines)| {
+            tags_and_links.extend(continuation_lines.into_iter().flatten());
+            tags_and_links
+        })
+}
+
+fn parse_tag_or_link<'a>() -> impl Parser<'a, &'a str, TagOrLink<'a>, extra::Err<Rich<'a, char>>> {
+    choice((
+        parse_tag().map(TagOrLink::Tag),
+        parse_link().map(TagOrLink::Link),
+    ))
+}
+
 fn parse_postings<'a>() -> impl Parser<'a, &'a str, Vec<Posting<'a>>, extra::Err<Rich<'a, char>>> {
-    just('\n')
+    parse_line_end()
         .ignore_then(parse_posting())
         .repeated()
         .at_least(1)
         .collect()
 }
 
-pub fn marshal_transaction_directive(
+pub fn marshal_transaction_directive_with_metadata(
     directive: &DirectiveTransaction,
+    metadata: &Metadata,
     writer: &mut impl Write,
 ) -> std::fmt::Result {
     // Write flag
@@ -54,9 +116,21 @@ pub fn marshal_transaction_directive(
         marshal_transaction_description(description, writer)?;
     }
 
+    // Write tags and links
+    for tag in directive.tags() {
+        write!(writer, " ")?;
+        marshal_tag(tag, writer)?;
+    }
+    for link in directive.links() {
+        write!(writer, " ")?;
+        marshal_link(link, writer)?;
+    }
+
+    marshal_metadata(metadata, writer)?;
+
     // Write postings
     for posting in directive.postings() {
-        write!(writer, "\n")?;
+        writeln!(writer)?;
         marshal_posting(posting, writer)?;
     }
 
@@ -66,8 +140,22 @@ pub fn marshal_transaction_directive(
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn parse_transaction_directive<'a>()
+    -> impl Parser<'a, &'a str, DirectiveTransaction<'a>, extra::Err<Rich<'a, char>>> {
+        parse_transaction_header()
+            .then(parse_postings())
+            .map(|(transaction, postings)| transaction.with_postings(postings))
+    }
+
+    fn marshal_transaction_directive(
+        directive: &DirectiveTransaction,
+        writer: &mut impl Write,
+    ) -> std::fmt::Result {
+        marshal_transaction_directive_with_metadata(directive, &Metadata::new(), writer)
+    }
     use crate::model::{
-        Amount, Flag, account, commodity,
+        Amount, CostSpec, Flag, PostingPrice, account, commodity,
         directive::{PostingAmount, TransactionDescription},
     };
     use rstest::rstest;
@@ -112,6 +200,18 @@ mod tests {
         Some((None, "Mixed postings")),
         3
     )]
+    #[case(
+        "* \"Flight\" #trip-japan ^booking-42\n  Assets:Cash  -900.00 USD\n  Expenses:Travel",
+        Flag::ASTERISK,
+        Some((None, "Flight")),
+        2
+    )]
+    #[case(
+        "* #trip-japan\n  #food ^receipt-7\n  Assets:Cash  -9.00 USD\n  Expenses:Food",
+        Flag::ASTERISK,
+        None,
+        2
+    )]
     fn valid_transaction_template(
         #[case] input: &str,
         #[case] expected_flag: Flag,
@@ -231,12 +331,81 @@ mod tests {
         assert_eq!(output, expected);
     }
 
+    #[test]
+    fn parse_transaction_tags_and_links() {
+        let input = "* \"Sushi\" #trip-japan ^receipt-7 #food\n  #trip-japan ^receipt-8\n  Assets:Cash  -9.00 USD\n  Expenses:Food";
+        let parsed = parse_transaction_directive()
+            .parse(input)
+            .into_result()
+            .unwrap();
+
+        let tags: Vec<&str> = parsed.tags().iter().map(AsRef::as_ref).collect();
+        let links: Vec<&str> = parsed.links().iter().map(AsRef::as_ref).collect();
+        assert_eq!(tags, ["trip-japan", "food"]);
+        assert_eq!(links, ["receipt-7", "receipt-8"]);
+        assert_eq!(parsed.postings().len(), 2);
+    }
+
+    #[test]
+    fn marshal_transaction_tags_and_links() {
+        let transaction = DirectiveTransaction::new(Flag::ASTERISK)
+            .with_description(TransactionDescription::new_without_payee("Sushi"))
+            .with_tag(Tag::new("trip-japan").unwrap())
+            .with_link(Link::new("receipt-7").unwrap())
+            .with_posting(Posting::new_without_amount(account!(Expenses:Food)));
+
+        let mut output = String::new();
+        marshal_transaction_directive(&transaction, &mut output).unwrap();
+
+        assert_eq!(
+            output,
+            "* \"Sushi\" #trip-japan ^receipt-7\n  Expenses:Food"
+        );
+    }
+
+    #[test]
+    fn parse_and_marshal_transaction_with_metadata() {
+        let input = "* \"Sushi\" #food\n  receipt: \"scan-42.pdf\"\n  Assets:Cash  -9.00 USD\n    exchange: 2024-03-01\n  Expenses:Food";
+        let (transaction, metadata) = parse_transaction_directive_with_metadata()
+            .parse(input)
+            .into_result()
+            .unwrap();
+
+        assert_eq!(
+            metadata.get("receipt").and_then(|v| v.as_str()),
+            Some("scan-42.pdf")
+        );
+        assert_eq!(transaction.postings().len(), 2);
+        assert!(
+            transaction.postings()[0]
+                .metadata()
+                .contains_key("exchange")
+        );
+        assert!(transaction.postings()[1].metadata().is_empty());
+
+        let mut marshalled = String::new();
+        marshal_transaction_directive_with_metadata(&transaction, &metadata, &mut marshalled)
+            .unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[test]
+    fn parse_transaction_directive_rejects_metadata() {
+        let input =
+            "* \"Sushi\"\n  receipt: \"scan-42.pdf\"\n  Assets:Cash  -9.00 USD\n  Expenses:Food";
+        let result = parse_transaction_directive().parse(input);
+
+        assert!(!result.has_output());
+    }
+
     #[rstest]
     #[case("*")] // Missing postings
     #[case("! \"payee\"\n")] // Missing postings after newline
     #[case("x")] // Invalid flag
     #[case("* \"unterminated quote")] // Unterminated quote
     #[case("* \"payee\" \"narration\"")] // Missing postings
+    #[case("* \"narration\"#tag\n  Assets:Cash")] // Missing space before tag
+    #[case("* \"narration\" ^\n  Assets:Cash")] // Empty link
     fn parse_transaction_invalid(#[case] input: &str) {
         let result = parse_transaction_directive().parse(input);
         assert!(!result.has_output(), "Should fail to parse: {}", input);
@@ -308,7 +477,7 @@ mod tests {
 
         // Buy 10 shares of AAPL at $150 per share
         let stock_amount = Amount::new(dec!(10), stock_commodity);
-        let cost_amount = Amount::new(dec!(150.00), usd_commodity.clone());
+        let cost_amount = CostSpec::per_unit(dec!(150.00), usd_commodity.clone());
         let cash_amount = Amount::new(dec!(-1500.00), usd_commodity);
 
         let stock_posting = Posting::new(
@@ -344,7 +513,8 @@ mod tests {
 
         // Sell 5 shares of AAPL at current price of $155 per share
         let stock_amount = Amount::new(dec!(-5), stock_commodity);
-        let price_amount = Amount::new(dec!(155.00), usd_commodity.clone());
+        let price_amount =
+            PostingPrice::PerUnit(Amount::new(dec!(155.00), usd_commodity.clone()).into());
         let cash_amount = Amount::new(dec!(775.00), usd_commodity);
 
         let stock_posting = Posting::new(
@@ -380,8 +550,9 @@ mod tests {
 
         // Complex transaction with both cost and price
         let stock_amount = Amount::new(dec!(10), stock_commodity);
-        let cost_amount = Amount::new(dec!(150.00), usd_commodity.clone());
-        let price_amount = Amount::new(dec!(155.00), usd_commodity.clone());
+        let cost_amount = CostSpec::per_unit(dec!(150.00), usd_commodity.clone());
+        let price_amount =
+            PostingPrice::PerUnit(Amount::new(dec!(155.00), usd_commodity.clone()).into());
         let cash_amount = Amount::new(dec!(-1500.00), usd_commodity);
 
         let stock_posting = Posting::new(
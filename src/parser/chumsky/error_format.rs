@@ -1,5 +1,7 @@
 use ariadne::{Report, ReportKind};
-use chumsky::{ParseResult, error::Rich};
+use chumsky::{ParseResult, error::Rich, span::SimpleSpan};
+
+use crate::model::{FileId, SourceSpan};
 
 pub trait ParseResultExt {
     fn get_formatted_errors(&self) -> Vec<Report<'_>>;
@@ -8,22 +10,34 @@ pub trait ParseResultExt {
 impl<T> ParseResultExt for ParseResult<T, Rich<'_, char>> {
     fn get_formatted_errors(&self) -> Vec<Report<'_>> {
         self.errors()
-            .map(|e| crate::parser::chumsky::error_format::format_error(&e))
+            .map(|e| crate::parser::chumsky::error_format::format_error(e))
             .collect()
     }
 }
 
 pub fn format_error<'a>(error: &Rich<'a, char>) -> Report<'a> {
-    let mut report = Report::build(ReportKind::Error, error.span().into_range())
+    format_error_with_span(error, SimpleSpan::into_range)
+}
+
+/// Format a parse error of the given file, for reports covering multiple files
+pub fn format_error_in_file<'a>(error: &Rich<'a, char>, file_id: FileId) -> Report<'a, SourceSpan> {
+    format_error_with_span(error, |span| SourceSpan::new(file_id, span.into_range()))
+}
+
+fn format_error_with_span<'a, S: ariadne::Span + Clone>(
+    error: &Rich<'a, char>,
+    to_span: impl Fn(SimpleSpan) -> S,
+) -> Report<'a, S> {
+    let mut report = Report::build(ReportKind::Error, to_span(*error.span()))
         .with_message(error.to_string())
         .with_label(
-            ariadne::Label::new(error.span().into_range())
+            ariadne::Label::new(to_span(*error.span()))
                 .with_message(error.reason().to_string())
                 .with_color(ariadne::Color::Red),
         );
     for (expected_pattern, span) in error.contexts() {
         report = report.with_label(
-            ariadne::Label::new(span.into_range())
+            ariadne::Label::new(to_span(*span))
                 .with_message(expected_pattern.to_string())
                 .with_color(ariadne::Color::Yellow),
         );
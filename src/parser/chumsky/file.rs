@@ -0,0 +1,517 @@
+use chumsky::{input::Emitter, prelude::*, text::inline_whitespace};
+use std::fmt::Write;
+
+use crate::{
+    model::{Directive, DirectiveVariant, Ledger, Pragma, PragmaPushMeta, Tag},
+    parser::chumsky::{
+        comment::parse_comment,
+        directive::{marshal_directive, parse_directive},
+        pragma::{marshal_pragma, parse_pragma},
+    },
+};
+
+enum Entry<'a> {
+    Directive(Directive<'a>),
+    Pragma(Pragma<'a>),
+}
+
+/// Parser for a complete ledger file
+/// Syntax: { <directive> | <pragma> | <comment> | <section_header> } separated by line breaks
+///
+/// Directives and pragmas may appear in any order and may be followed by a comment.
+/// A malformed entry, e.g. one with trailing text or a malformed posting, is reported as an error
+/// and skipped up to the next line starting with a date or keyword, so that the remaining entries
+/// are still parsed.
+/// `pushtag`/`poptag` and `pushmeta`/`popmeta` are applied to the directives between them.
+/// Unbalanced or mismatched pops are reported as errors at the offending pragma.
+pub fn parse_file<'a>() -> impl Parser<'a, &'a str, Ledger<'a>, extra::Err<Rich<'a, char>>> {
+    let entry = choice((
+        parse_pragma().map(Entry::Pragma),
+        parse_directive().map(Entry::Directive),
+    ))
+    .map_with(|entry, e| Some((entry, e.span())))
+    .then_ignore(end_of_entry());
+    let ignored_line = parse_comment().or(parse_section_header()).map(|()| None);
+    let line_break = inline_whitespace()
+        .then(parse_comment().or_not())
+        .then(just('\n'))
+        .repeated()
+        .at_least(1);
+
+    entry
+        .or(ignored_line)
+        .recover_with(via_parser(skip_malformed_entry().map(|()| None)))
+        .separated_by(line_break)
+        .allow_leading()
+        .allow_trailing()
+        .collect::<Vec<_>>()
+        .then_ignore(inline_whitespace())
+        .validate(|entries, _, emitter| build_ledger(entries.into_iter().flatten(), emitter))
+}
+
+/// Lookahead for the end of an entry: the rest of its line may only hold a comment, and the
+/// next line must not be an indented line the entry didn't consume, e.g. a malformed posting.
+/// Otherwise the entry is malformed as a whole and skipped by [skip_malformed_entry].
+fn end_of_entry<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> {
+    let unconsumed_line = one_of(" \t")
+        .repeated()
+        .at_least(1)
+        .then(none_of(" \t\n;"))
+        .then(none_of('\n').repeated())
+        .to_slice();
+    let line_end =
+        just('\n').ignore_then(unconsumed_line.or_not().try_map(|line, span| match line {
+            Some(_) => Err(Rich::custom(span, "Malformed posting or metadata line")),
+            None => Ok(()),
+        }));
+
+    inline_whitespace()
+        .then(parse_comment().or_not())
+        .then(line_end.or(end()))
+        .ignored()
+        .rewind()
+}
+
+/// Recovery parser skipping a malformed entry, i.e. the rest of its first line and all following
+/// lines up to the next line starting with a date or keyword
+fn skip_malformed_entry<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> {
+    let rest_of_line = none_of('\n').repeated();
+    let first_line = inline_whitespace()
+        .then(none_of(" \t\n"))
+        .then(rest_of_line);
+    let entry_start = any().filter(|c: &char| c.is_ascii_digit() || c.is_ascii_lowercase());
+    let continuation_line = just('\n').then(entry_start.not()).then(rest_of_line);
+
+    first_line.then(continuation_line.repeated()).ignored()
+}
+
+/// Parser for org-mode or markdown section headers, which are ignored
+/// Syntax: ("*" | "#") <any text>, at the beginning of a line
+fn parse_section_header<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Rich<'a, char>>> {
+    one_of("*#").then(none_of('\n').repeated()).ignored()
+}
+
+/// Marshaller for a complete ledger file, writing the pragmas followed by the directives.
+/// Directives are separated by blank lines.
+pub fn marshal_file(ledger: &Ledger, writer: &mut impl Write) -> std::fmt::Result {
+    for pragma in ledger.pragmas() {
+        marshal_pragma(pragma, writer)?;
+        writeln!(writer)?;
+    }
+    for (index, directive) in ledger.directives().iter().enumerate() {
+        if index > 0 || !ledger.pragmas().is_empty() {
+            writeln!(writer)?;
+        }
+        marshal_directive(directive, writer)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct Scopes<'a> {
+    tags: Vec<(Tag<'a>, SimpleSpan)>,
+    metadata: Vec<(PragmaPushMeta<'a>, SimpleSpan)>,
+}
+
+fn build_ledger<'a>(
+    entries: impl IntoIterator<Item = (Entry<'a>, SimpleSpan)>,
+    emitter: &mut Emitter<Rich<'a, char>>,
+) -> Ledger<'a> {
+    let mut ledger = Ledger::new();
+    let mut scopes = Scopes::default();
+
+    for (entry, span) in entries {
+        match entry {
+            Entry::Directive(directive) => ledger.add_directive(scopes.apply(directive)),
+            Entry::Pragma(Pragma::PushTag(tag)) => scopes.tags.push((tag, span)),
+            Entry::Pragma(Pragma::PopTag(tag)) => {
+                match scopes.tags.iter().rposition(|(pushed, _)| *pushed == tag) {
+                    Some(index) => {
+                        scopes.tags.remove(index);
+                    }
+                    None => emitter.emit(Rich::custom(
+                        span,
+                        format!("Attempting to pop tag #{tag} that wasn't pushed"),
+                    )),
+                }
+            }
+            Entry::Pragma(Pragma::PushMeta(push_meta)) => scopes.metadata.push((push_meta, span)),
+            Entry::Pragma(Pragma::PopMeta(key)) => {
+                match scopes
+                    .metadata
+                    .iter()
+                    .rposition(|(pushed, _)| *pushed.key() == key)
+                {
+                    Some(index) => {
+                        scopes.metadata.remove(index);
+                    }
+                    None => emitter.emit(Rich::custom(
+                        span,
+                        format!("Attempting to pop metadata key {key} that wasn't pushed"),
+                    )),
+                }
+            }
+            Entry::Pragma(pragma) => ledger.add_pragma(pragma),
+        }
+    }
+
+    for (tag, span) in scopes.tags {
+        emitter.emit(Rich::custom(
+            span,
+            format!("Unbalanced pushed tag #{tag}, missing poptag"),
+        ));
+    }
+    for (push_meta, span) in scopes.metadata {
+        emitter.emit(Rich::custom(
+            span,
+            format!(
+                "Unbalanced pushed metadata key {}, missing popmeta",
+                push_meta.key()
+            ),
+        ));
+    }
+
+    ledger
+}
+
+impl<'a> Scopes<'a> {
+    /// Add pushed tags to transactions and pushed metadata to all directives.
+    /// Metadata given on the directive itself takes precedence, and later pushes win over earlier ones.
+    fn apply(&self, mut directive: Directive<'a>) -> Directive<'a> {
+        if let DirectiveVariant::Transaction(transaction) = directive.content_mut() {
+            for (tag, _) in &self.tags {
+                transaction.add_tag(tag.clone());
+            }
+        }
+        for (push_meta, _) in self.metadata.iter().rev() {
+            if !directive.metadata().contains_key(push_meta.key().as_ref()) {
+                directive
+                    .metadata_mut()
+                    .insert(push_meta.key().clone(), push_meta.value().clone());
+            }
+        }
+        directive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{MetadataValue, PragmaOption};
+    use crate::parser::chumsky::ParseResultExt as _;
+    use rstest::rstest;
+
+    fn tags<'a>(directive: &'a Directive) -> Vec<&'a str> {
+        directive
+            .as_transaction()
+            .unwrap()
+            .tags()
+            .iter()
+            .map(AsRef::as_ref)
+            .collect()
+    }
+
+    #[test]
+    fn parse_file_directives_and_pragmas() {
+        let input = "option \"title\" \"Our Ledger\"\n\
+                     \n\
+                     2024-01-01 open Assets:Cash\n\
+                     2024-01-02 *\n  Assets:Cash  -20.00 USD\n  Expenses:Coffee\n   \n\
+                     include \"2023.beancount\"\n";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+
+        assert_eq!(ledger.directives().len(), 2);
+        assert!(ledger.directives()[0].as_open().is_some());
+        assert!(ledger.directives()[1].as_transaction().is_some());
+        assert_eq!(
+            ledger.pragmas()[0],
+            Pragma::Option(PragmaOption::new("title", "Our Ledger"))
+        );
+        assert!(ledger.pragmas()[1].as_include().is_some());
+    }
+
+    #[test]
+    fn parse_file_with_comments_and_headers() {
+        let input = "; Personal ledger\n\
+                     * Accounts\n\
+                     ** Cash\n\
+                     2024-01-01 open Assets:Cash ; opened at the bank\n\
+                     ;2024-01-01 open Assets:Ignored\n\
+                     # Markdown section\n\
+                     2024-01-02 * \"Coffee\" ; trailing comment\n\
+                     \x20 ; comment before postings\n\
+                     \x20 Assets:Cash  -2.50 USD ; cash\n\
+                     \x20 ; comment between postings\n\
+                     \x20 Expenses:Coffee\n\
+                     \n\
+                     option \"title\" \"Ledger\" ; comment after pragma\n\
+                     ; comment at the end";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+
+        assert_eq!(ledger.directives().len(), 2);
+        assert!(ledger.directives()[0].as_open().is_some());
+        let transaction = ledger.directives()[1].as_transaction().unwrap();
+        assert_eq!(transaction.postings().len(), 2);
+        assert_eq!(ledger.pragmas().len(), 1);
+    }
+
+    #[test]
+    fn parse_file_attaches_spans() {
+        let input = "; comment\n\
+                     2024-01-01 open Assets:Cash\n\
+                     2024-01-02 * \"Coffee\"\n\
+                     \x20 Assets:Cash  -2.50 USD\n\
+                     \x20 Expenses:Coffee\n";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+
+        let open_span = ledger.directives()[0].span().unwrap();
+        assert_eq!(
+            &input[open_span.byte_range()],
+            "2024-01-01 open Assets:Cash"
+        );
+        assert_eq!(open_span.line_column(input), (2, 1));
+        let transaction = ledger.directives()[1].as_transaction().unwrap();
+        let posting_span = transaction.postings()[0].span().unwrap();
+        assert_eq!(&input[posting_span.byte_range()], "Assets:Cash  -2.50 USD");
+        assert_eq!(posting_span.line_column(input), (4, 3));
+    }
+
+    #[test]
+    fn parse_file_directives_in_any_order() {
+        let input = "2024-02-01 close Assets:Cash\n\
+                     option \"operating_currency\" \"USD\"\n\
+                     2024-01-01 open Assets:Cash\n\
+                     2024-01-15 balance Assets:Cash 0 USD";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+
+        let dates: Vec<String> = ledger
+            .directives()
+            .iter()
+            .map(|directive| directive.date().to_string())
+            .collect();
+        assert_eq!(dates, ["2024-02-01", "2024-01-01", "2024-01-15"]);
+    }
+
+    #[test]
+    fn parse_file_reports_errors() {
+        let result = parse_file().parse("2024-01-01 open assets:cash\n");
+
+        assert_eq!(result.output().unwrap().directives().len(), 0);
+        assert_eq!(result.errors().count(), 1);
+    }
+
+    #[test]
+    fn parse_file_recovers_from_malformed_entries() {
+        let input = "2024-01-01 open Assets:Cash\n\
+                     2024-01-02 * \"Coffee\" invalid\n\
+                     \x20 Assets:Cash  -2.50 USD\n\
+                     \x20 Expenses:Coffee\n\
+                     \n\
+                     2024-01-03 open Expenses:Coffee\n\
+                     optoin \"title\" \"Ledger\"\n\
+                     \x20 indented garbage\n\
+                     2024-01-04 close Assets:Cash\n\
+                     2024-01-05 balance Assets:Cash abc\n";
+        let result = parse_file().parse(input);
+
+        let dates: Vec<String> = result
+            .output()
+            .unwrap()
+            .directives()
+            .iter()
+            .map(|directive| directive.date().to_string())
+            .collect();
+        assert_eq!(dates, ["2024-01-01", "2024-01-03", "2024-01-04"]);
+        let error_lines: Vec<usize> = result
+            .errors()
+            .map(|error| input[..error.span().start].matches('\n').count() + 1)
+            .collect();
+        assert_eq!(error_lines, [2, 7, 10]);
+        assert_eq!(result.get_formatted_errors().len(), 3);
+    }
+
+    #[rstest]
+    #[case("  Assets:Cash  10.00 usd", "found 'u'")]
+    #[case("  Assets:Cash  {}", "Commodity")]
+    #[case("  Assets:Cash  (1/0) USD", "Division by zero")]
+    #[case("  Assets:Cash  -2.50 USD garbage", "found 'g'")]
+    fn parse_file_recovers_from_malformed_postings(#[case] posting: &str, #[case] message: &str) {
+        let input = format!(
+            "2024-01-01 open Assets:Cash\n\
+             2024-01-02 * \"Coffee\"\n\
+             \x20 Expenses:Coffee  2.50 USD\n\
+             {posting}\n\
+             \x20 Expenses:Tip\n\
+             2024-01-03 open Expenses:Coffee\n"
+        );
+        let result = parse_file().parse(&input);
+
+        // the whole transaction is skipped, not just the malformed posting
+        let dates: Vec<String> = result
+            .output()
+            .unwrap()
+            .directives()
+            .iter()
+            .map(|directive| directive.date().to_string())
+            .collect();
+        assert_eq!(dates, ["2024-01-01", "2024-01-03"]);
+        let errors: Vec<_> = result.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(input[..errors[0].span().start].matches('\n').count() + 1, 4);
+        assert!(
+            errors[0].to_string().contains(message),
+            "unexpected error: {}",
+            errors[0]
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "2024-01-02 open Assets:Bank \"BADBOOK\"",
+        "Unknown booking method: BADBOOK"
+    )]
+    #[case("2024-01-02 balance Assets:Bank (1/0) USD", "Division by zero")]
+    #[case(
+        "2024-01-02 open Assets:Bank USD ; fine\n  invalid: metadata: line",
+        "Malformed posting or metadata line"
+    )]
+    fn parse_file_recovers_from_malformed_directive_bodies(
+        #[case] entry: &str,
+        #[case] message: &str,
+    ) {
+        let input = format!("2024-01-01 open Assets:Cash\n{entry}\n2024-01-03 open Assets:Bank\n");
+        let result = parse_file().parse(&input);
+
+        let dates: Vec<String> = result
+            .output()
+            .unwrap()
+            .directives()
+            .iter()
+            .map(|directive| directive.date().to_string())
+            .collect();
+        assert_eq!(dates, ["2024-01-01", "2024-01-03"]);
+        let errors: Vec<_> = result.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].to_string().contains(message),
+            "unexpected error: {}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn marshal_and_parse_file() {
+        let input = "option \"title\" \"Ledger\"\n\
+                     2024-01-01 open Assets:Cash\n\
+                     2024-01-02 * \"Coffee\"\n  Assets:Cash  -2.50 USD\n  Expenses:Coffee\n";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_file(&ledger, &mut marshalled).unwrap();
+        assert_eq!(
+            marshalled,
+            "option \"title\" \"Ledger\"\n\
+             \n\
+             2024-01-01 open Assets:Cash\n\
+             \n\
+             2024-01-02 * \"Coffee\"\n  Assets:Cash  -2.50 USD\n  Expenses:Coffee\n"
+        );
+
+        let reparsed = parse_file().parse(&marshalled).into_result().unwrap();
+        assert_eq!(reparsed, ledger);
+    }
+
+    #[test]
+    fn parse_file_empty() {
+        let ledger = parse_file().parse("\n  \n").into_result().unwrap();
+
+        assert_eq!(ledger, Ledger::new());
+    }
+
+    #[test]
+    fn pushtag_applies_to_transactions_in_scope() {
+        let input = "2024-01-01 *\n  Assets:Cash  -1 USD\n  Expenses:Food\n\
+                     pushtag #trip-japan\n\
+                     pushtag #food\n\
+                     2024-01-02 *\n  Assets:Cash  -2 USD\n  Expenses:Food\n\
+                     poptag #food\n\
+                     2024-01-03 *\n  Assets:Cash  -3 USD\n  Expenses:Food\n\
+                     2024-01-03 open Assets:Yen\n\
+                     poptag #trip-japan\n\
+                     2024-01-04 *\n  Assets:Cash  -4 USD\n  Expenses:Food";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+        let directives = ledger.directives();
+
+        assert_eq!(tags(&directives[0]), Vec::<&str>::new());
+        assert_eq!(tags(&directives[1]), ["trip-japan", "food"]);
+        assert_eq!(tags(&directives[2]), ["trip-japan"]);
+        assert!(directives[3].as_open().is_some());
+        assert_eq!(tags(&directives[4]), Vec::<&str>::new());
+        assert!(ledger.pragmas().is_empty());
+    }
+
+    #[test]
+    fn pushmeta_applies_to_all_directives_in_scope() {
+        let input = "pushmeta location: \"Berlin\"\n\
+                     2024-01-01 open Assets:Cash\n\
+                     pushmeta location: \"Tokyo\"\n\
+                     2024-01-02 note Assets:Cash \"Exchanged money\"\n\
+                     popmeta location:\n\
+                     2024-01-03 close Assets:Cash\n\
+                     popmeta location:\n\
+                     2024-01-04 open Assets:Yen";
+        let ledger = parse_file().parse(input).into_result().unwrap();
+        let location = |index: usize| {
+            ledger.directives()[index]
+                .metadata()
+                .get("location")
+                .cloned()
+        };
+
+        assert_eq!(location(0), Some(MetadataValue::String("Berlin".into())));
+        assert_eq!(location(1), Some(MetadataValue::String("Tokyo".into())));
+        assert_eq!(location(2), Some(MetadataValue::String("Berlin".into())));
+        assert_eq!(location(3), None);
+    }
+
+    #[test]
+    fn poptag_without_pushtag() {
+        let input = "2024-01-01 open Assets:Cash\npoptag #trip";
+        let result = parse_file().parse(input);
+
+        let errors: Vec<_> = result.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span().into_range(), 28..40);
+        assert!(errors[0].to_string().contains("#trip"));
+    }
+
+    #[test]
+    fn poptag_mismatched() {
+        let input = "pushtag #trip\npoptag #food\npoptag #trip";
+        let result = parse_file().parse(input);
+
+        let errors: Vec<_> = result.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span().into_range(), 14..26);
+    }
+
+    #[test]
+    fn unbalanced_pushes() {
+        let input = "pushtag #trip\npushmeta location: \"Tokyo\"\n2024-01-01 open Assets:Cash";
+        let result = parse_file().parse(input);
+
+        let errors: Vec<_> = result.errors().collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span().into_range(), 0..13);
+        assert!(errors[0].to_string().contains("missing poptag"));
+        assert!(errors[1].to_string().contains("missing popmeta"));
+    }
+
+    #[test]
+    fn popmeta_without_pushmeta() {
+        let result = parse_file().parse("popmeta location:");
+
+        assert_eq!(result.errors().count(), 1);
+    }
+}
@@ -0,0 +1,71 @@
+use chumsky::prelude::*;
+use std::fmt::Write;
+
+use crate::{
+    model::IncompleteAmount,
+    parser::chumsky::{
+        amount::{marshal_amount, parse_amount},
+        commodity::{marshal_commodity, parse_commodity},
+        decimal::{marshal_decimal, parse_decimal},
+    },
+};
+
+/// Parser for an amount that may be missing its number or its commodity
+/// Syntax: <amount> | <number> | <commodity>
+pub fn parse_incomplete_amount<'a>()
+-> impl Parser<'a, &'a str, IncompleteAmount<'a>, extra::Err<Rich<'a, char>>> {
+    choice((
+        parse_amount().map(IncompleteAmount::Complete),
+        parse_decimal().map(IncompleteAmount::MissingCommodity),
+        parse_commodity().map(IncompleteAmount::MissingNumber),
+    ))
+}
+
+pub fn marshal_incomplete_amount(
+    amount: &IncompleteAmount,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    match amount {
+        IncompleteAmount::Complete(amount) => marshal_amount(amount, writer),
+        IncompleteAmount::MissingNumber(commodity) => marshal_commodity(commodity, writer),
+        IncompleteAmount::MissingCommodity(number) => marshal_decimal(number, writer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, commodity};
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    #[rstest]
+    #[case("10 USD", IncompleteAmount::Complete(Amount::new(dec!(10), commodity!(USD))))]
+    #[case("-2.50 EUR", IncompleteAmount::Complete(Amount::new(dec!(-2.50), commodity!(EUR))))]
+    #[case("USD", IncompleteAmount::MissingNumber(commodity!(USD)))]
+    #[case("10", IncompleteAmount::MissingCommodity(dec!(10)))]
+    #[case("-2.50", IncompleteAmount::MissingCommodity(dec!(-2.50)))]
+    fn parse_and_marshal_incomplete_amount(
+        #[case] input: &str,
+        #[case] expected: IncompleteAmount,
+    ) {
+        let parsed = parse_incomplete_amount()
+            .parse(input)
+            .into_result()
+            .unwrap();
+        assert_eq!(parsed, expected);
+
+        let mut marshalled = String::new();
+        marshal_incomplete_amount(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[rstest]
+    #[case("")] // Empty
+    #[case("usd")] // Invalid commodity
+    #[case("10 usd")] // Invalid commodity after number
+    fn parse_incomplete_amount_invalid(#[case] input: &str) {
+        let result = parse_incomplete_amount().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
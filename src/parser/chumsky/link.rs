@@ -0,0 +1,52 @@
+use chumsky::prelude::*;
+use std::fmt::Write;
+
+use crate::model::Link;
+
+/// Parser for links
+/// Syntax: "^" <link_name>
+pub fn parse_link<'a>() -> impl Parser<'a, &'a str, Link<'a>, extra::Err<Rich<'a, char>>> {
+    just('^').ignore_then(
+        any()
+            .filter(|c: &char| !c.is_whitespace())
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .try_map(|slice: &'a str, span| {
+                Link::try_from(slice).map_err(|e| Rich::custom(span, format!("{}", e)))
+            }),
+    )
+}
+
+pub fn marshal_link(link: &Link, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "^{}", link)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("^invoice-2024-03", "invoice-2024-03")]
+    #[case("^receipts/2024.03_01", "receipts/2024.03_01")]
+    fn parse_and_marshal_link(#[case] input: &str, #[case] expected: &str) {
+        let parsed = parse_link().parse(input).into_result().unwrap();
+        assert_eq!(parsed.as_ref(), expected);
+
+        let mut marshalled = String::new();
+        marshal_link(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("^")]
+    #[case("invoice")]
+    #[case("#invoice")]
+    #[case("^invoice:42")]
+    fn parse_link_invalid(#[case] input: &str) {
+        let result = parse_link().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
@@ -0,0 +1,162 @@
+use chumsky::{prelude::*, text::inline_whitespace};
+use std::fmt::Write;
+
+use crate::{
+    model::Metadata,
+    parser::chumsky::{
+        comment::parse_line_end,
+        metadata::{
+            metadata_key::{marshal_metadata_key, parse_metadata_key},
+            metadata_value::{marshal_metadata_value, parse_metadata_value},
+        },
+    },
+};
+
+/// Parser for the metadata lines following a directive
+/// Syntax: { <line_end> <indentation> <key> ":" <value> }
+pub fn parse_metadata<'a>() -> impl Parser<'a, &'a str, Metadata<'a>, extra::Err<Rich<'a, char>>> {
+    let entry = parse_line_end()
+        .ignore_then(inline_whitespace().at_least(1))
+        .ignore_then(parse_metadata_key())
+        .then_ignore(just(':'))
+        .then_ignore(inline_whitespace())
+        .then(parse_metadata_value());
+
+    entry
+        .map_with(|entry, e| (entry, e.span()))
+        .repeated()
+        .collect::<Vec<_>>()
+        .try_map(|entries, _span| {
+            let mut metadata = Metadata::new();
+            for ((key, value), span) in entries {
+                if metadata.contains_key(key.as_ref()) {
+                    return Err(Rich::custom(
+                        span,
+                        format!("Duplicate metadata key: {}", key),
+                    ));
+                }
+                metadata.insert(key, value);
+            }
+            Ok(metadata)
+        })
+}
+
+const DIRECTIVE_METADATA_INDENT: usize = 2;
+const POSTING_METADATA_INDENT: usize = 4;
+
+/// Marshaller for directive metadata, writing each entry on its own indented line
+pub fn marshal_metadata(metadata: &Metadata, writer: &mut impl Write) -> std::fmt::Result {
+    marshal_metadata_with_indent(metadata, DIRECTIVE_METADATA_INDENT, writer)
+}
+
+/// Marshaller for posting metadata, indented deeper than the posting itself
+pub fn marshal_posting_metadata(metadata: &Metadata, writer: &mut impl Write) -> std::fmt::Result {
+    marshal_metadata_with_indent(metadata, POSTING_METADATA_INDENT, writer)
+}
+
+fn marshal_metadata_with_indent(
+    metadata: &Metadata,
+    indent: usize,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    for (key, value) in metadata.iter() {
+        write!(writer, "\n{:indent$}", "")?;
+        marshal_metadata_key(key, writer)?;
+        write!(writer, ": ")?;
+        marshal_metadata_value(value, writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{MetadataKey, MetadataValue, commodity};
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use rust_decimal_macros::dec;
+
+    fn entry<'a>(key: &'a str, value: MetadataValue<'a>) -> (MetadataKey<'a>, MetadataValue<'a>) {
+        (MetadataKey::new(key).unwrap(), value)
+    }
+
+    #[template]
+    #[rstest]
+    #[case("", vec![])]
+    #[case("\n  name: \"US Dollar\"", vec![entry("name", MetadataValue::String("US Dollar".into()))])]
+    #[case(
+        "\n  name: \"US Dollar\"\n  precision: 2",
+        vec![
+            entry("name", MetadataValue::String("US Dollar".into())),
+            entry("precision", MetadataValue::Number(dec!(2))),
+        ]
+    )]
+    #[case(
+        "\n    quote: USD\n\texport:FALSE",
+        vec![
+            entry("quote", MetadataValue::Commodity(commodity!(USD))),
+            entry("export", MetadataValue::Bool(false)),
+        ]
+    )]
+    fn valid_metadata_template(
+        #[case] input: &str,
+        #[case] expected: Vec<(MetadataKey, MetadataValue)>,
+    ) {
+    }
+
+    #[apply(valid_metadata_template)]
+    fn parse_metadata_valid(
+        #[case] input: &str,
+        #[case] expected: Vec<(MetadataKey, MetadataValue)>,
+    ) {
+        let result = parse_metadata().parse(input);
+        assert!(result.has_output(), "Failed to parse metadata: {}", input);
+        let parsed = result.into_result().unwrap();
+
+        let expected: Metadata = expected.into_iter().collect();
+        assert_eq!(parsed, expected);
+    }
+
+    #[apply(valid_metadata_template)]
+    fn marshal_and_parse_metadata(
+        #[case] input: &str,
+        #[case] _expected: Vec<(MetadataKey, MetadataValue)>,
+    ) {
+        let original = parse_metadata().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_metadata(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_metadata().parse(&marshalled).into_result().unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("\nname: \"US Dollar\"")] // Missing indentation
+    #[case("\n  name \"US Dollar\"")] // Missing colon
+    #[case("\n  Name: \"US Dollar\"")] // Invalid key
+    #[case("\n  name:")] // Missing value
+    #[case("\n  name: 1\n  name: 2")] // Duplicate key
+    fn parse_metadata_invalid(#[case] input: &str) {
+        let result = parse_metadata().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_metadata_basic() {
+        let metadata: Metadata = vec![
+            entry("name", MetadataValue::String("US Dollar".into())),
+            entry("precision", MetadataValue::Number(dec!(2))),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut output = String::new();
+        marshal_metadata(&metadata, &mut output).unwrap();
+        assert_eq!(output, "\n  name: \"US Dollar\"\n  precision: 2");
+
+        let mut output = String::new();
+        marshal_posting_metadata(&metadata, &mut output).unwrap();
+        assert_eq!(output, "\n    name: \"US Dollar\"\n    precision: 2");
+    }
+}
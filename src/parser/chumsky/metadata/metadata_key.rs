@@ -0,0 +1,71 @@
+use chumsky::prelude::*;
+use std::fmt::Write;
+
+use crate::model::MetadataKey;
+
+pub fn parse_metadata_key<'a>()
+-> impl Parser<'a, &'a str, MetadataKey<'a>, extra::Err<Rich<'a, char>>> {
+    any()
+        .filter(|c: &char| !c.is_whitespace() && *c != ':')
+        .repeated()
+        .to_slice()
+        .try_map(|slice: &'a str, span| {
+            MetadataKey::try_from(slice)
+                .map_err(|e| chumsky::error::Rich::custom(span, format!("{}", e)))
+        })
+}
+
+pub fn marshal_metadata_key(key: &MetadataKey, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{}", key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("name")]
+    #[case("precision")]
+    #[case("export-name")]
+    #[case("price_source")]
+    #[case("isin2")]
+    #[case("camelCase")]
+    #[case("a")]
+    fn valid_metadata_key_template(#[case] input: &str) {}
+
+    #[apply(valid_metadata_key_template)]
+    fn parse_metadata_key_valid(#[case] input: &str) {
+        let result = parse_metadata_key().parse(input);
+        assert!(result.has_output(), "Failed to parse key: {}", input);
+        assert_eq!(result.into_result().unwrap().as_ref(), input);
+    }
+
+    #[apply(valid_metadata_key_template)]
+    fn marshal_and_parse_metadata_key(#[case] input: &str) {
+        let original = parse_metadata_key().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_metadata_key(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_metadata_key()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("")] // Empty
+    #[case("Name")] // Uppercase start
+    #[case("1name")] // Digit start
+    #[case("na.me")] // Invalid character
+    #[case("name:")] // Colon is not part of the key
+    #[case("na me")] // Space
+    fn parse_metadata_key_invalid(#[case] input: &str) {
+        let result = parse_metadata_key().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
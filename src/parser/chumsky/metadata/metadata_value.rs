@@ -0,0 +1,107 @@
+use chumsky::{prelude::*, text::keyword};
+use std::fmt::Write;
+
+use crate::{
+    model::MetadataValue,
+    parser::chumsky::{
+        account::{marshal_account, parse_account},
+        amount::{marshal_amount, parse_amount},
+        commodity::{marshal_commodity, parse_commodity},
+        date::{marshal_date, parse_date},
+        decimal::{marshal_decimal, parse_decimal},
+        quoted_string::{marshal_quoted_string, parse_quoted_string},
+        tag::{marshal_tag, parse_tag},
+    },
+};
+
+const KEYWORD_TRUE: &str = "TRUE";
+const KEYWORD_FALSE: &str = "FALSE";
+
+/// Parser for metadata values
+/// Syntax: <quoted_string> | TRUE | FALSE | <date> | <amount> | <number> | <account> | <commodity> | <tag>
+pub fn parse_metadata_value<'a>()
+-> impl Parser<'a, &'a str, MetadataValue<'a>, extra::Err<Rich<'a, char>>> {
+    choice((
+        parse_quoted_string().map(MetadataValue::String),
+        keyword(KEYWORD_TRUE).to(MetadataValue::Bool(true)),
+        keyword(KEYWORD_FALSE).to(MetadataValue::Bool(false)),
+        parse_date().map(MetadataValue::Date),
+        parse_amount().map(MetadataValue::Amount),
+        parse_decimal().map(MetadataValue::Number),
+        parse_account().map(MetadataValue::Account),
+        parse_commodity().map(MetadataValue::Commodity),
+        parse_tag().map(MetadataValue::Tag),
+    ))
+}
+
+pub fn marshal_metadata_value(value: &MetadataValue, writer: &mut impl Write) -> std::fmt::Result {
+    match value {
+        MetadataValue::String(string) => marshal_quoted_string(string, writer),
+        MetadataValue::Number(number) => marshal_decimal(number, writer),
+        MetadataValue::Amount(amount) => marshal_amount(amount, writer),
+        MetadataValue::Date(date) => marshal_date(date, writer),
+        MetadataValue::Account(account) => marshal_account(account.clone(), writer),
+        MetadataValue::Commodity(commodity) => marshal_commodity(commodity, writer),
+        MetadataValue::Tag(tag) => marshal_tag(tag, writer),
+        MetadataValue::Bool(true) => write!(writer, "{KEYWORD_TRUE}"),
+        MetadataValue::Bool(false) => write!(writer, "{KEYWORD_FALSE}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Amount, Tag, account, commodity};
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use rstest_reuse::*;
+    use rust_decimal_macros::dec;
+
+    #[template]
+    #[rstest]
+    #[case("\"US Dollar\"", MetadataValue::String("US Dollar".into()))]
+    #[case("\"\"", MetadataValue::String("".into()))]
+    #[case("\"with \\\"quotes\\\"\"", MetadataValue::String("with \"quotes\"".into()))]
+    #[case("2", MetadataValue::Number(dec!(2)))]
+    #[case("-0.5", MetadataValue::Number(dec!(-0.5)))]
+    #[case("USD", MetadataValue::Commodity(commodity!(USD)))]
+    #[case("TRUEUSD", MetadataValue::Commodity(commodity!(TRUEUSD)))]
+    #[case("TRUE", MetadataValue::Bool(true))]
+    #[case("FALSE", MetadataValue::Bool(false))]
+    #[case("2024-03-01", MetadataValue::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()))]
+    #[case("10.50 USD", MetadataValue::Amount(Amount::new(dec!(10.50), commodity!(USD))))]
+    #[case("Assets:Cash", MetadataValue::Account(account!(Assets:Cash)))]
+    #[case("#trip-japan", MetadataValue::Tag(Tag::new("trip-japan").unwrap()))]
+    fn valid_metadata_value_template(#[case] input: &str, #[case] expected: MetadataValue) {}
+
+    #[apply(valid_metadata_value_template)]
+    fn parse_metadata_value_valid(#[case] input: &str, #[case] expected: MetadataValue) {
+        let result = parse_metadata_value().parse(input);
+        assert!(result.has_output(), "Failed to parse value: {}", input);
+        assert_eq!(result.into_result().unwrap(), expected);
+    }
+
+    #[apply(valid_metadata_value_template)]
+    fn marshal_and_parse_metadata_value(#[case] input: &str, #[case] _expected: MetadataValue) {
+        let original = parse_metadata_value().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_metadata_value(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_metadata_value()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("")] // Empty
+    #[case("\"unterminated")] // Unterminated string
+    #[case("usd")] // Lowercase is neither a commodity nor a keyword
+    #[case("1.2.3")] // Invalid number
+    fn parse_metadata_value_invalid(#[case] input: &str) {
+        let result = parse_metadata_value().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
@@ -0,0 +1,7 @@
+mod metadata;
+mod metadata_key;
+mod metadata_value;
+
+pub use metadata::{marshal_metadata, marshal_posting_metadata, parse_metadata};
+pub use metadata_key::{marshal_metadata_key, parse_metadata_key};
+pub use metadata_value::{marshal_metadata_value, parse_metadata_value};
@@ -1,13 +1,22 @@
 mod account;
 mod amount;
 mod amount_with_tolerance;
+mod comment;
 mod commodity;
 mod commodity_list;
 mod date;
 mod decimal;
 mod directive;
 mod error_format;
+mod file;
+mod incomplete_amount;
+mod link;
+mod metadata;
+mod pragma;
 mod quoted_string;
+mod tag;
 
 pub use directive::{marshal_directive, parse_directive};
-pub use error_format::ParseResultExt;
+pub use error_format::{ParseResultExt, format_error_in_file};
+pub use file::{marshal_file, parse_file};
+pub use pragma::{marshal_options, marshal_pragma, parse_pragma};
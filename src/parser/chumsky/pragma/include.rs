@@ -0,0 +1,59 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::PragmaInclude,
+    parser::chumsky::quoted_string::{marshal_quoted_string, parse_quoted_string},
+};
+
+const KEYWORD_INCLUDE: &str = "include";
+
+/// Parser for include pragma
+/// Syntax: "include" <quoted_string>
+pub fn parse_include_pragma<'a>()
+-> impl Parser<'a, &'a str, PragmaInclude<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_INCLUDE)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .map(PragmaInclude::from_str_path)
+}
+
+/// Marshaller for include pragma
+pub fn marshal_include_pragma(pragma: &PragmaInclude, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_INCLUDE} ")?;
+    marshal_quoted_string(&pragma.path().to_string_lossy(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::Path;
+
+    #[rstest]
+    #[case("include \"2023.beancount\"", "2023.beancount")]
+    #[case(
+        "include   \"../ledgers/accounts.beancount\"",
+        "../ledgers/accounts.beancount"
+    )]
+    fn parse_and_marshal_include_pragma(#[case] input: &str, #[case] expected_path: &str) {
+        let parsed = parse_include_pragma().parse(input).into_result().unwrap();
+        assert_eq!(parsed.path(), Path::new(expected_path));
+
+        let mut marshalled = String::new();
+        marshal_include_pragma(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, format!("include \"{expected_path}\""));
+    }
+
+    #[rstest]
+    #[case("include")] // Missing path
+    #[case("include 2023.beancount")] // Path not quoted
+    #[case("include\"2023.beancount\"")] // Missing space
+    fn parse_include_pragma_invalid(#[case] input: &str) {
+        let result = parse_include_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
@@ -0,0 +1,115 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::{MetadataKey, PragmaPushMeta},
+    parser::chumsky::metadata::{
+        marshal_metadata_key, marshal_metadata_value, parse_metadata_key, parse_metadata_value,
+    },
+};
+
+const KEYWORD_PUSHMETA: &str = "pushmeta";
+const KEYWORD_POPMETA: &str = "popmeta";
+
+/// Parser for pushmeta pragma
+/// Syntax: "pushmeta" <key> ":" <value>
+pub fn parse_pushmeta_pragma<'a>()
+-> impl Parser<'a, &'a str, PragmaPushMeta<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_PUSHMETA)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_metadata_key())
+        .then_ignore(just(':'))
+        .then_ignore(inline_whitespace())
+        .then(parse_metadata_value())
+        .map(|(key, value)| PragmaPushMeta::new(key, value))
+}
+
+/// Parser for popmeta pragma
+/// Syntax: "popmeta" <key> ":"
+pub fn parse_popmeta_pragma<'a>()
+-> impl Parser<'a, &'a str, MetadataKey<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_POPMETA)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_metadata_key())
+        .then_ignore(just(':'))
+}
+
+pub fn marshal_pushmeta_pragma(
+    pragma: &PragmaPushMeta,
+    writer: &mut impl Write,
+) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_PUSHMETA} ")?;
+    marshal_metadata_key(pragma.key(), writer)?;
+    write!(writer, ": ")?;
+    marshal_metadata_value(pragma.value(), writer)
+}
+
+pub fn marshal_popmeta_pragma(key: &MetadataKey, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_POPMETA} ")?;
+    marshal_metadata_key(key, writer)?;
+    write!(writer, ":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::MetadataValue;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("pushmeta location: \"Tokyo\"")]
+    #[case("pushmeta trip: TRUE")]
+    #[case("pushmeta budget: 300")]
+    fn parse_and_marshal_pushmeta_pragma(#[case] input: &str) {
+        let parsed = parse_pushmeta_pragma().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_pushmeta_pragma(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[test]
+    fn parse_pushmeta_pragma_entry() {
+        let parsed = parse_pushmeta_pragma()
+            .parse("pushmeta location:   \"Tokyo\"")
+            .into_result()
+            .unwrap();
+
+        assert_eq!(parsed.key().as_ref(), "location");
+        assert_eq!(parsed.value(), &MetadataValue::String("Tokyo".into()));
+    }
+
+    #[test]
+    fn parse_and_marshal_popmeta_pragma() {
+        let parsed = parse_popmeta_pragma()
+            .parse("popmeta location:")
+            .into_result()
+            .unwrap();
+        assert_eq!(parsed.as_ref(), "location");
+
+        let mut marshalled = String::new();
+        marshal_popmeta_pragma(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, "popmeta location:");
+    }
+
+    #[rstest]
+    #[case("pushmeta location")] // Missing colon and value
+    #[case("pushmeta location:")] // Missing value
+    #[case("pushmeta Location: \"Tokyo\"")] // Invalid key
+    #[case("pushmeta\nlocation: \"Tokyo\"")] // Key on next line
+    fn parse_pushmeta_pragma_invalid(#[case] input: &str) {
+        let result = parse_pushmeta_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[rstest]
+    #[case("popmeta location")] // Missing colon
+    #[case("popmeta location: \"Tokyo\"")] // Value not allowed
+    fn parse_popmeta_pragma_invalid(#[case] input: &str) {
+        let result = parse_popmeta_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
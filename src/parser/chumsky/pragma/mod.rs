@@ -0,0 +1,8 @@
+mod include;
+mod meta;
+mod option;
+mod plugin;
+mod pragma;
+mod tag;
+
+pub use pragma::{marshal_options, marshal_pragma, parse_pragma};
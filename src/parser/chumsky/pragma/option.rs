@@ -0,0 +1,129 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::{FileId, PragmaOption, SourceSpan},
+    parser::chumsky::quoted_string::{marshal_quoted_string, parse_quoted_string},
+};
+
+const KEYWORD_OPTION: &str = "option";
+
+/// Parser for option pragma
+/// Syntax: "option" <quoted_string> <quoted_string>
+pub fn parse_option_pragma<'a>()
+-> impl Parser<'a, &'a str, PragmaOption<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(KEYWORD_OPTION)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .then_ignore(inline_whitespace().at_least(1))
+        .then(parse_quoted_string())
+        .map_with(|(name, value), e| {
+            let span: SimpleSpan = e.span();
+            PragmaOption::new(name, value)
+                .with_span(SourceSpan::new(FileId::default(), span.into_range()))
+        })
+}
+
+/// Marshaller for option pragma
+pub fn marshal_option_pragma(pragma: &PragmaOption, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_OPTION} ")?;
+    marshal_quoted_string(pragma.name(), writer)?;
+    write!(writer, " ")?;
+    marshal_quoted_string(pragma.value(), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case("option \"title\" \"Our Ledger\"", "title", "Our Ledger")]
+    #[case(
+        "option   \"operating_currency\"   \"EUR\"",
+        "operating_currency",
+        "EUR"
+    )]
+    #[case("option \"documents\" \"\"", "documents", "")]
+    #[case(
+        "option \"title\" \"The \\\"Real\\\" Ledger\"",
+        "title",
+        "The \"Real\" Ledger"
+    )]
+    fn valid_option_pragma_template(
+        #[case] input: &str,
+        #[case] expected_name: &str,
+        #[case] expected_value: &str,
+    ) {
+    }
+
+    #[apply(valid_option_pragma_template)]
+    fn parse_option_pragma_valid(
+        #[case] input: &str,
+        #[case] expected_name: &str,
+        #[case] expected_value: &str,
+    ) {
+        let result = parse_option_pragma().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse option pragma: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.name(), expected_name);
+        assert_eq!(parsed.value(), expected_value);
+    }
+
+    #[apply(valid_option_pragma_template)]
+    fn marshal_and_parse_option_pragma(
+        #[case] input: &str,
+        #[case] _expected_name: &str,
+        #[case] _expected_value: &str,
+    ) {
+        let original = parse_option_pragma().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_option_pragma(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_option_pragma()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("option")] // Missing name and value
+    #[case("option \"title\"")] // Missing value
+    #[case("option title \"Our Ledger\"")] // Name not quoted
+    #[case("option\"title\" \"Our Ledger\"")] // Missing space
+    fn parse_option_pragma_invalid(#[case] input: &str) {
+        let result = parse_option_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn parse_option_pragma_span() {
+        let parsed = parse_option_pragma()
+            .parse("option \"title\" \"Our Ledger\"")
+            .into_result()
+            .unwrap();
+
+        assert_eq!(parsed.span().unwrap().byte_range(), 0..27);
+    }
+
+    #[test]
+    fn marshal_option_pragma_basic() {
+        let pragma = PragmaOption::new("operating_currency", "EUR");
+
+        let mut output = String::new();
+        marshal_option_pragma(&pragma, &mut output).unwrap();
+        assert_eq!(output, "option \"operating_currency\" \"EUR\"");
+    }
+}
@@ -0,0 +1,117 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::PragmaPlugin,
+    parser::chumsky::quoted_string::{marshal_quoted_string, parse_quoted_string},
+};
+
+const KEYWORD_PLUGIN: &str = "plugin";
+
+/// Parser for plugin pragma
+/// Syntax: "plugin" <quoted_string> [<quoted_string>]
+pub fn parse_plugin_pragma<'a>()
+-> impl Parser<'a, &'a str, PragmaPlugin<'a>, extra::Err<Rich<'a, char>>> {
+    let config = inline_whitespace()
+        .at_least(1)
+        .ignore_then(parse_quoted_string())
+        .or_not();
+
+    keyword(KEYWORD_PLUGIN)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_quoted_string())
+        .then(config)
+        .map(|(module_name, config)| {
+            let plugin = PragmaPlugin::new(module_name);
+            match config {
+                Some(config) => plugin.with_config(config),
+                None => plugin,
+            }
+        })
+}
+
+/// Marshaller for plugin pragma
+pub fn marshal_plugin_pragma(pragma: &PragmaPlugin, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_PLUGIN} ")?;
+    marshal_quoted_string(pragma.module_name(), writer)?;
+    if let Some(config) = pragma.config() {
+        write!(writer, " ")?;
+        marshal_quoted_string(config, writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rstest_reuse::*;
+
+    #[template]
+    #[rstest]
+    #[case(
+        "plugin \"beancount.plugins.auto_accounts\"",
+        "beancount.plugins.auto_accounts",
+        None
+    )]
+    #[case(
+        "plugin \"beancount.plugins.check_commodity\" \"USD\"",
+        "beancount.plugins.check_commodity",
+        Some("USD")
+    )]
+    #[case("plugin   \"my_plugin\"   \"\"", "my_plugin", Some(""))]
+    fn valid_plugin_pragma_template(
+        #[case] input: &str,
+        #[case] expected_module_name: &str,
+        #[case] expected_config: Option<&str>,
+    ) {
+    }
+
+    #[apply(valid_plugin_pragma_template)]
+    fn parse_plugin_pragma_valid(
+        #[case] input: &str,
+        #[case] expected_module_name: &str,
+        #[case] expected_config: Option<&str>,
+    ) {
+        let result = parse_plugin_pragma().parse(input);
+        assert!(
+            result.has_output(),
+            "Failed to parse plugin pragma: {}",
+            input
+        );
+        let parsed = result.into_result().unwrap();
+
+        assert_eq!(parsed.module_name(), expected_module_name);
+        assert_eq!(parsed.config(), expected_config);
+    }
+
+    #[apply(valid_plugin_pragma_template)]
+    fn marshal_and_parse_plugin_pragma(
+        #[case] input: &str,
+        #[case] _expected_module_name: &str,
+        #[case] _expected_config: Option<&str>,
+    ) {
+        let original = parse_plugin_pragma().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_plugin_pragma(&original, &mut marshalled).unwrap();
+
+        let reparsed = parse_plugin_pragma()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[rstest]
+    #[case("plugin")] // Missing module name
+    #[case("plugin beancount.plugins.auto_accounts")] // Module name not quoted
+    #[case("plugin\"beancount.plugins.auto_accounts\"")] // Missing space
+    fn parse_plugin_pragma_invalid(#[case] input: &str) {
+        let result = parse_plugin_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
@@ -0,0 +1,154 @@
+use chumsky::prelude::*;
+use std::fmt::Write;
+
+use crate::{
+    model::{Options, Pragma},
+    parser::chumsky::pragma::{
+        include::{marshal_include_pragma, parse_include_pragma},
+        meta::{
+            marshal_popmeta_pragma, marshal_pushmeta_pragma, parse_popmeta_pragma,
+            parse_pushmeta_pragma,
+        },
+        option::{marshal_option_pragma, parse_option_pragma},
+        plugin::{marshal_plugin_pragma, parse_plugin_pragma},
+        tag::{
+            marshal_poptag_pragma, marshal_pushtag_pragma, parse_poptag_pragma,
+            parse_pushtag_pragma,
+        },
+    },
+};
+
+/// Parser for undated lines
+/// Syntax: <option_pragma> | <plugin_pragma> | <include_pragma> | <pushtag_pragma> | <poptag_pragma>
+///       | <pushmeta_pragma> | <popmeta_pragma>
+pub fn parse_pragma<'a>() -> impl Parser<'a, &'a str, Pragma<'a>, extra::Err<Rich<'a, char>>> {
+    choice((
+        parse_option_pragma().map(Pragma::Option),
+        parse_plugin_pragma().map(Pragma::Plugin),
+        parse_include_pragma().map(Pragma::Include),
+        parse_pushtag_pragma().map(Pragma::PushTag),
+        parse_poptag_pragma().map(Pragma::PopTag),
+        parse_pushmeta_pragma().map(Pragma::PushMeta),
+        parse_popmeta_pragma().map(Pragma::PopMeta),
+    ))
+}
+
+pub fn marshal_pragma(pragma: &Pragma, writer: &mut impl Write) -> std::fmt::Result {
+    match pragma {
+        Pragma::Option(option) => marshal_option_pragma(option, writer),
+        Pragma::Plugin(plugin) => marshal_plugin_pragma(plugin, writer),
+        Pragma::Include(include) => marshal_include_pragma(include, writer),
+        Pragma::PushTag(tag) => marshal_pushtag_pragma(tag, writer),
+        Pragma::PopTag(tag) => marshal_poptag_pragma(tag, writer),
+        Pragma::PushMeta(push_meta) => marshal_pushmeta_pragma(push_meta, writer),
+        Pragma::PopMeta(key) => marshal_popmeta_pragma(key, writer),
+    }
+}
+
+/// Marshal options as `option` lines, one per line
+pub fn marshal_options(options: &Options, writer: &mut impl Write) -> std::fmt::Result {
+    for option in options.to_pragmas() {
+        marshal_option_pragma(&option, writer)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{PragmaOption, commodity};
+    use rstest::rstest;
+    use std::path::Path;
+
+    #[rstest]
+    #[case("option \"title\" \"Our Ledger\"")]
+    #[case("option \"operating_currency\" \"EUR\"")]
+    #[case("plugin \"beancount.plugins.auto_accounts\"")]
+    #[case("plugin \"beancount.plugins.check_commodity\" \"USD\"")]
+    #[case("include \"2023.beancount\"")]
+    #[case("pushtag #trip-japan")]
+    #[case("poptag #trip-japan")]
+    #[case("pushmeta location: \"Tokyo\"")]
+    #[case("popmeta location:")]
+    fn parse_and_marshal_pragma(#[case] input: &str) {
+        let parsed = parse_pragma().parse(input).into_result().unwrap();
+
+        let mut marshalled = String::new();
+        marshal_pragma(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[test]
+    fn parse_pragma_variants() {
+        let option = parse_pragma()
+            .parse("option \"title\" \"Ledger\"")
+            .into_result()
+            .unwrap();
+        assert_eq!(option.as_option().unwrap().name(), "title");
+
+        let plugin = parse_pragma()
+            .parse("plugin \"beancount.plugins.auto_accounts\"")
+            .into_result()
+            .unwrap();
+        assert_eq!(
+            plugin.as_plugin().unwrap().module_name(),
+            "beancount.plugins.auto_accounts"
+        );
+
+        let include = parse_pragma()
+            .parse("include \"2023.beancount\"")
+            .into_result()
+            .unwrap();
+        assert_eq!(
+            include.as_include().unwrap().path(),
+            Path::new("2023.beancount")
+        );
+    }
+
+    #[rstest]
+    #[case("2024-01-01 open Assets:Cash")]
+    #[case("options \"title\" \"Ledger\"")]
+    #[case("pushtag trip")]
+    fn parse_pragma_invalid(#[case] input: &str) {
+        let result = parse_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_and_parse_options() {
+        let options = Options::from_pragmas([
+            PragmaOption::new("title", "Our Ledger"),
+            PragmaOption::new("operating_currency", "EUR"),
+            PragmaOption::new("booking_method", "FIFO"),
+            PragmaOption::new("render_commas", "TRUE"),
+        ])
+        .unwrap();
+
+        let mut marshalled = String::new();
+        marshal_options(&options, &mut marshalled).unwrap();
+        assert_eq!(
+            marshalled,
+            "option \"title\" \"Our Ledger\"\n\
+             option \"operating_currency\" \"EUR\"\n\
+             option \"booking_method\" \"FIFO\"\n\
+             option \"render_commas\" \"TRUE\"\n"
+        );
+
+        let pragmas = parse_pragma()
+            .separated_by(just('\n'))
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .parse(&marshalled)
+            .into_result()
+            .unwrap();
+        let reparsed =
+            Options::from_pragmas(pragmas.into_iter().filter_map(|pragma| match pragma {
+                Pragma::Option(option) => Some(option),
+                _ => None,
+            }))
+            .unwrap();
+        assert_eq!(reparsed, options);
+        assert_eq!(reparsed.operating_currencies(), [commodity!(EUR)]);
+    }
+}
@@ -0,0 +1,88 @@
+use chumsky::{
+    prelude::*,
+    text::{inline_whitespace, keyword},
+};
+use std::fmt::Write;
+
+use crate::{
+    model::Tag,
+    parser::chumsky::tag::{marshal_tag, parse_tag},
+};
+
+const KEYWORD_PUSHTAG: &str = "pushtag";
+const KEYWORD_POPTAG: &str = "poptag";
+
+/// Parser for pushtag pragma
+/// Syntax: "pushtag" <tag>
+pub fn parse_pushtag_pragma<'a>() -> impl Parser<'a, &'a str, Tag<'a>, extra::Err<Rich<'a, char>>> {
+    parse_tag_pragma(KEYWORD_PUSHTAG)
+}
+
+/// Parser for poptag pragma
+/// Syntax: "poptag" <tag>
+pub fn parse_poptag_pragma<'a>() -> impl Parser<'a, &'a str, Tag<'a>, extra::Err<Rich<'a, char>>> {
+    parse_tag_pragma(KEYWORD_POPTAG)
+}
+
+fn parse_tag_pragma<'a>(
+    keyword_str: &'static str,
+) -> impl Parser<'a, &'a str, Tag<'a>, extra::Err<Rich<'a, char>>> {
+    keyword(keyword_str)
+        .then_ignore(inline_whitespace().at_least(1))
+        .ignore_then(parse_tag())
+}
+
+pub fn marshal_pushtag_pragma(tag: &Tag, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_PUSHTAG} ")?;
+    marshal_tag(tag, writer)
+}
+
+pub fn marshal_poptag_pragma(tag: &Tag, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "{KEYWORD_POPTAG} ")?;
+    marshal_tag(tag, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("pushtag #trip-japan", "trip-japan")]
+    #[case("pushtag   #trip", "trip")]
+    fn parse_pushtag_pragma_valid(#[case] input: &str, #[case] expected: &str) {
+        let parsed = parse_pushtag_pragma().parse(input).into_result().unwrap();
+        assert_eq!(parsed.as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case("poptag #trip-japan", "trip-japan")]
+    #[case("poptag   #trip", "trip")]
+    fn parse_poptag_pragma_valid(#[case] input: &str, #[case] expected: &str) {
+        let parsed = parse_poptag_pragma().parse(input).into_result().unwrap();
+        assert_eq!(parsed.as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case("pushtag")] // Missing tag
+    #[case("pushtag trip")] // Missing #
+    #[case("pushtag#trip")] // Missing space
+    #[case("poptag #trip")] // Wrong keyword
+    fn parse_pushtag_pragma_invalid(#[case] input: &str) {
+        let result = parse_pushtag_pragma().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+
+    #[test]
+    fn marshal_tag_pragmas() {
+        let tag = Tag::new("trip-japan").unwrap();
+
+        let mut output = String::new();
+        marshal_pushtag_pragma(&tag, &mut output).unwrap();
+        assert_eq!(output, "pushtag #trip-japan");
+
+        let mut output = String::new();
+        marshal_poptag_pragma(&tag, &mut output).unwrap();
+        assert_eq!(output, "poptag #trip-japan");
+    }
+}
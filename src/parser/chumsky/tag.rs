@@ -0,0 +1,53 @@
+use chumsky::prelude::*;
+use std::fmt::Write;
+
+use crate::model::Tag;
+
+/// Parser for tags
+/// Syntax: "#" <tag_name>
+pub fn parse_tag<'a>() -> impl Parser<'a, &'a str, Tag<'a>, extra::Err<Rich<'a, char>>> {
+    just('#').ignore_then(
+        any()
+            .filter(|c: &char| !c.is_whitespace())
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .try_map(|slice: &'a str, span| {
+                Tag::try_from(slice).map_err(|e| Rich::custom(span, format!("{}", e)))
+            }),
+    )
+}
+
+pub fn marshal_tag(tag: &Tag, writer: &mut impl Write) -> std::fmt::Result {
+    write!(writer, "#{}", tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("#trip-japan", "trip-japan")]
+    #[case("#invoices/2024.03", "invoices/2024.03")]
+    #[case("#a_b", "a_b")]
+    fn parse_and_marshal_tag(#[case] input: &str, #[case] expected: &str) {
+        let parsed = parse_tag().parse(input).into_result().unwrap();
+        assert_eq!(parsed.as_ref(), expected);
+
+        let mut marshalled = String::new();
+        marshal_tag(&parsed, &mut marshalled).unwrap();
+        assert_eq!(marshalled, input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("#")]
+    #[case("trip")]
+    #[case("# trip")]
+    #[case("#trip japan")]
+    fn parse_tag_invalid(#[case] input: &str) {
+        let result = parse_tag().parse(input);
+        assert!(!result.has_output(), "Should fail to parse: {}", input);
+    }
+}
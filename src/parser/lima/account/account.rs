@@ -37,7 +37,7 @@ mod tests {
         let beancount_file =
             // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
             "2020-01-01 * \"Test Transaction\"\n  Assets:US:Bank  100.00 USD\n  Expenses:Food\n";
-        let beancount_file = BeancountSources::try_from(beancount_file).unwrap();
+        let beancount_file = BeancountSources::from(beancount_file);
         let parser = BeancountParser::new(&beancount_file);
         let parsed = parser.parse().unwrap();
         let DirectiveVariant::Transaction(parsed_directive) =
@@ -46,13 +46,7 @@ mod tests {
             panic!("Expected a transaction directive");
         };
         let parsed_account_1 = parsed_directive.postings().next().unwrap().account().item();
-        let parsed_account_2 = parsed_directive
-            .postings()
-            .skip(1)
-            .next()
-            .unwrap()
-            .account()
-            .item();
+        let parsed_account_2 = parsed_directive.postings().nth(1).unwrap().account().item();
         let account_1 = Account::try_from(parsed_account_1).unwrap();
         let account_2 = Account::try_from(parsed_account_2).unwrap();
         assert_eq!(account!(Assets:US:Bank), account_1);
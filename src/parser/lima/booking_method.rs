@@ -0,0 +1,17 @@
+use beancount_parser_lima::Booking;
+
+use crate::model::BookingMethod;
+
+impl From<&Booking> for BookingMethod {
+    fn from(booking: &Booking) -> Self {
+        match booking {
+            Booking::Strict => BookingMethod::Strict,
+            Booking::StrictWithSize => BookingMethod::StrictWithSize,
+            Booking::None => BookingMethod::None,
+            Booking::Average => BookingMethod::Average,
+            Booking::Fifo => BookingMethod::Fifo,
+            Booking::Lifo => BookingMethod::Lifo,
+            Booking::Hifo => BookingMethod::Hifo,
+        }
+    }
+}
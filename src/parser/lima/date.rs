@@ -0,0 +1,5 @@
+pub fn date_into(date: &time::Date) -> chrono::NaiveDate {
+    // TODO Is there a more efficient way?
+    chrono::NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day() as u32)
+        .expect("Invalid date conversion")
+}
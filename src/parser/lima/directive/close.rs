@@ -0,0 +1,37 @@
+use crate::{model::DirectiveClose, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Close<'a>> for DirectiveClose<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(close: &'r beancount_parser_lima::Close<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveClose::new(close.account().item().try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use crate::model::account;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2020-01-01 close Assets:OldBank\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Close(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a close directive");
+        };
+        let close = DirectiveClose::try_from(parsed_directive).unwrap();
+        assert_eq!(&account!(Assets:OldBank), close.account());
+    }
+}
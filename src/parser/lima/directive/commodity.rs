@@ -0,0 +1,57 @@
+use crate::{
+    model::{Commodity, DirectiveCommodity},
+    parser::lima::error::LimaConversionError,
+};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Commodity<'a>> for DirectiveCommodity<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(commodity: &'r beancount_parser_lima::Commodity<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveCommodity::new(Commodity::try_from(
+            commodity.currency().item(),
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use crate::model::{Directive, MetadataValue, commodity};
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        let beancount_file = "2010-01-01 commodity USD\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Commodity(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a commodity directive");
+        };
+        let directive = DirectiveCommodity::try_from(parsed_directive).unwrap();
+        assert_eq!(&commodity!(USD), directive.commodity());
+    }
+
+    #[test]
+    fn test_try_from_directive_with_metadata() {
+        let beancount_file = "2010-01-01 commodity USD\n  name: \"US Dollar\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let directive = Directive::try_from(parsed.directives.first().unwrap().item()).unwrap();
+
+        let commodity = directive.as_commodity().unwrap();
+        assert_eq!(&commodity!(USD), commodity.commodity());
+        assert_eq!(
+            directive.metadata().get("name"),
+            Some(&MetadataValue::String("US Dollar".into()))
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    model::{Directive, DirectiveVariant},
-    parser::lima::error::LimaConversionError,
+    model::{Directive, DirectiveCommodity, DirectiveVariant},
+    parser::lima::{
+        date::date_into, directive::transaction::add_tags_and_links, error::LimaConversionError,
+    },
 };
 
 impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Directive<'a>> for Directive<'a>
@@ -11,21 +13,44 @@ where
 
     fn try_from(directive: &'r beancount_parser_lima::Directive<'a>) -> Result<Self, Self::Error> {
         let date = date_into(directive.date().item());
+        // lima doesn't support `custom` directives, so there is no source for DirectiveVariant::Custom
         let variant = match directive.variant() {
             beancount_parser_lima::DirectiveVariant::Open(open) => {
                 DirectiveVariant::Open(open.try_into()?)
             }
+            beancount_parser_lima::DirectiveVariant::Close(close) => {
+                DirectiveVariant::Close(close.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Commodity(commodity) => {
+                DirectiveVariant::Commodity(DirectiveCommodity::try_from(commodity)?)
+            }
+            beancount_parser_lima::DirectiveVariant::Price(price) => {
+                DirectiveVariant::Price(price.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Pad(pad) => {
+                DirectiveVariant::Pad(pad.try_into()?)
+            }
             beancount_parser_lima::DirectiveVariant::Transaction(transaction) => {
-                DirectiveVariant::Transaction(transaction.try_into()?)
+                let mut transaction = transaction.try_into()?;
+                add_tags_and_links(&mut transaction, directive.metadata())?;
+                DirectiveVariant::Transaction(transaction)
+            }
+            beancount_parser_lima::DirectiveVariant::Note(note) => {
+                DirectiveVariant::Note(note.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Document(document) => {
+                DirectiveVariant::Document(document.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Balance(balance) => {
+                DirectiveVariant::Balance(balance.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Event(event) => {
+                DirectiveVariant::Event(event.try_into()?)
+            }
+            beancount_parser_lima::DirectiveVariant::Query(query) => {
+                DirectiveVariant::Query(query.try_into()?)
             }
-            _ => todo!(),
         };
-        Ok(Directive::new(date, variant))
+        Ok(Directive::new(date, variant).with_metadata(directive.metadata().try_into()?))
     }
 }
-
-fn date_into(date: &time::Date) -> chrono::NaiveDate {
-    // TODO Is there a more efficient way?
-    chrono::NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day() as u32)
-        .expect("Invalid date conversion")
-}
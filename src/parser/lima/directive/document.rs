@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use crate::{model::DirectiveDocument, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Document<'a>> for DirectiveDocument<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(document: &'r beancount_parser_lima::Document<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveDocument::new(
+            document.account().item().try_into()?,
+            Path::new(*document.path().item()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use crate::model::account;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2024-02-01 document Assets:Checking \"statements/2024-01.pdf\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Document(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a document directive");
+        };
+        let document = DirectiveDocument::try_from(parsed_directive).unwrap();
+        assert_eq!(&account!(Assets:Checking), document.account());
+        assert_eq!(Path::new("statements/2024-01.pdf"), document.path());
+    }
+}
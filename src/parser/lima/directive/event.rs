@@ -0,0 +1,39 @@
+use crate::{model::DirectiveEvent, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Event<'a>> for DirectiveEvent<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(event: &'r beancount_parser_lima::Event<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveEvent::new(
+            *event.event_type().item(),
+            *event.description().item(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2024-03-01 event \"location\" \"Berlin\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Event(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected an event directive");
+        };
+        let event = DirectiveEvent::try_from(parsed_directive).unwrap();
+        assert_eq!("location", event.event_type());
+        assert_eq!("Berlin", event.description());
+    }
+}
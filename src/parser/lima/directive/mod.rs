@@ -1,6 +1,14 @@
 mod balance;
+mod close;
+mod commodity;
 mod directive;
+mod document;
+mod event;
+mod note;
 mod open;
+mod pad;
+mod price;
+mod query;
 mod transaction;
 
 pub use transaction::{LimaPostingConversionError, LimaTransactionConversionError};
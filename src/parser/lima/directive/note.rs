@@ -0,0 +1,41 @@
+use crate::{model::DirectiveNote, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Note<'a>> for DirectiveNote<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(note: &'r beancount_parser_lima::Note<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveNote::new(
+            note.account().item().try_into()?,
+            *note.comment().item(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use crate::model::account;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2024-01-05 note Assets:Checking \"Called bank about fee\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Note(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a note directive");
+        };
+        let note = DirectiveNote::try_from(parsed_directive).unwrap();
+        assert_eq!(&account!(Assets:Checking), note.account());
+        assert_eq!("Called bank about fee", note.comment());
+    }
+}
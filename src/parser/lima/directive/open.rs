@@ -17,6 +17,41 @@ where
             .currencies()
             .map(|c| c.item().try_into())
             .collect::<Result<HashSet<Commodity<'a>>, LimaConversionError>>()?;
-        Ok(DirectiveOpen::new(account, currencies))
+        let open = DirectiveOpen::new(account, currencies);
+        match directive.booking() {
+            Some(booking) => Ok(open.with_booking_method(booking.item().into())),
+            None => Ok(open),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+    use rstest::rstest;
+
+    use crate::model::BookingMethod;
+
+    use super::*;
+
+    #[rstest]
+    #[case("2024-01-01 open Assets:Stocks HOOL\n", None)]
+    #[case(
+        "2024-01-01 open Assets:Stocks HOOL \"FIFO\"\n",
+        Some(BookingMethod::Fifo)
+    )]
+    #[case("2024-01-01 open Assets:Stocks \"HIFO\"\n", Some(BookingMethod::Hifo))]
+    fn test_try_from(#[case] input: &str, #[case] expected: Option<BookingMethod>) {
+        let beancount_file = BeancountSources::from(input);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Open(open) = parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected open directive");
+        };
+
+        let converted = DirectiveOpen::try_from(open).unwrap();
+
+        assert_eq!(converted.booking_method(), expected);
     }
 }
@@ -0,0 +1,41 @@
+use crate::{model::DirectivePad, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Pad<'a>> for DirectivePad<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(pad: &'r beancount_parser_lima::Pad<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectivePad::new(
+            pad.account().item().try_into()?,
+            pad.source().item().try_into()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use crate::model::account;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2020-01-01 pad Assets:Checking Equity:OpeningBalances\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Pad(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a pad directive");
+        };
+        let pad = DirectivePad::try_from(parsed_directive).unwrap();
+        assert_eq!(&account!(Assets:Checking), pad.account());
+        assert_eq!(&account!(Equity:OpeningBalances), pad.source_account());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    model::{Commodity, DirectivePrice},
+    parser::lima::error::LimaConversionError,
+};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Price<'a>> for DirectivePrice<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(price: &'r beancount_parser_lima::Price<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectivePrice::new(
+            Commodity::try_from(price.currency().item())?,
+            price.amount().item().try_into()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+    use rust_decimal_macros::dec;
+
+    use crate::model::{Amount, commodity};
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2024-01-02 price HOOL 579.18 USD\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Price(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a price directive");
+        };
+        let price = DirectivePrice::try_from(parsed_directive).unwrap();
+        assert_eq!(&commodity!(HOOL), price.commodity());
+        assert_eq!(&Amount::new(dec!(579.18), commodity!(USD)), price.price());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{model::DirectiveQuery, parser::lima::error::LimaConversionError};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Query<'a>> for DirectiveQuery<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(query: &'r beancount_parser_lima::Query<'a>) -> Result<Self, Self::Error> {
+        Ok(DirectiveQuery::new(
+            *query.name().item(),
+            *query.content().item(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "2024-03-01 query \"cash\" \"SELECT account, sum(position)\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Query(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a query directive");
+        };
+        let query = DirectiveQuery::try_from(parsed_directive).unwrap();
+        assert_eq!("cash", query.name());
+        assert_eq!("SELECT account, sum(position)", query.query());
+    }
+}
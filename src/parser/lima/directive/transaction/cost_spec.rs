@@ -0,0 +1,78 @@
+use crate::{
+    model::directive::CostSpec,
+    parser::lima::{date::date_into, error::LimaConversionError},
+};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::CostSpec<'a>> for CostSpec<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(cost_spec: &'r beancount_parser_lima::CostSpec<'a>) -> Result<Self, Self::Error> {
+        let mut result = CostSpec::new();
+        if let Some(per_unit) = cost_spec.per_unit() {
+            result = result.with_per_unit_cost(per_unit.item().value());
+        }
+        if let Some(total) = cost_spec.total() {
+            result = result.with_total_cost(total.item().value());
+        }
+        if let Some(currency) = cost_spec.currency() {
+            result = result.with_commodity(currency.item().try_into()?);
+        }
+        if let Some(date) = cost_spec.date() {
+            result = result.with_date(date_into(date.item()));
+        }
+        if let Some(label) = cost_spec.label() {
+            result = result.with_label(*label.item());
+        }
+        if cost_spec.merge() {
+            result = result.with_merge();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    use crate::model::commodity;
+
+    use super::*;
+
+    #[rstest]
+    #[case("{502.12 USD}", CostSpec::per_unit(dec!(502.12), commodity!(USD)))]
+    #[case("{# 9.95 USD}", CostSpec::total(dec!(9.95), commodity!(USD)))]
+    #[case(
+        "{502.12 # 9.95 USD}",
+        CostSpec::per_unit(dec!(502.12), commodity!(USD)).with_total_cost(dec!(9.95))
+    )]
+    #[case(
+        "{502.12 USD, 2014-05-01, \"first-lot\"}",
+        CostSpec::per_unit(dec!(502.12), commodity!(USD))
+            .with_date(NaiveDate::from_ymd_opt(2014, 5, 1).unwrap())
+            .with_label("first-lot")
+    )]
+    #[case("{*}", CostSpec::new().with_merge())]
+    fn test_try_from(#[case] cost_spec: &str, #[case] expected: CostSpec) {
+        let beancount_file =
+            format!("2014-05-05 * \"Buy\"\n  Assets:Stock  10 HOOL {cost_spec}\n  Assets:Cash\n");
+        let beancount_file = BeancountSources::from(beancount_file.as_str());
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Transaction(transaction) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected transaction");
+        };
+        let posting = transaction.postings().next().unwrap();
+
+        let converted = CostSpec::try_from(posting.cost_spec().unwrap().item()).unwrap();
+
+        assert_eq!(converted, expected);
+    }
+}
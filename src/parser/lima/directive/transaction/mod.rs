@@ -1,6 +1,7 @@
+mod cost_spec;
 mod flag;
 mod posting;
 mod transaction;
 
 pub use posting::LimaPostingConversionError;
-pub use transaction::LimaTransactionConversionError;
+pub use transaction::{LimaTransactionConversionError, add_tags_and_links};
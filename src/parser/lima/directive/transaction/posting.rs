@@ -1,19 +1,16 @@
+use beancount_parser_lima::{PriceSpec, ScopedExprValue};
 use thiserror::Error;
 
 use crate::{
     model::{
-        Amount,
-        directive::{Posting, PostingAmount},
+        Amount, IncompleteAmount,
+        directive::{CostSpec, Posting, PostingAmount, PostingPrice},
     },
     parser::lima::error::LimaConversionError,
 };
 
 #[derive(Error, Debug)]
 pub enum LimaPostingConversionError<'a> {
-    #[error("Currency without amount in posting: {0}")]
-    CurrencyWithoutAmount(beancount_parser_lima::Posting<'a>),
-    #[error("Amount without currency in posting: {0}")]
-    AmountWithoutCurrency(beancount_parser_lima::Posting<'a>),
     #[error("Cost without amount in posting: {0}")]
     CostWithoutAmount(beancount_parser_lima::Posting<'a>),
     #[error("Price without amount in posting: {0}")]
@@ -29,12 +26,29 @@ where
     fn try_from(posting: &'r beancount_parser_lima::Posting<'a>) -> Result<Self, Self::Error> {
         let flag = posting.flag().map(|f| f.item().into());
         let account = posting.account().item().try_into()?;
-        let cost = todo!();
-        let price = todo!();
-        let amount = match (posting.amount(), posting.currency()) {
-            (Some(amount), Some(currency)) => {
-                let mut amount =
-                    PostingAmount::new(Amount::new(amount.value(), currency.item().try_into()?));
+        let metadata = posting.metadata().try_into()?;
+        let cost: Option<CostSpec<'a>> = posting
+            .cost_spec()
+            .map(|cost_spec| cost_spec.item().try_into())
+            .transpose()?;
+        let price = posting
+            .price_annotation()
+            .map(|price| price_into(price.item()))
+            .transpose()?;
+        let units = match (posting.amount(), posting.currency()) {
+            (Some(amount), Some(currency)) => Some(IncompleteAmount::Complete(Amount::new(
+                amount.value(),
+                currency.item().try_into()?,
+            ))),
+            (None, Some(currency)) => {
+                Some(IncompleteAmount::MissingNumber(currency.item().try_into()?))
+            }
+            (Some(amount), None) => Some(IncompleteAmount::MissingCommodity(amount.value())),
+            (None, None) => None,
+        };
+        let amount = match units {
+            Some(units) => {
+                let mut amount = PostingAmount::new(units);
                 if let Some(cost) = cost {
                     amount = amount.with_cost(cost);
                 }
@@ -43,32 +57,22 @@ where
                 }
                 Some(amount)
             }
-            (None, None) => {
+            None => {
                 if cost.is_some() {
-                    return Err(LimaConversionError::InvalidPosting(
-                        LimaPostingConversionError::CostWithoutAmount(*posting),
-                    ));
+                    return Err(LimaConversionError::InvalidPosting(Box::new(
+                        LimaPostingConversionError::CostWithoutAmount(posting.clone()),
+                    )));
                 }
                 if price.is_some() {
-                    return Err(LimaConversionError::InvalidPosting(
-                        LimaPostingConversionError::PriceWithoutAmount(*posting),
-                    ));
+                    return Err(LimaConversionError::InvalidPosting(Box::new(
+                        LimaPostingConversionError::PriceWithoutAmount(posting.clone()),
+                    )));
                 }
                 None
             }
-            (None, Some(_currency)) => {
-                return Err(LimaConversionError::InvalidPosting(
-                    LimaPostingConversionError::CurrencyWithoutAmount(*posting),
-                ));
-            }
-            (Some(_amount), None) => {
-                return Err(LimaConversionError::InvalidPosting(
-                    LimaPostingConversionError::AmountWithoutCurrency(*posting),
-                ));
-            }
         };
 
-        let posting = if let Some(amount) = amount {
+        let mut posting = if let Some(amount) = amount {
             Posting::new(account, amount)
         } else {
             Posting::new_without_amount(account)
@@ -76,8 +80,81 @@ where
         if let Some(flag) = flag {
             posting = posting.with_flag(flag);
         }
-        Ok(posting)
+        Ok(posting.with_metadata(metadata))
     }
 }
 
-// TODO Tests
+fn price_into<'a, 'r>(price: &'r PriceSpec<'r>) -> Result<PostingPrice<'a>, LimaConversionError<'a>>
+where
+    'r: 'a,
+{
+    let (number, currency) = match price {
+        PriceSpec::CurrencyAmount(number, currency) => (Some(number), Some(currency)),
+        PriceSpec::BareAmount(number) => (Some(number), None),
+        PriceSpec::BareCurrency(currency) => (None, Some(currency)),
+    };
+    let value = number.map(|number| match number {
+        ScopedExprValue::PerUnit(value) | ScopedExprValue::Total(value) => value.value(),
+    });
+    let amount = match (value, currency) {
+        (Some(value), Some(currency)) => {
+            IncompleteAmount::Complete(Amount::new(value, currency.try_into()?))
+        }
+        (Some(value), None) => IncompleteAmount::MissingCommodity(value),
+        (None, Some(currency)) => IncompleteAmount::MissingNumber(currency.try_into()?),
+        (None, None) => unreachable!("Price spec has number or currency"),
+    };
+    match number {
+        Some(ScopedExprValue::Total(_)) => Ok(PostingPrice::Total(amount)),
+        _ => Ok(PostingPrice::PerUnit(amount)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    use crate::model::commodity;
+
+    use super::*;
+
+    #[rstest]
+    #[case("10 USD", IncompleteAmount::Complete(Amount::new(dec!(10), commodity!(USD))), None)]
+    #[case("USD", IncompleteAmount::MissingNumber(commodity!(USD)), None)]
+    #[case("10", IncompleteAmount::MissingCommodity(dec!(10)), None)]
+    #[case(
+        "-10 HOOL @@ 5000 USD",
+        IncompleteAmount::Complete(Amount::new(dec!(-10), commodity!(HOOL))),
+        Some(PostingPrice::Total(Amount::new(dec!(5000), commodity!(USD)).into()))
+    )]
+    #[case(
+        "-10 HOOL @ USD",
+        IncompleteAmount::Complete(Amount::new(dec!(-10), commodity!(HOOL))),
+        Some(PostingPrice::PerUnit(IncompleteAmount::MissingNumber(commodity!(USD))))
+    )]
+    fn test_try_from(
+        #[case] amount: &str,
+        #[case] expected_units: IncompleteAmount,
+        #[case] expected_price: Option<PostingPrice>,
+    ) {
+        let beancount_file =
+            format!("2024-01-01 * \"Test\"\n  Assets:Cash  {amount}\n  Equity:Other\n");
+        let beancount_file = BeancountSources::from(beancount_file.as_str());
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Transaction(transaction) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected transaction");
+        };
+        let posting = transaction.postings().next().unwrap();
+
+        let converted = Posting::try_from(posting.item()).unwrap();
+
+        let posting_amount = converted.amount().unwrap();
+        assert_eq!(posting_amount.units(), &expected_units);
+        assert_eq!(posting_amount.price(), expected_price.as_ref());
+    }
+}
@@ -2,10 +2,10 @@ use thiserror::Error;
 
 use crate::{
     model::{
-        DirectiveTransaction,
+        DirectiveTransaction, Link, Tag,
         directive::{Posting, TransactionDescription},
     },
-    parser::lima::error::LimaConversionError,
+    parser::lima::{error::LimaConversionError, source_span::span_into},
 };
 
 #[derive(Error, Debug)]
@@ -38,17 +38,82 @@ where
             }
             (None, None) => (),
             (Some(_payee), None) => {
-                return Err(LimaConversionError::InvalidTransaction(
+                return Err(LimaConversionError::InvalidTransaction(Box::new(
                     LimaTransactionConversionError::PayeeWithoutNarration(transaction.clone()),
-                ));
+                )));
             }
         };
         let postings = transaction
             .postings()
-            .map(|posting| posting.item().try_into())
+            .map(|posting| {
+                Posting::try_from(posting.item()).map(|p| p.with_span(span_into(posting.span())))
+            })
             .collect::<Result<Vec<Posting<'a>>, LimaConversionError<'a>>>()?;
         result = result.with_postings(postings);
 
         Ok(result)
     }
 }
+
+/// Add the tags and links that lima stores in the directive metadata to a converted transaction
+pub fn add_tags_and_links<'a, 'r>(
+    transaction: &mut DirectiveTransaction<'a>,
+    metadata: &'r beancount_parser_lima::Metadata<'a>,
+) -> Result<(), LimaConversionError<'a>>
+where
+    'r: 'a,
+{
+    // lima stores tags and links in hash sets, restore the source order from the spans
+    let mut tags: Vec<_> = metadata.tags().collect();
+    tags.sort_by_key(|tag| tag.span().start);
+    for tag in tags {
+        transaction.add_tag(Tag::new(tag.item().as_ref())?);
+    }
+
+    let mut links: Vec<_> = metadata.links().collect();
+    links.sort_by_key(|link| link.span().start);
+    for link in links {
+        transaction.add_link(Link::new(link.item().as_ref())?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources, DirectiveVariant};
+
+    use super::*;
+
+    #[test]
+    fn test_add_tags_and_links() {
+        let beancount_file = "2024-03-01 * \"Sushi\" #trip-japan ^receipt-7 #food\n  Assets:Cash  -9.00 USD\n  Expenses:Food\n";
+        let beancount_file_content = beancount_file;
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let DirectiveVariant::Transaction(parsed_directive) =
+            parsed.directives.first().unwrap().item().variant()
+        else {
+            panic!("Expected a transaction directive");
+        };
+        let mut transaction = DirectiveTransaction::try_from(parsed_directive).unwrap();
+        add_tags_and_links(
+            &mut transaction,
+            parsed.directives.first().unwrap().item().metadata(),
+        )
+        .unwrap();
+
+        let tags: Vec<&str> = transaction.tags().iter().map(AsRef::as_ref).collect();
+        let links: Vec<&str> = transaction.links().iter().map(AsRef::as_ref).collect();
+        assert_eq!(tags, ["trip-japan", "food"]);
+        assert_eq!(links, ["receipt-7"]);
+        assert_eq!(transaction.postings().len(), 2);
+        let posting_lines: Vec<&str> = transaction
+            .postings()
+            .iter()
+            .map(|posting| &beancount_file_content[posting.span().unwrap().byte_range()])
+            .collect();
+        assert_eq!(posting_lines[0].trim(), "Assets:Cash  -9.00 USD");
+    }
+}
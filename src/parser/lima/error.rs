@@ -1,12 +1,16 @@
 use thiserror::Error;
 
 use crate::{
-    model::{InvalidAccountComponentError, InvalidCommodityError},
+    model::{
+        InvalidAccountComponentError, InvalidCommodityError, InvalidLinkError,
+        InvalidMetadataKeyError, InvalidTagError,
+    },
     parser::lima::directive::{LimaPostingConversionError, LimaTransactionConversionError},
 };
 
 /// An error happened when attempting to convert the output of [beancount_parser_lima] into our model types.
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum LimaConversionError<'a> {
     #[error("Invalid commodity: {0}")]
     InvalidCommodity(#[from] InvalidCommodityError),
@@ -14,9 +18,21 @@ pub enum LimaConversionError<'a> {
     #[error("Invalid account component: {0}")]
     InvalidAccountComponent(#[from] InvalidAccountComponentError),
 
+    #[error("Invalid metadata key: {0}")]
+    InvalidMetadataKey(#[from] InvalidMetadataKeyError),
+
+    #[error("Invalid tag: {0}")]
+    InvalidTag(#[from] InvalidTagError),
+
+    #[error("Invalid link: {0}")]
+    InvalidLink(#[from] InvalidLinkError),
+
+    #[error("Unsupported metadata value: {0}")]
+    UnsupportedMetadataValue(Box<beancount_parser_lima::MetaValue<'a>>),
+
     #[error("Invalid posting: {0}")]
-    InvalidPosting(LimaPostingConversionError<'a>),
+    InvalidPosting(Box<LimaPostingConversionError<'a>>),
 
     #[error("Invalid transaction: {0}")]
-    InvalidTransaction(LimaTransactionConversionError<'a>),
+    InvalidTransaction(Box<LimaTransactionConversionError<'a>>),
 }
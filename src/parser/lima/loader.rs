@@ -0,0 +1,225 @@
+use std::{io, path::Path};
+
+use beancount_parser_lima::{BeancountParser, BeancountSources, ParseSuccess};
+use thiserror::Error;
+
+use crate::{
+    model::{Directive, PragmaPlugin, SourceSpan},
+    parser::lima::{error::LimaConversionError, source_span::span_into},
+};
+
+#[derive(Debug, Error)]
+pub enum LimaLoadError<'a> {
+    #[error("Failed to read ledger: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Failed to parse ledger, found {} errors", errors.len())]
+    Parse {
+        errors: Vec<beancount_parser_lima::Error>,
+        warnings: Vec<beancount_parser_lima::Warning>,
+    },
+
+    #[error("Failed to convert ledger, found {} errors", errors.len())]
+    Conversion {
+        errors: Vec<LimaDirectiveConversionError<'a>>,
+    },
+}
+
+/// A directive parsed by lima that couldn't be converted into our model types
+#[derive(Debug, Error)]
+#[error("Failed to convert directive: {error}")]
+pub struct LimaDirectiveConversionError<'a> {
+    error: LimaConversionError<'a>,
+    span: SourceSpan,
+}
+
+impl<'a> LimaDirectiveConversionError<'a> {
+    pub fn error(&self) -> &LimaConversionError<'a> {
+        &self.error
+    }
+
+    /// Source location of the directive
+    pub fn span(&self) -> &SourceSpan {
+        &self.span
+    }
+}
+
+/// Ledger loaded with the lima backend
+#[derive(Debug)]
+pub struct LimaLedger<'a> {
+    directives: Vec<Directive<'a>>,
+    plugins: Vec<PragmaPlugin<'a>>,
+    warnings: Vec<beancount_parser_lima::Warning>,
+}
+
+impl<'a> LimaLedger<'a> {
+    /// Directives of all files, sorted by date
+    pub fn directives(&self) -> &[Directive<'a>] {
+        &self.directives
+    }
+
+    pub fn into_directives(self) -> Vec<Directive<'a>> {
+        self.directives
+    }
+
+    pub fn plugins(&self) -> &[PragmaPlugin<'a>] {
+        &self.plugins
+    }
+
+    pub fn warnings(&self) -> &[beancount_parser_lima::Warning] {
+        &self.warnings
+    }
+}
+
+/// Loader using [beancount_parser_lima] as parser backend. It reads the main file together with
+/// all included files, which lima resolves itself.
+pub struct LimaLoader {
+    sources: BeancountSources,
+}
+
+impl LimaLoader {
+    pub fn read(main_file: &Path) -> Result<Self, LimaLoadError<'static>> {
+        Ok(Self {
+            sources: BeancountSources::try_from(main_file)?,
+        })
+    }
+
+    /// Parse and convert the ledger, and pass the result to `process`.
+    /// The directives and conversion errors borrow from lima's parse result, which only lives
+    /// during this call.
+    pub fn load<T>(
+        &self,
+        process: impl for<'a> FnOnce(Result<LimaLedger<'a>, LimaLoadError<'a>>) -> T,
+    ) -> T {
+        let parser = BeancountParser::new(&self.sources);
+        let parsed = match parser.parse() {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return process(Err(LimaLoadError::Parse {
+                    errors: error.errors,
+                    warnings: error.warnings,
+                }));
+            }
+        };
+        let ledger = ingest(&parsed).map(|directives| LimaLedger {
+            directives,
+            plugins: parsed.plugins.iter().map(PragmaPlugin::from).collect(),
+            warnings: parsed.warnings.clone(),
+        });
+        process(ledger)
+    }
+
+    /// Render lima's errors or warnings, e.g. from [LimaLoadError::Parse], with their source locations
+    pub fn write_errors<E, K>(
+        &self,
+        writer: impl io::Write + Copy,
+        errors: Vec<E>,
+    ) -> io::Result<()>
+    where
+        E: Into<beancount_parser_lima::AnnotatedErrorOrWarning<K>>,
+        K: beancount_parser_lima::ErrorOrWarningKind,
+    {
+        self.sources.write(writer, errors)
+    }
+}
+
+impl From<&str> for LimaLoader {
+    fn from(content: &str) -> Self {
+        Self {
+            sources: BeancountSources::from(content),
+        }
+    }
+}
+
+/// Convert all directives, collecting the errors of every directive that fails to convert
+fn ingest<'a, 'r>(parsed: &'r ParseSuccess<'a>) -> Result<Vec<Directive<'a>>, LimaLoadError<'a>>
+where
+    'r: 'a,
+{
+    let mut directives = Vec::with_capacity(parsed.directives.len());
+    let mut errors = Vec::new();
+    for directive in &parsed.directives {
+        let span = span_into(directive.span());
+        match Directive::try_from(directive.item()) {
+            Ok(converted) => directives.push(converted.with_span(span)),
+            Err(error) => errors.push(LimaDirectiveConversionError { error, span }),
+        }
+    }
+    if errors.is_empty() {
+        Ok(directives)
+    } else {
+        Err(LimaLoadError::Conversion { errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let loader = LimaLoader::from(
+            "plugin \"beancount.plugins.auto_accounts\"\n\
+             2024-01-02 open Assets:Bank\n\
+             2024-01-01 open Assets:Cash\n",
+        );
+
+        let dates = loader.load(|ledger| {
+            let ledger = ledger.unwrap();
+            assert_eq!(ledger.plugins().len(), 1);
+            assert!(ledger.warnings().is_empty());
+            ledger
+                .directives()
+                .iter()
+                .map(|directive| directive.date().to_string())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(dates, ["2024-01-01", "2024-01-02"]);
+    }
+
+    #[test]
+    fn test_load_parse_error() {
+        let loader = LimaLoader::from("2024-01-01 open Assets:Cash\n2024-01-02 opne Assets:Bank\n");
+
+        let error_count = loader.load(|ledger| match ledger {
+            Err(LimaLoadError::Parse { errors, .. }) => errors.len(),
+            other => panic!("Expected a parse error, got {other:?}"),
+        });
+
+        assert_eq!(error_count, 1);
+    }
+
+    #[test]
+    fn test_load_conversion_errors() {
+        let loader = LimaLoader::from(
+            "2024-01-01 open Assets:Cash\n  receipt: ^receipt-7\n\
+             2024-01-02 open Assets:Bank\n\
+             2024-01-03 close Assets:Cash\n  receipt: ^receipt-8\n",
+        );
+
+        let errors = loader.load(|ledger| match ledger {
+            Err(LimaLoadError::Conversion { errors }) => errors
+                .iter()
+                .map(|error| {
+                    assert!(matches!(
+                        error.error(),
+                        LimaConversionError::UnsupportedMetadataValue(_)
+                    ));
+                    error.span().byte_range().start
+                })
+                .collect::<Vec<_>>(),
+            other => panic!("Expected conversion errors, got {other:?}"),
+        });
+
+        // every failing directive is reported, not just the first one
+        assert_eq!(errors, [0, 78]);
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let error = LimaLoader::read(Path::new("/nonexistent/main.beancount")).err();
+
+        assert!(matches!(error, Some(LimaLoadError::Io(_))));
+    }
+}
@@ -0,0 +1,129 @@
+use beancount_parser_lima::{MetaValue, SimpleValue};
+
+use crate::{
+    model::{Commodity, Metadata, MetadataKey, MetadataValue, Tag},
+    parser::lima::{date::date_into, error::LimaConversionError},
+};
+
+impl<'a, 'r> TryFrom<&'r beancount_parser_lima::Metadata<'a>> for Metadata<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(metadata: &'r beancount_parser_lima::Metadata<'a>) -> Result<Self, Self::Error> {
+        // lima stores key/values in a hash map, restore the source order from the spans
+        let mut key_values: Vec<_> = metadata.key_values().collect();
+        key_values.sort_by_key(|(key, _)| key.span().start);
+
+        key_values
+            .into_iter()
+            .map(|(key, value)| {
+                let key = MetadataKey::new(key.item().as_ref())?;
+                let value = value.item().try_into()?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
+
+impl<'a, 'r> TryFrom<&'r MetaValue<'a>> for MetadataValue<'a>
+where
+    'r: 'a,
+{
+    type Error = LimaConversionError<'a>;
+
+    fn try_from(value: &'r MetaValue<'a>) -> Result<Self, Self::Error> {
+        match value {
+            MetaValue::Simple(SimpleValue::String(string)) => {
+                Ok(MetadataValue::String((*string).into()))
+            }
+            MetaValue::Simple(SimpleValue::Expr(expr)) => Ok(MetadataValue::Number(expr.value())),
+            MetaValue::Simple(SimpleValue::Currency(currency)) => {
+                Ok(MetadataValue::Commodity(Commodity::try_from(currency)?))
+            }
+            MetaValue::Simple(SimpleValue::Bool(value)) => Ok(MetadataValue::Bool(*value)),
+            MetaValue::Simple(SimpleValue::Date(date)) => Ok(MetadataValue::Date(date_into(date))),
+            MetaValue::Simple(SimpleValue::Account(account)) => {
+                Ok(MetadataValue::Account(account.try_into()?))
+            }
+            MetaValue::Simple(SimpleValue::Tag(tag)) => {
+                Ok(MetadataValue::Tag(Tag::new(tag.as_ref())?))
+            }
+            MetaValue::Amount(amount) => Ok(MetadataValue::Amount(amount.try_into()?)),
+            _ => Err(LimaConversionError::UnsupportedMetadataValue(Box::new(
+                value.clone(),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources};
+    use rust_decimal_macros::dec;
+
+    use crate::model::{Amount, account, commodity};
+
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        let beancount_file = "2010-01-01 commodity USD\n  name: \"US Dollar\"\n  precision: 2\n  quote: EUR\n  export: TRUE\n  alpha: FALSE\n  since: 2009-12-31\n  parent: Assets:Cash\n  label: #fx\n  limit: 100 EUR\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let directive = parsed.directives.first().unwrap().item();
+
+        let metadata = Metadata::try_from(directive.metadata()).unwrap();
+
+        let keys: Vec<&str> = metadata.iter().map(|(k, _)| k.as_ref()).collect();
+        assert_eq!(
+            keys,
+            [
+                "name",
+                "precision",
+                "quote",
+                "export",
+                "alpha",
+                "since",
+                "parent",
+                "label",
+                "limit"
+            ]
+        );
+        assert_eq!(
+            metadata.get("name"),
+            Some(&MetadataValue::String("US Dollar".into()))
+        );
+        assert_eq!(
+            metadata.get("precision"),
+            Some(&MetadataValue::Number(dec!(2)))
+        );
+        assert_eq!(
+            metadata.get("quote"),
+            Some(&MetadataValue::Commodity(commodity!(EUR)))
+        );
+        assert_eq!(metadata.get("export"), Some(&MetadataValue::Bool(true)));
+        assert_eq!(metadata.get("alpha"), Some(&MetadataValue::Bool(false)));
+        assert_eq!(
+            metadata.get("since").and_then(MetadataValue::as_date),
+            chrono::NaiveDate::from_ymd_opt(2009, 12, 31).as_ref()
+        );
+        assert_eq!(
+            metadata.get("parent").and_then(MetadataValue::as_account),
+            Some(&account!(Assets:Cash))
+        );
+        assert_eq!(
+            metadata
+                .get("label")
+                .and_then(MetadataValue::as_tag)
+                .map(AsRef::as_ref),
+            Some("fx")
+        );
+        assert_eq!(
+            metadata.get("limit").and_then(MetadataValue::as_amount),
+            Some(&Amount::new(dec!(100), commodity!(EUR)))
+        );
+    }
+}
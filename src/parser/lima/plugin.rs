@@ -0,0 +1,33 @@
+use crate::model::PragmaPlugin;
+
+impl<'a, 'r> From<&'r beancount_parser_lima::Plugin<'a>> for PragmaPlugin<'a>
+where
+    'r: 'a,
+{
+    fn from(plugin: &'r beancount_parser_lima::Plugin<'a>) -> Self {
+        let pragma = PragmaPlugin::new(*plugin.module_name().item());
+        match plugin.config() {
+            Some(config) => pragma.with_config(*config.item()),
+            None => pragma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources};
+
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        // TODO Remove newline at end after https://github.com/tesujimath/beancount-parser-lima/issues/32 is fixed
+        let beancount_file = "plugin \"beancount.plugins.check_commodity\" \"USD\"\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+        let plugin = PragmaPlugin::from(parsed.plugins.first().unwrap());
+        assert_eq!("beancount.plugins.check_commodity", plugin.module_name());
+        assert_eq!(Some("USD"), plugin.config());
+    }
+}
@@ -0,0 +1,26 @@
+use chumsky_lima::span::Span as _;
+
+use crate::model::{FileId, SourceSpan};
+
+pub fn span_into(span: &beancount_parser_lima::Span) -> SourceSpan {
+    SourceSpan::new(FileId::new(span.context().into()), span.start..span.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use beancount_parser_lima::{BeancountParser, BeancountSources};
+
+    use super::*;
+
+    #[test]
+    fn test_span_into() {
+        let beancount_file = "; header\n2024-01-01 open Assets:Cash\n";
+        let beancount_file = BeancountSources::from(beancount_file);
+        let parser = BeancountParser::new(&beancount_file);
+        let parsed = parser.parse().unwrap();
+
+        let span = span_into(parsed.directives.first().unwrap().span());
+        assert_eq!(span.file_id(), FileId::default());
+        assert_eq!(span.byte_range().start, 9);
+    }
+}
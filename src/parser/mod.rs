@@ -1,5 +1,12 @@
 mod chumsky;
 mod lima;
+pub use lima::{
+    LimaConversionError, LimaDirectiveConversionError, LimaLedger, LimaLoadError, LimaLoader,
+    LimaPostingConversionError, LimaTransactionConversionError,
+};
 
-// TODO Remove, instead export a data loader style type
-pub use chumsky::{ParseResultExt, marshal_directive, parse_directive};
+pub(crate) use chumsky::format_error_in_file;
+pub use chumsky::{
+    ParseResultExt, marshal_directive, marshal_file, marshal_options, marshal_pragma,
+    parse_directive, parse_file, parse_pragma,
+};
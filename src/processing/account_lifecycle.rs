@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use ariadne::Report;
+use chrono::{Days, NaiveDate};
+use thiserror::Error;
+
+use crate::{
+    model::{Account, Directive, DirectiveVariant, SourceSpan},
+    processing::format_semantic_error,
+};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccountLifecycleErrorKind {
+    #[error("account was never opened")]
+    NeverOpened,
+
+    #[error("account is only opened on {0}")]
+    UsedBeforeOpen(NaiveDate),
+
+    #[error("account was closed on {0}")]
+    UsedAfterClose(NaiveDate),
+
+    #[error("account was already opened on {0}")]
+    DuplicateOpen(NaiveDate),
+
+    #[error("account was already closed on {0}")]
+    DuplicateClose(NaiveDate),
+
+    #[error("closing an account that was never opened")]
+    CloseOfUnopened,
+}
+
+/// An account referenced outside of the dates it's open, or opened or closed inconsistently
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid use of {account} on {date}: {kind}")]
+pub struct AccountLifecycleError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    account: Account<'a>,
+    kind: AccountLifecycleErrorKind,
+}
+
+impl<'a> AccountLifecycleError<'a> {
+    fn new(
+        directive: &Directive<'a>,
+        span: Option<&SourceSpan>,
+        account: &Account<'a>,
+        kind: AccountLifecycleErrorKind,
+    ) -> Self {
+        Self {
+            date: *directive.date(),
+            span: span.or(directive.span()).cloned(),
+            account: account.clone(),
+            kind,
+        }
+    }
+
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the posting or directive referencing the account, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    pub fn kind(&self) -> &AccountLifecycleErrorKind {
+        &self.kind
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Lifetime {
+    open: Option<NaiveDate>,
+    close: Option<NaiveDate>,
+}
+
+impl Lifetime {
+    /// Why the account can't be referenced on `date`, allowing for `grace_days` after closing
+    fn check(&self, date: NaiveDate, grace_days: u64) -> Option<AccountLifecycleErrorKind> {
+        let open = self.open?;
+        if date < open {
+            return Some(AccountLifecycleErrorKind::UsedBeforeOpen(open));
+        }
+        let close = self.close?;
+        (close + Days::new(grace_days) < date)
+            .then_some(AccountLifecycleErrorKind::UsedAfterClose(close))
+    }
+}
+
+/// Check that every account is referenced only while it's open, like beancount.
+///
+/// An account is open from the date of its `open` directive through the date of its `close`
+/// directive. Balance assertions may still reference it the day after closing, since they
+/// apply at the beginning of their date. Accounts may only be opened and closed once, and
+/// closed only if they're opened.
+pub fn check_account_lifecycles<'a>(
+    directives: &[Directive<'a>],
+) -> Vec<AccountLifecycleError<'a>> {
+    let mut order: Vec<&Directive<'a>> = directives.iter().collect();
+    order.sort_by_key(|directive| *directive.date());
+
+    let mut errors = Vec::new();
+    let mut lifetimes: HashMap<&Account<'a>, Lifetime> = HashMap::new();
+    for directive in &order {
+        if let DirectiveVariant::Open(open) = directive.content() {
+            let lifetime = lifetimes.entry(open.account()).or_default();
+            match lifetime.open {
+                Some(date) => errors.push(AccountLifecycleError::new(
+                    directive,
+                    None,
+                    open.account(),
+                    AccountLifecycleErrorKind::DuplicateOpen(date),
+                )),
+                None => lifetime.open = Some(*directive.date()),
+            }
+        }
+    }
+    for directive in &order {
+        if let DirectiveVariant::Close(close) = directive.content() {
+            match lifetimes.get_mut(close.account()) {
+                Some(lifetime) => match lifetime.close {
+                    Some(date) => errors.push(AccountLifecycleError::new(
+                        directive,
+                        None,
+                        close.account(),
+                        AccountLifecycleErrorKind::DuplicateClose(date),
+                    )),
+                    None => lifetime.close = Some(*directive.date()),
+                },
+                None => errors.push(AccountLifecycleError::new(
+                    directive,
+                    None,
+                    close.account(),
+                    AccountLifecycleErrorKind::CloseOfUnopened,
+                )),
+            }
+        }
+    }
+
+    for directive in order {
+        // accounts referenced by the directive, with the span of the posting if any,
+        // and the days the reference may come after the account was closed
+        let references: Vec<(&Account<'a>, Option<&SourceSpan>, u64)> = match directive.content() {
+            // duplicate closes are already reported above
+            DirectiveVariant::Close(close)
+                if lifetimes
+                    .get(close.account())
+                    .is_some_and(|lifetime| lifetime.close == Some(*directive.date())) =>
+            {
+                vec![(close.account(), None, 0)]
+            }
+            DirectiveVariant::Transaction(transaction) => transaction
+                .postings()
+                .iter()
+                .map(|posting| (posting.account(), posting.span(), 0))
+                .collect(),
+            DirectiveVariant::Balance(balance) => vec![(balance.account(), None, 1)],
+            DirectiveVariant::Pad(pad) => {
+                vec![(pad.account(), None, 0), (pad.source_account(), None, 0)]
+            }
+            DirectiveVariant::Note(note) => vec![(note.account(), None, 0)],
+            DirectiveVariant::Document(document) => vec![(document.account(), None, 0)],
+            _ => vec![],
+        };
+        for (account, span, grace_days) in references {
+            let kind = match lifetimes.get(account) {
+                Some(lifetime) => lifetime.check(*directive.date(), grace_days),
+                None => Some(AccountLifecycleErrorKind::NeverOpened),
+            };
+            if let Some(kind) = kind {
+                errors.push(AccountLifecycleError::new(directive, span, account, kind));
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        Amount, AmountWithTolerance, DirectiveBalance, DirectiveClose, DirectiveNote,
+        DirectiveOpen, DirectivePad, DirectiveTransaction, FileId, Flag, account, commodity,
+        directive::{Posting, PostingAmount},
+    };
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn open(day: u32, account: Account<'static>) -> Directive<'static> {
+        Directive::new_open(date(day), DirectiveOpen::new(account, Default::default()))
+    }
+
+    fn close(day: u32, account: Account<'static>) -> Directive<'static> {
+        Directive::new_close(date(day), DirectiveClose::new(account))
+    }
+
+    fn transfer(day: u32, from: Account<'static>, to: Account<'static>) -> Directive<'static> {
+        let amount = |number| PostingAmount::new(Amount::new(number, commodity!(USD)));
+        Directive::new_transaction(
+            date(day),
+            DirectiveTransaction::new(Flag::ASTERISK).with_postings(vec![
+                Posting::new(from, amount(dec!(-10))),
+                Posting::new(to, amount(dec!(10)))
+                    .with_span(SourceSpan::new(FileId::default(), 30..40)),
+            ]),
+        )
+        .with_span(SourceSpan::new(FileId::default(), 0..40))
+    }
+
+    fn balance(day: u32, account: Account<'static>) -> Directive<'static> {
+        let amount = AmountWithTolerance::without_tolerance(dec!(0), commodity!(USD));
+        Directive::new_balance(date(day), DirectiveBalance::new(account, amount))
+    }
+
+    fn kinds(
+        directives: &[Directive<'static>],
+    ) -> Vec<(Account<'static>, AccountLifecycleErrorKind)> {
+        check_account_lifecycles(directives)
+            .into_iter()
+            .map(|error| (error.account().clone(), error.kind().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn accounts_used_while_open() {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            open(1, account!(Expenses:Food)),
+            transfer(1, account!(Assets:Cash), account!(Expenses:Food)),
+            transfer(5, account!(Assets:Cash), account!(Expenses:Food)),
+            close(5, account!(Expenses:Food)),
+            balance(6, account!(Expenses:Food)),
+        ];
+
+        assert_eq!(check_account_lifecycles(&directives), []);
+    }
+
+    #[test]
+    fn account_never_opened() {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            transfer(2, account!(Assets:Cash), account!(Expenses:Food)),
+        ];
+
+        let errors = check_account_lifecycles(&directives);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].account(), &account!(Expenses:Food));
+        assert_eq!(errors[0].kind(), &AccountLifecycleErrorKind::NeverOpened);
+        // reported at the posting, not the whole transaction
+        assert_eq!(errors[0].span().unwrap().byte_range(), 30..40);
+        assert_eq!(
+            errors[0].to_string(),
+            "Invalid use of Expenses:Food on 2024-03-02: account was never opened"
+        );
+    }
+
+    #[test]
+    fn account_used_before_open() {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            transfer(2, account!(Expenses:Food), account!(Assets:Cash)),
+            open(3, account!(Expenses:Food)),
+        ];
+
+        let errors = check_account_lifecycles(&directives);
+
+        assert_eq!(
+            kinds(&directives),
+            [(
+                account!(Expenses:Food),
+                AccountLifecycleErrorKind::UsedBeforeOpen(date(3))
+            )]
+        );
+        // the posting has no span of its own, so the transaction's is used
+        assert_eq!(errors[0].span().unwrap().byte_range(), 0..40);
+    }
+
+    #[rstest]
+    #[case(transfer(6, account!(Assets:Cash), account!(Assets:Old)), true)]
+    #[case(balance(6, account!(Assets:Old)), false)]
+    #[case(balance(7, account!(Assets:Old)), true)]
+    #[case(Directive::new_note(date(6), DirectiveNote::new(account!(Assets:Old), "note")), true)]
+    #[case(Directive::new_pad(date(6), DirectivePad::new(account!(Assets:Cash), account!(Assets:Old))), true)]
+    fn account_used_after_close(#[case] directive: Directive<'static>, #[case] fails: bool) {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            open(1, account!(Assets:Old)),
+            close(5, account!(Assets:Old)),
+            directive,
+        ];
+
+        let expected = if fails {
+            vec![(
+                account!(Assets:Old),
+                AccountLifecycleErrorKind::UsedAfterClose(date(5)),
+            )]
+        } else {
+            vec![]
+        };
+        assert_eq!(kinds(&directives), expected);
+    }
+
+    #[test]
+    fn duplicate_open() {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            open(4, account!(Assets:Cash)),
+        ];
+
+        assert_eq!(
+            kinds(&directives),
+            [(
+                account!(Assets:Cash),
+                AccountLifecycleErrorKind::DuplicateOpen(date(1))
+            )]
+        );
+    }
+
+    #[test]
+    fn duplicate_close() {
+        let directives = vec![
+            open(1, account!(Assets:Cash)),
+            close(4, account!(Assets:Cash)),
+            close(6, account!(Assets:Cash)),
+        ];
+
+        assert_eq!(
+            kinds(&directives),
+            [(
+                account!(Assets:Cash),
+                AccountLifecycleErrorKind::DuplicateClose(date(4))
+            )]
+        );
+    }
+
+    #[test]
+    fn close_of_unopened_account() {
+        let directives = vec![close(4, account!(Assets:Cash))];
+
+        assert_eq!(
+            kinds(&directives),
+            [(
+                account!(Assets:Cash),
+                AccountLifecycleErrorKind::CloseOfUnopened
+            )]
+        );
+    }
+
+    #[test]
+    fn close_before_open() {
+        let directives = vec![
+            close(1, account!(Assets:Cash)),
+            open(4, account!(Assets:Cash)),
+        ];
+
+        assert_eq!(
+            kinds(&directives),
+            [(
+                account!(Assets:Cash),
+                AccountLifecycleErrorKind::UsedBeforeOpen(date(4))
+            )]
+        );
+    }
+}
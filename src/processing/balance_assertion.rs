@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+
+use ariadne::Report;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::{
+    model::{
+        Account, Amount, Commodity, Directive, DirectiveBalance, DirectiveVariant, Options,
+        SourceSpan, directive::PostingAmount,
+    },
+    processing::format_semantic_error,
+};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BalanceAssertionErrorKind<'a> {
+    /// The running balance of the account and its sub-accounts, and the actual minus the
+    /// expected balance
+    #[error(
+        "but was {} {commodity} ({} {commodity} difference)",
+        actual.number(),
+        difference.number(),
+        commodity = actual.commodity(),
+    )]
+    Mismatch {
+        actual: Amount<'a>,
+        difference: Amount<'a>,
+    },
+
+    #[error("but the running balance overflows")]
+    Overflow,
+}
+
+/// A balance assertion that doesn't match the running balance of its account
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error(
+    "Balance failed for {account} on {date}: expected {} {}, {kind}",
+    expected.number(),
+    expected.commodity()
+)]
+pub struct BalanceAssertionError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    account: Account<'a>,
+    expected: Amount<'a>,
+    kind: BalanceAssertionErrorKind<'a>,
+}
+
+impl<'a> BalanceAssertionError<'a> {
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the balance assertion, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    /// The asserted balance
+    pub fn expected(&self) -> &Amount<'a> {
+        &self.expected
+    }
+
+    pub fn kind(&self) -> &BalanceAssertionErrorKind<'a> {
+        &self.kind
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+/// Check every balance assertion against the running balance of its account, like beancount.
+///
+/// The balance of an account includes the postings to all its sub-accounts. Assertions apply at
+/// the beginning of their date, so transactions on the same date aren't included yet.
+/// Only postings with known units are counted, so transactions should be interpolated first,
+/// see [interpolate_transactions](crate::processing::interpolate_transactions).
+/// Assertions on a balance that overflows are reported as [BalanceAssertionErrorKind::Overflow].
+pub fn check_balance_assertions<'a>(
+    directives: &[Directive<'a>],
+    options: &Options,
+) -> Vec<BalanceAssertionError<'a>> {
+    let mut order: Vec<&Directive<'a>> = directives.iter().collect();
+    order.sort_by_key(|directive| {
+        let priority = match directive.content() {
+            DirectiveVariant::Balance(_) => 0,
+            _ => 1,
+        };
+        (*directive.date(), priority)
+    });
+
+    // None once the running balance has overflowed
+    let mut running_balances: HashMap<(Account<'a>, Commodity<'a>), Option<Decimal>> =
+        HashMap::new();
+    let mut errors = Vec::new();
+
+    for directive in order {
+        match directive.content() {
+            DirectiveVariant::Transaction(transaction) => {
+                for posting in transaction.postings() {
+                    if let Some(amount) = posting.amount().and_then(PostingAmount::amount) {
+                        let balance = running_balances
+                            .entry((posting.account().clone(), amount.commodity().clone()))
+                            .or_insert(Some(Decimal::ZERO));
+                        *balance =
+                            balance.and_then(|balance| balance.checked_add(*amount.number()));
+                    }
+                }
+            }
+            DirectiveVariant::Balance(balance) => {
+                let commodity = balance.amount_with_tolerance().commodity();
+                let actual = running_balances
+                    .iter()
+                    .filter(|((account, balance_commodity), _)| {
+                        balance_commodity == commodity && balance.account().contains(account)
+                    })
+                    .try_fold(Decimal::ZERO, |sum, (_, number)| {
+                        sum.checked_add((*number)?)
+                    });
+                let expected = *balance.amount_with_tolerance().number();
+                let difference =
+                    actual.and_then(|actual| Some((actual, actual.checked_sub(expected)?)));
+                let kind = match difference {
+                    Some((_, difference))
+                        if difference.abs() <= balance_tolerance(balance, options) =>
+                    {
+                        continue;
+                    }
+                    Some((actual, difference)) => BalanceAssertionErrorKind::Mismatch {
+                        actual: Amount::new(actual, commodity.clone()),
+                        difference: Amount::new(difference, commodity.clone()),
+                    },
+                    None => BalanceAssertionErrorKind::Overflow,
+                };
+                errors.push(BalanceAssertionError {
+                    date: *directive.date(),
+                    span: directive.span().cloned(),
+                    account: balance.account().clone(),
+                    expected: balance.amount_with_tolerance().amount().clone(),
+                    kind,
+                });
+            }
+            _ => {}
+        }
+    }
+    errors
+}
+
+/// The explicit `~` tolerance of the assertion, otherwise inferred from the precision of the
+/// asserted amount like in beancount: twice the `inferred_tolerance_multiplier` times its last
+/// digit, e.g. `0.01` for `12.34 USD`. Integer amounts must match exactly.
+fn balance_tolerance(balance: &DirectiveBalance, options: &Options) -> Decimal {
+    let amount = balance.amount_with_tolerance();
+    if let Some(tolerance) = amount.tolerance() {
+        return *tolerance;
+    }
+    let scale = amount.number().scale();
+    if scale == 0 {
+        return Decimal::ZERO;
+    }
+    Decimal::new(2, scale) * options.inferred_tolerance_multiplier()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        AmountWithTolerance, FileId, Flag, PragmaOption, account, commodity,
+        directive::{DirectiveTransaction, Posting},
+    };
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn transfer(
+        day: u32,
+        from: Account<'static>,
+        to: Account<'static>,
+        number: Decimal,
+    ) -> Directive<'static> {
+        let amount = |number| PostingAmount::new(Amount::new(number, commodity!(USD)));
+        Directive::new_transaction(
+            date(day),
+            DirectiveTransaction::new(Flag::ASTERISK).with_postings(vec![
+                Posting::new(from, amount(-number)),
+                Posting::new(to, amount(number)),
+            ]),
+        )
+    }
+
+    fn balance(
+        day: u32,
+        account: Account<'static>,
+        amount: AmountWithTolerance<'static>,
+    ) -> Directive<'static> {
+        Directive::new_balance(date(day), DirectiveBalance::new(account, amount))
+    }
+
+    fn usd(number: Decimal) -> AmountWithTolerance<'static> {
+        AmountWithTolerance::without_tolerance(number, commodity!(USD))
+    }
+
+    #[test]
+    fn passing_assertions() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                dec!(100.00),
+            ),
+            balance(2, account!(Assets:Cash), usd(dec!(100.00))),
+            transfer(
+                2,
+                account!(Assets:Cash),
+                account!(Expenses:Food),
+                dec!(12.50),
+            ),
+            balance(3, account!(Assets:Cash), usd(dec!(87.50))),
+            balance(3, account!(Expenses:Food), usd(dec!(12.50))),
+        ];
+
+        assert_eq!(check_balance_assertions(&directives, &Options::new()), []);
+    }
+
+    #[test]
+    fn assertion_applies_at_start_of_day() {
+        // the transaction on the same date isn't included, even if it comes first
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                dec!(100),
+            ),
+            transfer(2, account!(Assets:Cash), account!(Expenses:Food), dec!(20)),
+            balance(2, account!(Assets:Cash), usd(dec!(100))),
+        ];
+
+        assert_eq!(check_balance_assertions(&directives, &Options::new()), []);
+    }
+
+    #[test]
+    fn failing_assertion() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                dec!(100.00),
+            ),
+            balance(2, account!(Assets:Cash), usd(dec!(90.00)))
+                .with_span(SourceSpan::new(FileId::default(), 5..15)),
+        ];
+
+        let errors = check_balance_assertions(&directives, &Options::new());
+
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.account(), &account!(Assets:Cash));
+        assert_eq!(error.expected(), &Amount::new(dec!(90.00), commodity!(USD)));
+        assert_eq!(
+            error.kind(),
+            &BalanceAssertionErrorKind::Mismatch {
+                actual: Amount::new(dec!(100.00), commodity!(USD)),
+                difference: Amount::new(dec!(10.00), commodity!(USD)),
+            }
+        );
+        assert_eq!(error.span().unwrap().byte_range(), 5..15);
+        assert_eq!(
+            error.to_string(),
+            "Balance failed for Assets:Cash on 2024-03-02: expected 90.00 USD, but was 100.00 USD (10.00 USD difference)"
+        );
+    }
+
+    #[test]
+    fn assertion_includes_sub_accounts() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Bank:Checking),
+                dec!(100),
+            ),
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Bank:Savings),
+                dec!(50),
+            ),
+            transfer(1, account!(Equity:Opening), account!(Assets:Bank), dec!(5)),
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Banking),
+                dec!(1),
+            ),
+            balance(2, account!(Assets:Bank), usd(dec!(155))),
+            balance(2, account!(Assets:Bank:Savings), usd(dec!(50))),
+        ];
+
+        assert_eq!(check_balance_assertions(&directives, &Options::new()), []);
+    }
+
+    #[test]
+    fn assertion_per_commodity() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                dec!(100),
+            ),
+            balance(2, account!(Assets:Cash), usd(dec!(100))),
+            balance(
+                2,
+                account!(Assets:Cash),
+                AmountWithTolerance::without_tolerance(dec!(0), commodity!(EUR)),
+            ),
+        ];
+
+        assert_eq!(check_balance_assertions(&directives, &Options::new()), []);
+    }
+
+    #[rstest]
+    #[case(usd(dec!(100)), dec!(100.01), false)]
+    #[case(usd(dec!(100.00)), dec!(100.01), true)]
+    #[case(usd(dec!(100.00)), dec!(99.99), true)]
+    #[case(usd(dec!(100.00)), dec!(100.02), false)]
+    #[case(usd(dec!(100.0)), dec!(100.1), true)]
+    #[case(AmountWithTolerance::with_tolerance(dec!(100), dec!(0.5), commodity!(USD)), dec!(100.5), true)]
+    #[case(AmountWithTolerance::with_tolerance(dec!(100), dec!(0.5), commodity!(USD)), dec!(100.51), false)]
+    #[case(AmountWithTolerance::with_tolerance(dec!(100.00), dec!(0), commodity!(USD)), dec!(100.01), false)]
+    fn assertion_tolerance(
+        #[case] asserted: AmountWithTolerance<'static>,
+        #[case] actual: Decimal,
+        #[case] passes: bool,
+    ) {
+        let directives = vec![
+            transfer(1, account!(Equity:Opening), account!(Assets:Cash), actual),
+            balance(2, account!(Assets:Cash), asserted),
+        ];
+
+        assert_eq!(
+            check_balance_assertions(&directives, &Options::new()).is_empty(),
+            passes
+        );
+    }
+
+    #[test]
+    fn tolerance_multiplier_option() {
+        let options =
+            Options::from_pragmas([PragmaOption::new("inferred_tolerance_multiplier", "1.5")])
+                .unwrap();
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                dec!(100.03),
+            ),
+            balance(2, account!(Assets:Cash), usd(dec!(100.00))),
+        ];
+
+        assert_eq!(check_balance_assertions(&directives, &options), []);
+    }
+
+    #[test]
+    fn overflowing_balance() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                Decimal::MAX,
+            ),
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Cash),
+                Decimal::MAX,
+            ),
+            balance(2, account!(Assets:Cash), usd(dec!(100))),
+            balance(2, account!(Equity:Opening), usd(dec!(100))),
+        ];
+
+        let errors = check_balance_assertions(&directives, &Options::new());
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind(), &BalanceAssertionErrorKind::Overflow);
+        assert_eq!(
+            errors[0].to_string(),
+            "Balance failed for Assets:Cash on 2024-03-02: expected 100 USD, but the running balance overflows"
+        );
+        assert_eq!(errors[1].kind(), &BalanceAssertionErrorKind::Overflow);
+    }
+
+    #[test]
+    fn overflowing_sum_of_sub_accounts() {
+        let directives = vec![
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Bank:Checking),
+                Decimal::MAX,
+            ),
+            transfer(
+                1,
+                account!(Equity:Opening),
+                account!(Assets:Bank:Savings),
+                Decimal::MAX,
+            ),
+            balance(2, account!(Assets:Bank), usd(dec!(0))),
+            balance(2, account!(Assets:Bank:Savings), usd(Decimal::MIN)),
+        ];
+
+        let kinds: Vec<BalanceAssertionErrorKind> =
+            check_balance_assertions(&directives, &Options::new())
+                .into_iter()
+                .map(|error| error.kind().clone())
+                .collect();
+
+        // the difference MAX - MIN overflows as well
+        assert_eq!(
+            kinds,
+            [
+                BalanceAssertionErrorKind::Overflow,
+                BalanceAssertionErrorKind::Overflow
+            ]
+        );
+    }
+}
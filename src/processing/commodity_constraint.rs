@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use ariadne::Report;
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use crate::{
+    model::{Account, Commodity, Directive, DirectiveOpen, DirectiveVariant, SourceSpan},
+    processing::format_semantic_error,
+};
+
+/// A posting or balance assertion in a commodity its account's `open` directive doesn't allow
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error(
+    "Invalid commodity {commodity} for {account} on {date}, allowed: {}",
+    format_commodities(allowed)
+)]
+pub struct CommodityConstraintError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    account: Account<'a>,
+    commodity: Commodity<'a>,
+    allowed: Vec<Commodity<'a>>,
+}
+
+impl<'a> CommodityConstraintError<'a> {
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the posting or balance assertion, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    pub fn commodity(&self) -> &Commodity<'a> {
+        &self.commodity
+    }
+
+    /// The commodities declared for the account, in sorted order
+    pub fn allowed(&self) -> &[Commodity<'a>] {
+        &self.allowed
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+fn format_commodities(commodities: &[Commodity]) -> String {
+    commodities
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check that postings and balance assertions only use the commodities their account's `open`
+/// directive allows. Accounts opened without commodities accept any commodity, as do accounts
+/// that aren't opened at all, see [check_account_lifecycles](crate::processing::check_account_lifecycles).
+///
+/// Postings whose units don't have a commodity yet are skipped, so transactions should be
+/// interpolated first, see [interpolate_transactions](crate::processing::interpolate_transactions).
+pub fn check_commodity_constraints<'a>(
+    directives: &[Directive<'a>],
+) -> Vec<CommodityConstraintError<'a>> {
+    let mut opens: HashMap<&Account<'a>, &DirectiveOpen<'a>> = HashMap::new();
+    let mut order: Vec<&Directive<'a>> = directives.iter().collect();
+    order.sort_by_key(|directive| *directive.date());
+    for directive in &order {
+        if let DirectiveVariant::Open(open) = directive.content() {
+            opens.entry(open.account()).or_insert(open);
+        }
+    }
+
+    let mut errors = Vec::new();
+    for directive in directives {
+        // commodities used by the directive, with the span of the posting if any
+        let references: Vec<(&Account<'a>, &Commodity<'a>, Option<&SourceSpan>)> =
+            match directive.content() {
+                DirectiveVariant::Transaction(transaction) => transaction
+                    .postings()
+                    .iter()
+                    .filter_map(|posting| {
+                        let commodity = posting.amount()?.units().commodity()?;
+                        Some((posting.account(), commodity, posting.span()))
+                    })
+                    .collect(),
+                DirectiveVariant::Balance(balance) => vec![(
+                    balance.account(),
+                    balance.amount_with_tolerance().commodity(),
+                    None,
+                )],
+                _ => vec![],
+            };
+        for (account, commodity, span) in references {
+            let Some(open) = opens.get(account) else {
+                continue;
+            };
+            if open.commodity_constraints().len() == 0
+                || open
+                    .commodity_constraints()
+                    .any(|allowed| allowed == commodity)
+            {
+                continue;
+            }
+            let mut allowed: Vec<Commodity<'a>> = open.commodity_constraints().cloned().collect();
+            allowed.sort();
+            errors.push(CommodityConstraintError {
+                date: *directive.date(),
+                span: span.or(directive.span()).cloned(),
+                account: account.clone(),
+                commodity: commodity.clone(),
+                allowed,
+            });
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{
+            Amount, AmountWithTolerance, DirectiveBalance, DirectiveTransaction, FileId, Flag,
+            IncompleteAmount, account, commodity,
+            directive::{Posting, PostingAmount},
+        },
+        processing::interpolate_transactions,
+    };
+    use common_macros::hash_set;
+    use rust_decimal_macros::dec;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn open(
+        account: Account<'static>,
+        commodities: impl IntoIterator<Item = Commodity<'static>>,
+    ) -> Directive<'static> {
+        Directive::new_open(
+            date(1),
+            DirectiveOpen::new(account, commodities.into_iter().collect()),
+        )
+    }
+
+    fn transaction(postings: Vec<Posting<'static>>) -> Directive<'static> {
+        Directive::new_transaction(
+            date(2),
+            DirectiveTransaction::new(Flag::ASTERISK).with_postings(postings),
+        )
+        .with_span(SourceSpan::new(FileId::default(), 0..40))
+    }
+
+    fn posting(
+        account: Account<'static>,
+        units: impl Into<IncompleteAmount<'static>>,
+    ) -> Posting<'static> {
+        Posting::new(account, PostingAmount::new(units))
+    }
+
+    fn opens() -> Vec<Directive<'static>> {
+        vec![
+            open(account!(Assets:Cash), [commodity!(USD), commodity!(EUR)]),
+            open(account!(Expenses:Food), []),
+        ]
+    }
+
+    #[test]
+    fn allowed_commodities() {
+        let mut directives = opens();
+        directives.push(transaction(vec![
+            posting(
+                account!(Assets:Cash),
+                Amount::new(dec!(-10), commodity!(USD)),
+            ),
+            posting(
+                account!(Expenses:Food),
+                Amount::new(dec!(10), commodity!(USD)),
+            ),
+            posting(
+                account!(Assets:Cash),
+                Amount::new(dec!(-10), commodity!(EUR)),
+            ),
+            posting(
+                account!(Expenses:Food),
+                Amount::new(dec!(10), commodity!(EUR)),
+            ),
+        ]));
+        directives.push(transaction(vec![
+            posting(
+                account!(Expenses:Food),
+                Amount::new(dec!(1), commodity!(CHF)),
+            ),
+            posting(
+                account!(Expenses:Unknown),
+                Amount::new(dec!(-1), commodity!(CHF)),
+            ),
+        ]));
+
+        assert_eq!(check_commodity_constraints(&directives), []);
+    }
+
+    #[test]
+    fn posting_in_disallowed_commodity() {
+        let mut directives = opens();
+        directives.push(transaction(vec![
+            posting(
+                account!(Assets:Cash),
+                Amount::new(dec!(-10), commodity!(CHF)),
+            )
+            .with_span(SourceSpan::new(FileId::default(), 10..20)),
+            Posting::new_without_amount(account!(Expenses:Food)),
+        ]));
+
+        let errors = check_commodity_constraints(&directives);
+
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.account(), &account!(Assets:Cash));
+        assert_eq!(error.commodity(), &commodity!(CHF));
+        assert_eq!(error.allowed(), [commodity!(EUR), commodity!(USD)]);
+        assert_eq!(error.span().unwrap().byte_range(), 10..20);
+        assert_eq!(
+            error.to_string(),
+            "Invalid commodity CHF for Assets:Cash on 2024-03-02, allowed: EUR, USD"
+        );
+    }
+
+    #[test]
+    fn posting_with_missing_number() {
+        let mut directives = opens();
+        directives.push(transaction(vec![
+            posting(
+                account!(Assets:Cash),
+                Amount::new(dec!(-10), commodity!(USD)),
+            ),
+            posting(
+                account!(Assets:Cash),
+                IncompleteAmount::MissingNumber(commodity!(GBP)),
+            ),
+        ]));
+
+        let errors = check_commodity_constraints(&directives);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].commodity(), &commodity!(GBP));
+        // the posting has no span of its own, so the transaction's is used
+        assert_eq!(errors[0].span().unwrap().byte_range(), 0..40);
+    }
+
+    #[test]
+    fn interpolated_auto_posting() {
+        let mut directives = opens();
+        directives.push(transaction(vec![
+            posting(
+                account!(Expenses:Food),
+                Amount::new(dec!(10), commodity!(CHF)),
+            ),
+            Posting::new_without_amount(account!(Assets:Cash)),
+        ]));
+        // the auto posting has no commodity before interpolation
+        assert_eq!(check_commodity_constraints(&directives), []);
+
+        let (directives, interpolation_errors) = interpolate_transactions(directives);
+        assert_eq!(interpolation_errors, []);
+        let errors = check_commodity_constraints(&directives);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].account(), &account!(Assets:Cash));
+        assert_eq!(errors[0].commodity(), &commodity!(CHF));
+    }
+
+    #[test]
+    fn balance_in_disallowed_commodity() {
+        let mut directives = opens();
+        directives.push(Directive::new_balance(
+            date(3),
+            DirectiveBalance::new(
+                account!(Assets:Cash),
+                AmountWithTolerance::without_tolerance(dec!(0), commodity!(USD)),
+            ),
+        ));
+        directives.push(Directive::new_balance(
+            date(3),
+            DirectiveBalance::new(
+                account!(Assets:Cash),
+                AmountWithTolerance::without_tolerance(dec!(0), commodity!(CHF)),
+            ),
+        ));
+
+        let errors = check_commodity_constraints(&directives);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].commodity(), &commodity!(CHF));
+        assert_eq!(errors[0].date(), &date(3));
+    }
+
+    #[test]
+    fn first_open_applies() {
+        let directives = vec![
+            open(account!(Assets:Cash), hash_set![commodity!(USD)]),
+            Directive::new_open(
+                date(5),
+                DirectiveOpen::new(account!(Assets:Cash), hash_set![commodity!(CHF)]),
+            ),
+            transaction(vec![posting(
+                account!(Assets:Cash),
+                Amount::new(dec!(1), commodity!(CHF)),
+            )]),
+        ];
+
+        assert_eq!(check_commodity_constraints(&directives).len(), 1);
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use crate::model::{Directive, DirectiveVariant};
+
+/// Resolve relative paths of `document` directives against the directory of the ledger file
+/// that contained them. Absolute paths are left unchanged.
+pub fn resolve_document_paths<'a>(
+    directives: Vec<Directive<'a>>,
+    ledger_file: &Path,
+) -> Vec<Directive<'a>> {
+    let base_dir = ledger_file.parent().unwrap_or(Path::new(""));
+    directives
+        .into_iter()
+        .map(|mut directive| {
+            if let DirectiveVariant::Document(document) = directive.content_mut() {
+                document.resolve_path(base_dir);
+            }
+            directive
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DirectiveDocument, DirectiveNote, account};
+    use chrono::NaiveDate;
+
+    fn document(path: &str) -> Directive<'_> {
+        Directive::new_document(
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            DirectiveDocument::new(account!(Assets:Checking), Path::new(path)),
+        )
+    }
+
+    #[test]
+    fn resolves_relative_paths() {
+        let directives = vec![
+            document("statements/2024-01.pdf"),
+            document("/archive/2023-12.pdf"),
+        ];
+
+        let resolved =
+            resolve_document_paths(directives, Path::new("/home/user/ledger/main.beancount"));
+
+        assert_eq!(
+            resolved[0].as_document().unwrap().path(),
+            Path::new("/home/user/ledger/statements/2024-01.pdf")
+        );
+        assert_eq!(
+            resolved[1].as_document().unwrap().path(),
+            Path::new("/archive/2023-12.pdf")
+        );
+    }
+
+    #[test]
+    fn ledger_file_without_directory() {
+        let resolved = resolve_document_paths(
+            vec![document("statements/2024-01.pdf")],
+            Path::new("main.beancount"),
+        );
+
+        assert_eq!(
+            resolved[0].as_document().unwrap().path(),
+            Path::new("statements/2024-01.pdf")
+        );
+    }
+
+    #[test]
+    fn leaves_other_directives_unchanged() {
+        let note = Directive::new_note(
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            DirectiveNote::new(account!(Assets:Checking), "statements/2024-01.pdf"),
+        );
+
+        let resolved =
+            resolve_document_paths(vec![note.clone()], Path::new("/ledger/main.beancount"));
+
+        assert_eq!(resolved, vec![note]);
+    }
+}
@@ -0,0 +1,598 @@
+use std::collections::BTreeMap;
+
+use ariadne::Report;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::{
+    model::{
+        Amount, Commodity, Directive, DirectiveTransaction, DirectiveVariant, IncompleteAmount,
+        PostingPrice, SourceSpan,
+        directive::{Posting, PostingAmount},
+    },
+    processing::format_semantic_error,
+};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InterpolationErrorKind<'a> {
+    #[error("more than one posting without amount")]
+    MultipleAutoPostings,
+
+    #[error("more than one missing number in {0}")]
+    MultipleMissingNumbers(Commodity<'a>),
+
+    #[error("the missing numbers of posting #{} can't be inferred", index + 1)]
+    UnsupportedPosting { index: usize },
+}
+
+/// A transaction whose missing amounts couldn't be filled in
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Cannot interpolate transaction on {date}: {kind}")]
+pub struct InterpolationError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    kind: InterpolationErrorKind<'a>,
+}
+
+impl<'a> InterpolationError<'a> {
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the transaction, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn kind(&self) -> &InterpolationErrorKind<'a> {
+        &self.kind
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+/// Fill in the amounts left out of transactions, see [interpolate_transaction].
+/// Transactions that can't be interpolated are left unchanged and reported as errors.
+pub fn interpolate_transactions<'a>(
+    directives: Vec<Directive<'a>>,
+) -> (Vec<Directive<'a>>, Vec<InterpolationError<'a>>) {
+    let mut errors = Vec::new();
+    let directives = directives
+        .into_iter()
+        .map(|mut directive| {
+            let date = *directive.date();
+            let span = directive.span().cloned();
+            if let DirectiveVariant::Transaction(transaction) = directive.content_mut() {
+                match interpolate_transaction(transaction) {
+                    Ok(interpolated) => *transaction = interpolated,
+                    Err(kind) => errors.push(InterpolationError { date, span, kind }),
+                }
+            }
+            directive
+        })
+        .collect();
+    (directives, errors)
+}
+
+/// Fill in the amounts left out of a transaction, so that it balances, like beancount:
+/// - A posting without amount receives the residual of all other commodities, split into
+///   one posting per commodity. It's dropped if there's no residual.
+/// - A missing units number, per-unit cost (e.g. `{USD}`) or price (e.g. `@ USD`) is computed
+///   from the residual in its commodity.
+///
+/// Each commodity may only have one missing number, and a posting without amount counts as
+/// missing a number in every commodity, so it can't be combined with other missing numbers.
+/// Interpolated units are rounded to the precision of the other units in their commodity, and
+/// their postings are marked with [Posting::with_interpolated_units].
+pub fn interpolate_transaction<'a>(
+    transaction: &DirectiveTransaction<'a>,
+) -> Result<DirectiveTransaction<'a>, InterpolationErrorKind<'a>> {
+    let mut residual: BTreeMap<Commodity<'a>, Decimal> = BTreeMap::new();
+    let mut auto_posting = None;
+    let mut missing: BTreeMap<usize, (Commodity<'a>, MissingNumber)> = BTreeMap::new();
+
+    for (index, posting) in transaction.postings().iter().enumerate() {
+        let Some(amount) = posting.amount() else {
+            if auto_posting.replace(index).is_some() {
+                return Err(InterpolationErrorKind::MultipleAutoPostings);
+            }
+            continue;
+        };
+        if let Some(weight) = amount.weight() {
+            let sum = residual.entry(weight.commodity().clone()).or_default();
+            *sum = sum
+                .checked_add(*weight.number())
+                .ok_or(InterpolationErrorKind::UnsupportedPosting { index })?;
+            continue;
+        }
+        let (commodity, missing_number) = MissingNumber::classify(amount)
+            .ok_or(InterpolationErrorKind::UnsupportedPosting { index })?;
+        if missing.values().any(|(other, _)| *other == commodity) {
+            return Err(InterpolationErrorKind::MultipleMissingNumbers(commodity));
+        }
+        missing.insert(index, (commodity, missing_number));
+    }
+    // the residual in the commodity of a missing number could also go to the auto posting
+    if auto_posting.is_some()
+        && let Some((commodity, _)) = missing.values().next()
+    {
+        return Err(InterpolationErrorKind::MultipleMissingNumbers(
+            commodity.clone(),
+        ));
+    }
+    if auto_posting.is_none() && missing.is_empty() {
+        return Ok(transaction.clone());
+    }
+
+    let scales = units_scales(transaction);
+    let round = |number: Decimal, commodity: &Commodity| match scales.get(commodity) {
+        Some(scale) => number.round_dp(*scale),
+        None => number,
+    };
+    let mut postings = Vec::with_capacity(transaction.postings().len());
+    for (index, posting) in transaction.postings().iter().enumerate() {
+        if let Some((commodity, missing_number)) = missing.get(&index) {
+            let needed = -residual.get(commodity).copied().unwrap_or_default();
+            let amount = posting
+                .amount()
+                .expect("Posting with missing number has an amount");
+            let filled = missing_number
+                .fill(amount, needed, |number| round(number, commodity))
+                .ok_or(InterpolationErrorKind::UnsupportedPosting { index })?;
+            let filled = posting.clone().with_amount(filled);
+            postings.push(match missing_number {
+                MissingNumber::Units | MissingNumber::UnitsAt(_) => {
+                    filled.with_interpolated_units()
+                }
+                MissingNumber::PerUnitCost(_) | MissingNumber::Price(_) => filled,
+            });
+        } else if auto_posting == Some(index) {
+            for (commodity, number) in residual.iter().filter(|(_, number)| !number.is_zero()) {
+                let units = Amount::new(round(-*number, commodity), commodity.clone());
+                postings.push(
+                    posting
+                        .clone()
+                        .with_amount(PostingAmount::new(units))
+                        .with_interpolated_units(),
+                );
+            }
+        } else {
+            postings.push(posting.clone());
+        }
+    }
+    Ok(transaction.clone().with_postings(postings))
+}
+
+/// Kinds of missing numbers that can be interpolated from the residual of their commodity
+#[derive(Debug, Clone, Copy)]
+enum MissingNumber {
+    /// Units without cost or price, e.g. `Assets:Cash USD`
+    Units,
+    /// Units held at a known per-unit cost or converted at a known per-unit price
+    UnitsAt(Decimal),
+    /// Per-unit cost of known units, e.g. `10 HOOL {USD}`
+    PerUnitCost(Decimal),
+    /// Price of known units, e.g. `100 EUR @ USD`
+    Price(Decimal),
+}
+
+impl MissingNumber {
+    /// The missing number of a posting amount and the commodity of the weight it determines
+    fn classify<'a>(amount: &PostingAmount<'a>) -> Option<(Commodity<'a>, Self)> {
+        match (amount.units(), amount.cost(), amount.price()) {
+            (IncompleteAmount::MissingNumber(commodity), None, None) => {
+                Some((commodity.clone(), Self::Units))
+            }
+            (IncompleteAmount::MissingNumber(_), Some(cost), _) => {
+                let per_unit = cost
+                    .per_unit_cost()
+                    .filter(|_| cost.total_cost().is_none())?;
+                Some((cost.commodity()?.clone(), Self::UnitsAt(*per_unit)))
+            }
+            (IncompleteAmount::MissingNumber(_), None, Some(PostingPrice::PerUnit(price))) => {
+                let price = price.as_complete()?;
+                Some((price.commodity().clone(), Self::UnitsAt(*price.number())))
+            }
+            (IncompleteAmount::Complete(units), Some(cost), _) => {
+                if cost.per_unit_cost().is_some() || cost.total_cost().is_some() {
+                    return None;
+                }
+                Some((
+                    cost.commodity()?.clone(),
+                    Self::PerUnitCost(*units.number()),
+                ))
+            }
+            (IncompleteAmount::Complete(units), None, Some(price)) => match price.amount() {
+                IncompleteAmount::MissingNumber(commodity) => {
+                    Some((commodity.clone(), Self::Price(*units.number())))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Fill in the missing number so that the weight of the posting amount is `needed`
+    fn fill<'a>(
+        self,
+        amount: &PostingAmount<'a>,
+        needed: Decimal,
+        round_units: impl Fn(Decimal) -> Decimal,
+    ) -> Option<PostingAmount<'a>> {
+        let with_units = |number: Decimal| {
+            let units = Amount::new(number, amount.units().commodity()?.clone());
+            let mut filled = PostingAmount::new(units);
+            if let Some(cost) = amount.cost() {
+                filled = filled.with_cost(cost.clone());
+            }
+            if let Some(price) = amount.price() {
+                filled = filled.with_price(price.clone());
+            }
+            Some(filled)
+        };
+        match self {
+            Self::Units => with_units(round_units(needed)),
+            Self::UnitsAt(per_unit) => with_units(needed.checked_div(per_unit)?),
+            Self::PerUnitCost(units) => {
+                let cost = amount
+                    .cost()?
+                    .clone()
+                    .with_per_unit_cost(needed.checked_div(units)?);
+                let mut filled = PostingAmount::new(amount.units().clone()).with_cost(cost);
+                if let Some(price) = amount.price() {
+                    filled = filled.with_price(price.clone());
+                }
+                Some(filled)
+            }
+            Self::Price(units) => {
+                let price = match amount.price()? {
+                    PostingPrice::PerUnit(price) => PostingPrice::PerUnit(
+                        Amount::new(needed.checked_div(units)?, price.commodity()?.clone()).into(),
+                    ),
+                    // total prices carry the sign of the units in the weight
+                    PostingPrice::Total(price) => {
+                        let total = if units.is_sign_negative() {
+                            -needed
+                        } else {
+                            needed
+                        };
+                        PostingPrice::Total(Amount::new(total, price.commodity()?.clone()).into())
+                    }
+                };
+                Some(PostingAmount::new(amount.units().clone()).with_price(price))
+            }
+        }
+    }
+}
+
+/// Largest number of decimal places of the given units per commodity.
+/// Commodities with only integer units aren't included, so they're not rounded.
+fn units_scales<'a>(transaction: &DirectiveTransaction<'a>) -> BTreeMap<Commodity<'a>, u32> {
+    let mut scales: BTreeMap<Commodity<'a>, u32> = BTreeMap::new();
+    for units in transaction
+        .postings()
+        .iter()
+        .filter_map(Posting::amount)
+        .filter_map(PostingAmount::amount)
+    {
+        if units.number().scale() > 0 {
+            let scale = scales.entry(units.commodity().clone()).or_default();
+            *scale = (*scale).max(units.number().scale());
+        }
+    }
+    scales
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Account, CostSpec, FileId, Flag, Options, account, commodity};
+    use crate::processing::check_transaction_balances;
+    use rust_decimal_macros::dec;
+
+    fn posting(account: Account<'static>, amount: PostingAmount<'static>) -> Posting<'static> {
+        Posting::new(account, amount)
+    }
+
+    fn units(number: Decimal, commodity: Commodity<'static>) -> PostingAmount<'static> {
+        PostingAmount::new(Amount::new(number, commodity))
+    }
+
+    fn transaction(postings: Vec<Posting<'static>>) -> DirectiveTransaction<'static> {
+        DirectiveTransaction::new(Flag::ASTERISK).with_postings(postings)
+    }
+
+    fn weights(transaction: &DirectiveTransaction<'static>) -> Vec<Option<Amount<'static>>> {
+        transaction
+            .postings()
+            .iter()
+            .map(|posting| posting.amount().and_then(PostingAmount::weight))
+            .collect()
+    }
+
+    #[test]
+    fn interpolate_auto_posting() {
+        // 2024-01-05 * "Cafe Mogador" "Lamb tagine with wine"
+        let transaction = transaction(vec![
+            posting(
+                account!(Liabilities:CreditCard),
+                units(dec!(-37.45), commodity!(USD)),
+            ),
+            Posting::new_without_amount(account!(Expenses:Restaurant)).with_flag(Flag::EXCLAMATION),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            interpolated.postings()[1],
+            Posting::new(
+                account!(Expenses:Restaurant),
+                units(dec!(37.45), commodity!(USD))
+            )
+            .with_flag(Flag::EXCLAMATION)
+            .with_interpolated_units()
+        );
+    }
+
+    #[test]
+    fn interpolate_auto_posting_per_commodity() {
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-10.00), commodity!(USD))),
+            posting(account!(Assets:Cash), units(dec!(-5.00), commodity!(EUR))),
+            posting(account!(Assets:Cash), units(dec!(5.00), commodity!(EUR))),
+            posting(account!(Assets:Cash), units(dec!(-2), commodity!(CHF))),
+            Posting::new_without_amount(account!(Expenses:Travel)),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            interpolated.postings()[4..],
+            [
+                posting(account!(Expenses:Travel), units(dec!(2), commodity!(CHF)))
+                    .with_interpolated_units(),
+                posting(
+                    account!(Expenses:Travel),
+                    units(dec!(10.00), commodity!(USD))
+                )
+                .with_interpolated_units(),
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolate_auto_posting_rounds_to_units_precision() {
+        let transaction = transaction(vec![
+            posting(
+                account!(Assets:Cash),
+                units(dec!(-100.00), commodity!(EUR)).with_price(PostingPrice::PerUnit(
+                    Amount::new(dec!(1.08765), commodity!(USD)).into(),
+                )),
+            ),
+            posting(account!(Expenses:Fees), units(dec!(1.50), commodity!(USD))),
+            Posting::new_without_amount(account!(Assets:Bank)),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            interpolated.postings()[2],
+            posting(account!(Assets:Bank), units(dec!(107.26), commodity!(USD)))
+                .with_interpolated_units()
+        );
+        let directive =
+            Directive::new_transaction(NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(), interpolated);
+        assert!(check_transaction_balances(&[directive], &Options::new()).is_empty());
+    }
+
+    #[test]
+    fn interpolate_missing_units_number() {
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            posting(
+                account!(Expenses:Food),
+                PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(USD))),
+            ),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            weights(&interpolated)[1],
+            Some(Amount::new(dec!(20.00), commodity!(USD)))
+        );
+        assert!(interpolated.postings()[1].has_interpolated_units());
+    }
+
+    #[test]
+    fn interpolate_missing_units_at_cost() {
+        let transaction = transaction(vec![
+            posting(
+                account!(Assets:Stock),
+                PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(HOOL)))
+                    .with_cost(CostSpec::per_unit(dec!(500), commodity!(USD))),
+            ),
+            posting(account!(Assets:Cash), units(dec!(-5000), commodity!(USD))),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            interpolated.postings()[0].amount().unwrap().amount(),
+            Some(&Amount::new(dec!(10), commodity!(HOOL)))
+        );
+    }
+
+    #[test]
+    fn interpolate_missing_per_unit_cost() {
+        let transaction = transaction(vec![
+            posting(
+                account!(Assets:Stock),
+                units(dec!(10), commodity!(HOOL))
+                    .with_cost(CostSpec::new().with_commodity(commodity!(USD))),
+            ),
+            posting(
+                account!(Assets:Cash),
+                units(dec!(-5021.20), commodity!(USD)),
+            ),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        let cost = interpolated.postings()[0].amount().unwrap().cost().unwrap();
+        assert_eq!(cost.per_unit_cost(), Some(&dec!(502.12)));
+        assert_eq!(cost.commodity(), Some(&commodity!(USD)));
+    }
+
+    #[test]
+    fn interpolate_missing_prices() {
+        let transaction = transaction(vec![
+            posting(
+                account!(Assets:Cash),
+                units(dec!(-100), commodity!(EUR)).with_price(PostingPrice::PerUnit(
+                    IncompleteAmount::MissingNumber(commodity!(USD)),
+                )),
+            ),
+            posting(account!(Assets:Bank), units(dec!(110.00), commodity!(USD))),
+            posting(
+                account!(Assets:Cash),
+                units(dec!(-50), commodity!(CHF)).with_price(PostingPrice::Total(
+                    IncompleteAmount::MissingNumber(commodity!(GBP)),
+                )),
+            ),
+            posting(account!(Assets:Bank), units(dec!(45.00), commodity!(GBP))),
+        ]);
+
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+
+        assert_eq!(
+            interpolated.postings()[0].amount().unwrap().price(),
+            Some(&PostingPrice::PerUnit(
+                Amount::new(dec!(1.1), commodity!(USD)).into()
+            ))
+        );
+        assert_eq!(
+            interpolated.postings()[2].amount().unwrap().price(),
+            Some(&PostingPrice::Total(
+                Amount::new(dec!(45.00), commodity!(GBP)).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn interpolate_complete_transaction_unchanged() {
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            posting(account!(Expenses:Food), units(dec!(20.00), commodity!(USD))),
+        ]);
+
+        assert_eq!(interpolate_transaction(&transaction), Ok(transaction));
+    }
+
+    #[test]
+    fn multiple_auto_postings() {
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            Posting::new_without_amount(account!(Expenses:Food)),
+            Posting::new_without_amount(account!(Expenses:Drinks)),
+        ]);
+
+        assert_eq!(
+            interpolate_transaction(&transaction),
+            Err(InterpolationErrorKind::MultipleAutoPostings)
+        );
+    }
+
+    #[test]
+    fn multiple_missing_numbers_in_commodity() {
+        let missing = || PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(USD)));
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            posting(account!(Expenses:Food), missing()),
+            posting(account!(Expenses:Drinks), missing()),
+        ]);
+
+        assert_eq!(
+            interpolate_transaction(&transaction),
+            Err(InterpolationErrorKind::MultipleMissingNumbers(commodity!(
+                USD
+            )))
+        );
+    }
+
+    #[test]
+    fn auto_posting_with_missing_number_in_commodity() {
+        let transaction = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            posting(
+                account!(Expenses:Food),
+                PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(USD))),
+            ),
+            Posting::new_without_amount(account!(Expenses:Drinks)),
+        ]);
+
+        assert_eq!(
+            interpolate_transaction(&transaction),
+            Err(InterpolationErrorKind::MultipleMissingNumbers(commodity!(
+                USD
+            )))
+        );
+    }
+
+    #[test]
+    fn unsupported_missing_numbers() {
+        let transaction = transaction(vec![
+            posting(
+                account!(Assets:Stock),
+                units(dec!(-10), commodity!(HOOL)).with_cost(CostSpec::new()),
+            ),
+            Posting::new_without_amount(account!(Assets:Cash)),
+        ]);
+
+        assert_eq!(
+            interpolate_transaction(&transaction),
+            Err(InterpolationErrorKind::UnsupportedPosting { index: 0 })
+        );
+    }
+
+    #[test]
+    fn interpolate_directives() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        let valid = transaction(vec![
+            posting(account!(Assets:Cash), units(dec!(-20.00), commodity!(USD))),
+            Posting::new_without_amount(account!(Expenses:Food)),
+        ]);
+        let invalid = transaction(vec![
+            Posting::new_without_amount(account!(Expenses:Food)),
+            Posting::new_without_amount(account!(Expenses:Drinks)),
+        ]);
+        let directives = vec![
+            Directive::new_transaction(date, valid.clone()),
+            Directive::new_transaction(date, invalid.clone())
+                .with_span(SourceSpan::new(FileId::default(), 10..20)),
+        ];
+
+        let (directives, errors) = interpolate_transactions(directives);
+
+        assert_eq!(
+            directives[0].as_transaction(),
+            Some(&interpolate_transaction(&valid).unwrap())
+        );
+        assert_eq!(directives[1].as_transaction(), Some(&invalid));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind(),
+            &InterpolationErrorKind::MultipleAutoPostings
+        );
+        assert_eq!(errors[0].span().unwrap().byte_range(), 10..20);
+        assert_eq!(
+            errors[0].to_string(),
+            "Cannot interpolate transaction on 2024-01-05: more than one posting without amount"
+        );
+    }
+}
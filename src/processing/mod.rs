@@ -0,0 +1,26 @@
+mod account_lifecycle;
+mod balance_assertion;
+mod commodity_constraint;
+mod document;
+mod interpolation;
+mod pad;
+mod report;
+mod transaction_balance;
+
+pub use account_lifecycle::{
+    AccountLifecycleError, AccountLifecycleErrorKind, check_account_lifecycles,
+};
+pub use balance_assertion::{
+    BalanceAssertionError, BalanceAssertionErrorKind, check_balance_assertions,
+};
+pub use commodity_constraint::{CommodityConstraintError, check_commodity_constraints};
+pub use document::resolve_document_paths;
+pub use interpolation::{
+    InterpolationError, InterpolationErrorKind, interpolate_transaction, interpolate_transactions,
+};
+pub use pad::{PadError, resolve_pads};
+pub use report::format_semantic_error;
+pub use transaction_balance::{
+    Tolerances, UnbalancedTransactionError, UnbalancedTransactionErrorKind,
+    check_transaction_balances, infer_tolerances,
+};
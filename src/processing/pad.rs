@@ -0,0 +1,448 @@
+use std::collections::{HashMap, HashSet};
+
+use ariadne::Report;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::{
+    model::{
+        Account, Amount, Commodity, Directive, DirectiveTransaction, DirectiveVariant, Flag,
+        SourceSpan,
+        directive::{Posting, PostingAmount, TransactionDescription},
+    },
+    processing::format_semantic_error,
+};
+
+/// A pad directive whose padding can't be computed, because the running balance overflows
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Cannot pad {account} on {date}: the balance in {commodity} overflows")]
+pub struct PadError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    account: Account<'a>,
+    commodity: Commodity<'a>,
+}
+
+impl<'a> PadError<'a> {
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the pad directive, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn account(&self) -> &Account<'a> {
+        &self.account
+    }
+
+    pub fn commodity(&self) -> &Commodity<'a> {
+        &self.commodity
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+/// Insert the padding transactions implied by `pad` directives.
+///
+/// Like upstream beancount, a pad directive pads its account once per commodity, using the
+/// first balance assertion on that account after the pad (and before the next pad on it).
+/// The synthesized transaction is flagged [Flag::PADDING], dated like the pad, and inserted
+/// directly after it. Pads that don't end up being needed are left without a transaction.
+///
+/// Running balances only include postings with an explicit amount. Pads whose padding
+/// can't be computed because a running balance overflows are reported as errors.
+pub fn resolve_pads<'a>(directives: Vec<Directive<'a>>) -> (Vec<Directive<'a>>, Vec<PadError<'a>>) {
+    let (paddings, errors) = compute_paddings(&directives);
+
+    let mut result = Vec::with_capacity(directives.len() + paddings.len());
+    let mut paddings_by_pad: HashMap<usize, Vec<Directive<'a>>> = HashMap::new();
+    for (pad_index, padding) in paddings {
+        paddings_by_pad.entry(pad_index).or_default().push(padding);
+    }
+    for (index, directive) in directives.into_iter().enumerate() {
+        result.push(directive);
+        if let Some(paddings) = paddings_by_pad.remove(&index) {
+            result.extend(paddings);
+        }
+    }
+    (result, errors)
+}
+
+struct ActivePad<'a> {
+    index: usize,
+    padded_commodities: HashSet<Commodity<'a>>,
+}
+
+/// Returns the padding transactions to insert, each with the index of the pad directive it belongs to
+fn compute_paddings<'a>(
+    directives: &[Directive<'a>],
+) -> (Vec<(usize, Directive<'a>)>, Vec<PadError<'a>>) {
+    // Balance assertions apply at the beginning of the day, so they're checked before anything else on that date
+    let mut order: Vec<usize> = (0..directives.len()).collect();
+    order.sort_by_key(|&index| {
+        let directive = &directives[index];
+        let priority = match directive.content() {
+            DirectiveVariant::Balance(_) => 0,
+            _ => 1,
+        };
+        (*directive.date(), priority)
+    });
+
+    // None once the running balance has overflowed
+    let mut running_balances: HashMap<(Account<'a>, Commodity<'a>), Option<Decimal>> =
+        HashMap::new();
+    let mut active_pads: HashMap<Account<'a>, ActivePad<'a>> = HashMap::new();
+    let mut paddings = Vec::new();
+    let mut errors = Vec::new();
+
+    for index in order {
+        let directive = &directives[index];
+        match directive.content() {
+            DirectiveVariant::Transaction(transaction) => {
+                for posting in transaction.postings() {
+                    if let Some(amount) = posting.amount().and_then(PostingAmount::amount) {
+                        let balance = running_balances
+                            .entry((posting.account().clone(), amount.commodity().clone()))
+                            .or_insert(Some(Decimal::ZERO));
+                        *balance =
+                            balance.and_then(|balance| balance.checked_add(*amount.number()));
+                    }
+                }
+            }
+            DirectiveVariant::Pad(pad) => {
+                active_pads.insert(
+                    pad.account().clone(),
+                    ActivePad {
+                        index,
+                        padded_commodities: HashSet::new(),
+                    },
+                );
+            }
+            DirectiveVariant::Balance(balance) => {
+                let Some(active_pad) = active_pads.get_mut(balance.account()) else {
+                    continue;
+                };
+                let commodity = balance.amount_with_tolerance().commodity();
+                if !active_pad.padded_commodities.insert(commodity.clone()) {
+                    continue;
+                }
+
+                let pad_directive = &directives[active_pad.index];
+                let pad = pad_directive
+                    .as_pad()
+                    .expect("Active pad must be a pad directive");
+                let current = running_balances
+                    .get(&(balance.account().clone(), commodity.clone()))
+                    .copied()
+                    .unwrap_or(Some(Decimal::ZERO));
+                let expected = *balance.amount_with_tolerance().number();
+                let Some(difference) = current.and_then(|current| expected.checked_sub(current))
+                else {
+                    errors.push(PadError {
+                        date: *pad_directive.date(),
+                        span: pad_directive.span().cloned(),
+                        account: pad.account().clone(),
+                        commodity: commodity.clone(),
+                    });
+                    continue;
+                };
+                let tolerance = balance
+                    .amount_with_tolerance()
+                    .tolerance()
+                    .copied()
+                    .unwrap_or_default();
+                if difference.abs() <= tolerance {
+                    continue;
+                }
+
+                let padding = padding_transaction(
+                    pad.account(),
+                    pad.source_account(),
+                    Amount::new(expected, commodity.clone()),
+                    Amount::new(difference, commodity.clone()),
+                );
+
+                running_balances.insert((pad.account().clone(), commodity.clone()), Some(expected));
+                let source_balance = running_balances
+                    .entry((pad.source_account().clone(), commodity.clone()))
+                    .or_insert(Some(Decimal::ZERO));
+                *source_balance =
+                    source_balance.and_then(|balance| balance.checked_sub(difference));
+
+                // the padding transaction points back at the pad directive that created it
+                let mut padding = Directive::new_transaction(*pad_directive.date(), padding);
+                if let Some(span) = pad_directive.span() {
+                    padding = padding.with_span(span.clone());
+                }
+                paddings.push((active_pad.index, padding));
+            }
+            _ => {}
+        }
+    }
+
+    (paddings, errors)
+}
+
+fn padding_transaction<'a>(
+    account: &Account<'a>,
+    source_account: &Account<'a>,
+    expected: Amount<'a>,
+    difference: Amount<'a>,
+) -> DirectiveTransaction<'a> {
+    let narration = format!(
+        "(Padding inserted for Balance of {} {} for difference {} {})",
+        expected.number(),
+        expected.commodity(),
+        difference.number(),
+        difference.commodity(),
+    );
+    let source_amount = Amount::new(-*difference.number(), difference.commodity().clone());
+
+    DirectiveTransaction::new(Flag::PADDING)
+        .with_description(TransactionDescription::new_without_payee(narration))
+        .with_posting(Posting::new(
+            account.clone(),
+            PostingAmount::new(difference),
+        ))
+        .with_posting(Posting::new(
+            source_account.clone(),
+            PostingAmount::new(source_amount),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AmountWithTolerance, DirectiveBalance, DirectivePad, account, commodity};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn pad(day: u32) -> Directive<'static> {
+        Directive::new_pad(
+            date(day),
+            DirectivePad::new(account!(Assets:Checking), account!(Equity:OpeningBalances)),
+        )
+    }
+
+    fn balance(day: u32, number: Decimal, commodity: Commodity<'static>) -> Directive<'static> {
+        Directive::new_balance(
+            date(day),
+            DirectiveBalance::new(
+                account!(Assets:Checking),
+                AmountWithTolerance::without_tolerance(number, commodity),
+            ),
+        )
+    }
+
+    fn deposit(day: u32, number: Decimal) -> Directive<'static> {
+        Directive::new_transaction(
+            date(day),
+            DirectiveTransaction::new(Flag::ASTERISK)
+                .with_posting(Posting::new(
+                    account!(Assets:Checking),
+                    PostingAmount::new(Amount::new(number, commodity!(USD))),
+                ))
+                .with_posting(Posting::new(
+                    account!(Income:Salary),
+                    PostingAmount::new(Amount::new(-number, commodity!(USD))),
+                )),
+        )
+    }
+
+    fn resolve(directives: Vec<Directive<'static>>) -> Vec<Directive<'static>> {
+        let (resolved, errors) = resolve_pads(directives);
+        assert_eq!(errors, []);
+        resolved
+    }
+
+    fn padded_amounts<'a>(directive: &Directive<'a>) -> Vec<(Account<'a>, Decimal)> {
+        let transaction = directive.as_transaction().unwrap();
+        assert_eq!(*transaction.flag(), Flag::PADDING);
+        transaction
+            .postings()
+            .iter()
+            .map(|posting| {
+                (
+                    posting.account().clone(),
+                    *posting.amount().unwrap().amount().unwrap().number(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pad_inserts_transaction_after_pad() {
+        let directives = vec![pad(1), balance(2, dec!(1000), commodity!(USD))];
+
+        let resolved = resolve(directives);
+
+        assert_eq!(resolved.len(), 3);
+        assert!(resolved[0].as_pad().is_some());
+        assert_eq!(resolved[1].date(), &date(1));
+        assert_eq!(
+            padded_amounts(&resolved[1]),
+            [
+                (account!(Assets:Checking), dec!(1000)),
+                (account!(Equity:OpeningBalances), dec!(-1000)),
+            ]
+        );
+        assert_eq!(
+            resolved[1]
+                .as_transaction()
+                .unwrap()
+                .description()
+                .unwrap()
+                .narration(),
+            "(Padding inserted for Balance of 1000 USD for difference 1000 USD)"
+        );
+        assert!(resolved[2].as_balance().is_some());
+    }
+
+    #[test]
+    fn pad_accounts_for_existing_postings() {
+        let directives = vec![
+            deposit(1, dec!(300)),
+            pad(2),
+            deposit(3, dec!(200)),
+            balance(4, dec!(1000), commodity!(USD)),
+        ];
+
+        let resolved = resolve(directives);
+
+        assert_eq!(resolved.len(), 5);
+        assert_eq!(
+            padded_amounts(&resolved[2]),
+            [
+                (account!(Assets:Checking), dec!(500)),
+                (account!(Equity:OpeningBalances), dec!(-500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pad_not_needed() {
+        let directives = vec![
+            pad(1),
+            deposit(1, dec!(1000)),
+            balance(2, dec!(1000), commodity!(USD)),
+        ];
+
+        let resolved = resolve(directives.clone());
+
+        assert_eq!(resolved, directives);
+    }
+
+    #[test]
+    fn pad_only_used_by_first_balance() {
+        let directives = vec![
+            pad(1),
+            balance(2, dec!(1000), commodity!(USD)),
+            balance(3, dec!(2000), commodity!(USD)),
+        ];
+
+        let resolved = resolve(directives);
+
+        // The second balance assertion is not padded and will fail
+        assert_eq!(resolved.len(), 4);
+        assert!(resolved[1].as_transaction().is_some());
+        assert!(resolved[2].as_balance().is_some());
+        assert!(resolved[3].as_balance().is_some());
+    }
+
+    #[test]
+    fn pad_once_per_commodity() {
+        let directives = vec![
+            pad(1),
+            balance(2, dec!(1000), commodity!(USD)),
+            balance(2, dec!(50), commodity!(EUR)),
+        ];
+
+        let resolved = resolve(directives);
+
+        assert_eq!(resolved.len(), 5);
+        let commodities: Vec<&str> = resolved[1..3]
+            .iter()
+            .map(|d| {
+                d.as_transaction().unwrap().postings()[0]
+                    .amount()
+                    .unwrap()
+                    .amount()
+                    .unwrap()
+                    .commodity()
+                    .as_ref()
+            })
+            .collect();
+        assert_eq!(commodities, ["USD", "EUR"]);
+    }
+
+    #[test]
+    fn balance_on_pad_date_is_not_padded() {
+        let directives = vec![pad(1), balance(1, dec!(1000), commodity!(USD))];
+
+        let resolved = resolve(directives.clone());
+
+        assert_eq!(resolved, directives);
+    }
+
+    #[test]
+    fn later_pad_replaces_earlier_one() {
+        let directives = vec![pad(1), pad(2), balance(3, dec!(1000), commodity!(USD))];
+
+        let resolved = resolve(directives);
+
+        assert_eq!(resolved.len(), 4);
+        assert!(resolved[1].as_pad().is_some());
+        assert_eq!(resolved[2].date(), &date(2));
+    }
+
+    #[test]
+    fn balance_within_tolerance_is_not_padded() {
+        let directives = vec![
+            deposit(1, dec!(999.99)),
+            pad(1),
+            Directive::new_balance(
+                date(2),
+                DirectiveBalance::new(
+                    account!(Assets:Checking),
+                    AmountWithTolerance::with_tolerance(dec!(1000), dec!(0.01), commodity!(USD)),
+                ),
+            ),
+        ];
+
+        let resolved = resolve(directives.clone());
+
+        assert_eq!(resolved, directives);
+    }
+
+    #[test]
+    fn overflowing_balance_is_reported() {
+        let directives = vec![
+            deposit(1, Decimal::MAX),
+            deposit(1, Decimal::MAX),
+            pad(2),
+            balance(3, dec!(1000), commodity!(USD)),
+        ];
+
+        let (resolved, errors) = resolve_pads(directives.clone());
+
+        assert_eq!(resolved, directives);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].account(), &account!(Assets:Checking));
+        assert_eq!(errors[0].commodity(), &commodity!(USD));
+        assert_eq!(
+            errors[0].to_string(),
+            "Cannot pad Assets:Checking on 2024-01-02: the balance in USD overflows"
+        );
+    }
+}
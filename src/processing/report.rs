@@ -0,0 +1,45 @@
+use ariadne::{Config, IndexType, Label, Report, ReportKind};
+
+use crate::model::SourceSpan;
+
+/// Ariadne report for an error found in parsed directives, e.g. an unbalanced transaction,
+/// pointing at the source location of the offending directive or posting.
+/// Render it with a cache mapping each [FileId](crate::model::FileId) to its file content.
+pub fn format_semantic_error<'a>(
+    message: impl ToString,
+    span: &SourceSpan,
+) -> Report<'a, SourceSpan> {
+    let message = message.to_string();
+    Report::build(ReportKind::Error, span.clone())
+        .with_config(Config::default().with_index_type(IndexType::Byte))
+        .with_message(&message)
+        .with_label(
+            Label::new(span.clone())
+                .with_message(message)
+                .with_color(ariadne::Color::Red),
+        )
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use ariadne::Source;
+
+    use super::*;
+    use crate::model::FileId;
+
+    #[test]
+    fn test_format_semantic_error() {
+        let source = "2024-01-01 open Assets:Cash\n2024-01-02 close Assets:Bank\n";
+        let span = SourceSpan::new(FileId::default(), 28..57);
+        let report = format_semantic_error("Account Assets:Bank is not open", &span);
+
+        let mut output = Vec::new();
+        report
+            .write((FileId::default(), Source::from(source)), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Account Assets:Bank is not open"));
+        assert!(output.contains("file #0:2:1"));
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+
+use ariadne::Report;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+use crate::{
+    model::{Amount, Commodity, Directive, DirectiveTransaction, Options, SourceSpan},
+    processing::format_semantic_error,
+};
+
+/// Upper bound for tolerances inferred from costs and prices, like in beancount
+const MAXIMUM_TOLERANCE: Decimal = dec!(0.5);
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UnbalancedTransactionErrorKind<'a> {
+    /// The amounts by which the transaction doesn't balance, one per commodity exceeding the tolerance
+    #[error("residual: {}", format_amounts(.0))]
+    Residual(Vec<Amount<'a>>),
+
+    #[error("the sum of its posting weights overflows")]
+    Overflow,
+}
+
+/// A transaction whose postings don't sum to zero in some commodities
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Transaction on {date} doesn't balance, {kind}")]
+pub struct UnbalancedTransactionError<'a> {
+    date: NaiveDate,
+    span: Option<SourceSpan>,
+    kind: UnbalancedTransactionErrorKind<'a>,
+}
+
+impl<'a> UnbalancedTransactionError<'a> {
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Source location of the transaction, if it was parsed from a file
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn kind(&self) -> &UnbalancedTransactionErrorKind<'a> {
+        &self.kind
+    }
+
+    pub fn report(&self) -> Option<Report<'static, SourceSpan>> {
+        self.span
+            .as_ref()
+            .map(|span| format_semantic_error(self, span))
+    }
+}
+
+fn format_amounts(amounts: &[Amount]) -> String {
+    amounts
+        .iter()
+        .map(|amount| format!("{} {}", amount.number(), amount.commodity()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check that the posting weights of every transaction sum to zero per commodity,
+/// up to the tolerance inferred for that commodity, see [infer_tolerances].
+///
+/// Transactions with a posting whose weight isn't known, e.g. a posting without amount that
+/// still has to be interpolated, are skipped. Transactions whose weights are too large to sum
+/// are reported as [UnbalancedTransactionErrorKind::Overflow].
+pub fn check_transaction_balances<'a>(
+    directives: &[Directive<'a>],
+    options: &Options,
+) -> Vec<UnbalancedTransactionError<'a>> {
+    directives
+        .iter()
+        .filter_map(|directive| {
+            let transaction = directive.as_transaction()?;
+            let kind = match transaction_residual(transaction) {
+                Ok(residual) => {
+                    let tolerances = infer_tolerances(transaction, options);
+                    let residual: Vec<Amount<'a>> = residual?
+                        .into_iter()
+                        .filter(|(commodity, number)| {
+                            number.abs() > tolerances.tolerance(commodity)
+                        })
+                        .map(|(commodity, number)| Amount::new(number, commodity))
+                        .collect();
+                    if residual.is_empty() {
+                        return None;
+                    }
+                    UnbalancedTransactionErrorKind::Residual(residual)
+                }
+                Err(kind) => kind,
+            };
+            Some(UnbalancedTransactionError {
+                date: *directive.date(),
+                span: directive.span().cloned(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Sum of the posting weights per commodity, None if the weight of a posting isn't known.
+/// Fails if a weight or the sum of weights overflows.
+fn transaction_residual<'a>(
+    transaction: &DirectiveTransaction<'a>,
+) -> Result<Option<BTreeMap<Commodity<'a>, Decimal>>, UnbalancedTransactionErrorKind<'a>> {
+    let mut residual: BTreeMap<Commodity<'a>, Decimal> = BTreeMap::new();
+    for posting in transaction.postings() {
+        let Some(amount) = posting.amount().filter(|amount| amount.is_complete()) else {
+            return Ok(None);
+        };
+        // the weight of a complete posting amount is only missing if it overflows
+        let weight = amount
+            .weight()
+            .ok_or(UnbalancedTransactionErrorKind::Overflow)?;
+        let sum = residual.entry(weight.commodity().clone()).or_default();
+        *sum = sum
+            .checked_add(*weight.number())
+            .ok_or(UnbalancedTransactionErrorKind::Overflow)?;
+    }
+    Ok(Some(residual))
+}
+
+/// Tolerances for checking the balance of a transaction, per commodity
+#[derive(Debug, Clone)]
+pub struct Tolerances<'a, 'o> {
+    inferred: BTreeMap<Commodity<'a>, Decimal>,
+    options: &'o Options<'o>,
+}
+
+impl Tolerances<'_, '_> {
+    /// The inferred tolerance for the commodity, or at least the `inferred_tolerance_default`
+    /// option for it. Without either, amounts must balance exactly.
+    pub fn tolerance(&self, commodity: &Commodity) -> Decimal {
+        let default = self
+            .options
+            .inferred_tolerance_default(commodity)
+            .unwrap_or_default();
+        self.inferred
+            .get(commodity)
+            .map_or(default, |inferred| (*inferred).max(default))
+    }
+}
+
+/// Infer the tolerances of a transaction from the precision of its numbers, like beancount.
+///
+/// Each posting's units with a fractional part allow a tolerance of `inferred_tolerance_multiplier`
+/// times their last digit, e.g. `0.005` for `12.34 USD`. Integer units don't allow any tolerance.
+/// Like in beancount, units filled in by interpolation are skipped.
+/// With `infer_tolerance_from_cost`, the units tolerance multiplied by the cost or price also
+/// applies to the cost or price commodity, up to [MAXIMUM_TOLERANCE].
+pub fn infer_tolerances<'a, 'o>(
+    transaction: &DirectiveTransaction<'a>,
+    options: &'o Options<'o>,
+) -> Tolerances<'a, 'o> {
+    let mut inferred: BTreeMap<Commodity<'a>, Decimal> = BTreeMap::new();
+    let mut cost_tolerances: BTreeMap<Commodity<'a>, Decimal> = BTreeMap::new();
+
+    for posting_amount in transaction
+        .postings()
+        .iter()
+        .filter(|posting| !posting.has_interpolated_units())
+        .filter_map(|posting| posting.amount())
+    {
+        let Some(units) = posting_amount.amount() else {
+            continue;
+        };
+        if units.number().scale() == 0 {
+            continue;
+        }
+        let tolerance =
+            Decimal::new(1, units.number().scale()) * options.inferred_tolerance_multiplier();
+        let max = inferred.entry(units.commodity().clone()).or_default();
+        *max = (*max).max(tolerance);
+
+        if !options.infer_tolerance_from_cost() {
+            continue;
+        }
+        if let Some(cost) = posting_amount.cost()
+            && let Some(commodity) = cost.commodity()
+        {
+            let cost_tolerance = [cost.total_cost(), cost.per_unit_cost()]
+                .into_iter()
+                .flatten()
+                .filter_map(|number| tolerance.checked_mul(*number))
+                .fold(MAXIMUM_TOLERANCE, Decimal::min);
+            *cost_tolerances.entry(commodity.clone()).or_default() += cost_tolerance;
+        }
+        if let Some(price) = posting_amount.per_unit_price()
+            && let Some(price_tolerance) = tolerance.checked_mul(*price.number())
+        {
+            *cost_tolerances
+                .entry(price.commodity().clone())
+                .or_default() += price_tolerance.min(MAXIMUM_TOLERANCE);
+        }
+    }
+
+    for (commodity, cost_tolerance) in cost_tolerances {
+        let max = inferred.entry(commodity).or_default();
+        *max = (*max).max(cost_tolerance);
+    }
+    Tolerances { inferred, options }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        Account, CostSpec, FileId, Flag, IncompleteAmount, PostingPrice, PragmaOption, account,
+        commodity,
+        directive::{Posting, PostingAmount},
+    };
+    use crate::processing::interpolate_transaction;
+    use rstest::rstest;
+
+    fn posting(
+        account: Account<'static>,
+        number: Decimal,
+        commodity: Commodity<'static>,
+    ) -> Posting<'static> {
+        posting_with(account, number, commodity, |amount| amount)
+    }
+
+    fn posting_with(
+        account: Account<'static>,
+        number: Decimal,
+        commodity: Commodity<'static>,
+        annotate: impl FnOnce(PostingAmount<'static>) -> PostingAmount<'static>,
+    ) -> Posting<'static> {
+        let amount = Amount::new(number, commodity);
+        Posting::new(account, annotate(PostingAmount::new(amount)))
+    }
+
+    fn transaction(postings: Vec<Posting<'static>>) -> Directive<'static> {
+        Directive::new_transaction(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            DirectiveTransaction::new(Flag::ASTERISK).with_postings(postings),
+        )
+    }
+
+    fn options(options: &[(&'static str, &'static str)]) -> Options<'static> {
+        Options::from_pragmas(
+            options
+                .iter()
+                .map(|(name, value)| PragmaOption::new(*name, *value)),
+        )
+        .unwrap()
+    }
+
+    fn residual(directive: &Directive<'static>, options: &Options) -> Option<Vec<Amount<'static>>> {
+        check_transaction_balances(std::slice::from_ref(directive), options)
+            .pop()
+            .map(|error| match error.kind() {
+                UnbalancedTransactionErrorKind::Residual(residual) => residual.clone(),
+                UnbalancedTransactionErrorKind::Overflow => panic!("unexpected overflow"),
+            })
+    }
+
+    #[test]
+    fn balanced_transaction() {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), dec!(-20.00), commodity!(USD)),
+            posting(account!(Expenses:Food), dec!(12.50), commodity!(USD)),
+            posting(account!(Expenses:Drinks), dec!(7.50), commodity!(USD)),
+        ]);
+
+        assert_eq!(residual(&directive, &Options::new()), None);
+    }
+
+    #[test]
+    fn unbalanced_transaction() {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), dec!(-20.00), commodity!(USD)),
+            posting(account!(Expenses:Food), dec!(12.50), commodity!(USD)),
+            posting(account!(Assets:Cash), dec!(-5), commodity!(EUR)),
+        ])
+        .with_span(SourceSpan::new(FileId::default(), 0..10));
+
+        let errors = check_transaction_balances(std::slice::from_ref(&directive), &Options::new());
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind(),
+            &UnbalancedTransactionErrorKind::Residual(vec![
+                Amount::new(dec!(-5), commodity!(EUR)),
+                Amount::new(dec!(-7.50), commodity!(USD)),
+            ])
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Transaction on 2024-03-01 doesn't balance, residual: -5 EUR, -7.50 USD"
+        );
+        assert_eq!(errors[0].span().unwrap().byte_range(), 0..10);
+        assert!(errors[0].report().is_some());
+    }
+
+    #[rstest]
+    #[case::within_inferred_tolerance(dec!(-10.004), None)]
+    #[case::at_inferred_tolerance(dec!(-10.005), None)]
+    #[case::beyond_inferred_tolerance(dec!(-10.006), Some(dec!(-0.006)))]
+    fn tolerance_from_precision(#[case] number: Decimal, #[case] expected: Option<Decimal>) {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), number, commodity!(USD)),
+            posting(account!(Expenses:Food), dec!(10.00), commodity!(USD)),
+        ]);
+
+        assert_eq!(
+            residual(&directive, &Options::new()),
+            expected.map(|number| vec![Amount::new(number, commodity!(USD))])
+        );
+    }
+
+    #[test]
+    fn integer_amounts_balance_exactly() {
+        let directive = transaction(vec![
+            posting(account!(Assets:Stock), dec!(10), commodity!(HOOL)),
+            posting(account!(Assets:Stock), dec!(-9), commodity!(HOOL)),
+        ]);
+        assert_eq!(
+            residual(&directive, &Options::new()),
+            Some(vec![Amount::new(dec!(1), commodity!(HOOL))])
+        );
+    }
+
+    #[test]
+    fn tolerance_options() {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), dec!(-10), commodity!(USD)),
+            posting(account!(Expenses:Food), dec!(10.02), commodity!(USD)),
+        ]);
+
+        assert!(residual(&directive, &Options::new()).is_some());
+        assert_eq!(
+            residual(
+                &directive,
+                &options(&[("inferred_tolerance_default", "USD:0.05")])
+            ),
+            None
+        );
+        assert_eq!(
+            residual(
+                &directive,
+                &options(&[("inferred_tolerance_multiplier", "3")])
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn weights_with_cost_and_price() {
+        let directive = transaction(vec![
+            posting_with(
+                account!(Assets:Stock),
+                dec!(10),
+                commodity!(HOOL),
+                |amount| amount.with_cost(CostSpec::per_unit(dec!(502.12), commodity!(USD))),
+            ),
+            posting_with(
+                account!(Assets:Cash),
+                dec!(-4000.00),
+                commodity!(EUR),
+                |amount| {
+                    amount.with_price(PostingPrice::Total(
+                        Amount::new(dec!(4400.00), commodity!(USD)).into(),
+                    ))
+                },
+            ),
+            posting(account!(Assets:Cash), dec!(-621.20), commodity!(USD)),
+        ]);
+
+        assert_eq!(residual(&directive, &Options::new()), None);
+    }
+
+    #[test]
+    fn tolerance_from_cost() {
+        let directive = transaction(vec![
+            posting_with(
+                account!(Assets:Stock),
+                dec!(1.5),
+                commodity!(HOOL),
+                |amount| amount.with_cost(CostSpec::per_unit(dec!(100), commodity!(USD))),
+            ),
+            posting(account!(Assets:Cash), dec!(-149.6), commodity!(USD)),
+        ]);
+
+        assert!(residual(&directive, &Options::new()).is_some());
+        assert_eq!(
+            residual(
+                &directive,
+                &options(&[("infer_tolerance_from_cost", "TRUE")])
+            ),
+            None,
+            "0.05 HOOL at 100 USD allow a tolerance of up to 0.5 USD"
+        );
+    }
+
+    #[test]
+    fn skips_transactions_to_interpolate() {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), dec!(-20.00), commodity!(USD)),
+            Posting::new_without_amount(account!(Expenses:Food)),
+        ]);
+        assert_eq!(residual(&directive, &Options::new()), None);
+
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), dec!(-20.00), commodity!(USD)),
+            Posting::new(
+                account!(Expenses:Food),
+                PostingAmount::new(IncompleteAmount::MissingNumber(commodity!(USD))),
+            ),
+        ]);
+        assert_eq!(residual(&directive, &Options::new()), None);
+    }
+
+    #[rstest]
+    #[case::sum(posting(account!(Assets:Cash), dec!(1), commodity!(USD)))]
+    #[case::weight(posting_with(
+        account!(Assets:Stock),
+        dec!(2),
+        commodity!(HOOL),
+        |amount| amount.with_cost(CostSpec::per_unit(Decimal::MAX, commodity!(USD))),
+    ))]
+    fn reports_overflowing_transactions(#[case] overflowing: Posting<'static>) {
+        let directive = transaction(vec![
+            posting(account!(Assets:Cash), Decimal::MAX, commodity!(USD)),
+            overflowing,
+        ])
+        .with_span(SourceSpan::new(FileId::default(), 0..10));
+
+        let errors = check_transaction_balances(std::slice::from_ref(&directive), &Options::new());
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), &UnbalancedTransactionErrorKind::Overflow);
+        assert_eq!(
+            errors[0].to_string(),
+            "Transaction on 2024-03-01 doesn't balance, the sum of its posting weights overflows"
+        );
+    }
+
+    #[test]
+    fn tolerance_skips_interpolated_units() {
+        let transaction = DirectiveTransaction::new(Flag::ASTERISK).with_postings(vec![
+            posting_with(
+                account!(Assets:Cash),
+                dec!(-100.00),
+                commodity!(EUR),
+                |amount| {
+                    amount.with_price(PostingPrice::PerUnit(
+                        Amount::new(dec!(1.0876543), commodity!(USD)).into(),
+                    ))
+                },
+            ),
+            Posting::new_without_amount(account!(Assets:Bank)),
+        ]);
+        let interpolated = interpolate_transaction(&transaction).unwrap();
+        assert_eq!(
+            interpolated.postings()[1].amount().unwrap().amount(),
+            Some(&Amount::new(dec!(108.765430000), commodity!(USD)))
+        );
+
+        let options = Options::new();
+        let tolerances = infer_tolerances(&interpolated, &options);
+
+        assert_eq!(tolerances.tolerance(&commodity!(EUR)), dec!(0.005));
+        assert_eq!(tolerances.tolerance(&commodity!(USD)), dec!(0));
+    }
+}